@@ -0,0 +1,525 @@
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::dialect::quote_literal;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Column types understood by every dialect.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DataType {
+    SmallInt,
+    Integer,
+    BigInt,
+    /// MySQL `INTEGER UNSIGNED`; widened on databases without unsigned types.
+    UnsignedInteger,
+    /// MySQL `BIGINT UNSIGNED`; widened on databases without unsigned types.
+    UnsignedBigInt,
+    /// Precision and scale.
+    Decimal(u32, u32),
+    Float,
+    Boolean,
+    Varchar(u32),
+    Text,
+    Date,
+    /// Time of day, with optional fractional-second digits.
+    Time(Option<u32>),
+    /// Date and time, with optional fractional-second digits.
+    Timestamp {
+        with_timezone: bool,
+        precision: Option<u32>,
+    },
+    Json,
+    /// Binary data with an optional maximum length in bytes.
+    Binary(Option<u32>),
+    /// A named set of allowed values. MySQL declares the values inline, while
+    /// Postgres creates a type called `name` first.
+    Enum {
+        name: String,
+        variants: Vec<String>,
+    },
+    /// A Postgres array of the inner type, which may be an array itself.
+    /// Other databases have no array types and store the values as
+    /// [`DataType::Json`] instead, so reads and writes must encode them.
+    Array(Box<DataType>),
+    /// Any other type, e.g. `vector(384)` from pgvector. Rendered verbatim by
+    /// every dialect.
+    Custom(String),
+}
+
+impl fmt::Display for DataType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataType::SmallInt => write!(f, "SMALLINT"),
+            DataType::Integer => write!(f, "INTEGER"),
+            DataType::BigInt => write!(f, "BIGINT"),
+            DataType::UnsignedInteger => write!(f, "INTEGER UNSIGNED"),
+            DataType::UnsignedBigInt => write!(f, "BIGINT UNSIGNED"),
+            DataType::Decimal(precision, scale) => write!(f, "DECIMAL({},{})", precision, scale),
+            DataType::Float => write!(f, "FLOAT"),
+            DataType::Boolean => write!(f, "BOOLEAN"),
+            DataType::Varchar(length) => write!(f, "VARCHAR({})", length),
+            DataType::Text => write!(f, "TEXT"),
+            DataType::Date => write!(f, "DATE"),
+            DataType::Time(precision) => write!(f, "TIME{}", Precision(*precision)),
+            DataType::Timestamp {
+                with_timezone,
+                precision,
+            } => write!(
+                f,
+                "TIMESTAMP{}{}",
+                Precision(*precision),
+                if *with_timezone {
+                    " WITH TIME ZONE"
+                } else {
+                    ""
+                }
+            ),
+            DataType::Json => write!(f, "JSON"),
+            // BLOB holds up to 64 KiB; anything larger or unbounded needs
+            // the 4 GiB LONGBLOB.
+            DataType::Binary(Some(length)) if *length <= 65_535 => write!(f, "BLOB"),
+            DataType::Binary(_) => write!(f, "LONGBLOB"),
+            DataType::Enum { variants, .. } => {
+                let variants: Vec<String> = variants.iter().map(|v| quote_literal(v)).collect();
+                write!(f, "ENUM({})", variants.join(","))
+            }
+            DataType::Array(inner) => write!(f, "{}[]", inner),
+            DataType::Custom(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// Parses the spelling produced by `Display`, ignoring case and extra
+/// whitespace, plus common synonyms such as `INT` and `NUMERIC(p,s)`.
+/// Anything else, including `ENUM(...)` whose type name is not part of its
+/// spelling, becomes [`DataType::Custom`] with the input as written.
+impl FromStr for DataType {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(DataType::from(s))
+    }
+}
+
+/// Parses like [`FromStr`], which also provides `TryFrom<&str>`.
+impl From<&str> for DataType {
+    fn from(s: &str) -> Self {
+        let normalized = s.split_whitespace().collect::<Vec<_>>().join(" ");
+        parse_data_type(&normalized.to_uppercase())
+            .unwrap_or_else(|| DataType::Custom(s.trim().to_string()))
+    }
+}
+
+/// Parses an uppercased type with single spaces, or `None` if it is not one
+/// of the built-in types.
+fn parse_data_type(s: &str) -> Option<DataType> {
+    if let Some(inner) = s.strip_suffix("[]") {
+        return Some(DataType::Array(Box::new(parse_data_type(
+            inner.trim_end(),
+        )?)));
+    }
+    // Split `NAME(args) suffix`, or `NAME suffix` without arguments.
+    let (name, args, suffix) = match s.find('(') {
+        Some(open) => {
+            let close = open + s[open..].find(')')?;
+            (
+                s[..open].trim_end(),
+                Some(&s[open + 1..close]),
+                s[close + 1..].trim_start(),
+            )
+        }
+        None => {
+            let (name, suffix) = s.split_once(' ').unwrap_or((s, ""));
+            (name, None, suffix)
+        }
+    };
+    let numbers = args.map(|args| {
+        args.split(',')
+            .map(|n| n.trim().parse::<u32>().ok())
+            .collect::<Option<Vec<u32>>>()
+    });
+    let precision = match numbers.as_ref().map(|n| n.as_deref()) {
+        None => Some(None),
+        Some(Some([precision])) => Some(Some(*precision)),
+        Some(_) => None,
+    };
+
+    let data_type = match (name, suffix) {
+        ("SMALLINT", "") if args.is_none() => DataType::SmallInt,
+        ("INTEGER" | "INT", "") if args.is_none() => DataType::Integer,
+        ("INTEGER" | "INT", "UNSIGNED") if args.is_none() => DataType::UnsignedInteger,
+        ("BIGINT", "") if args.is_none() => DataType::BigInt,
+        ("BIGINT", "UNSIGNED") if args.is_none() => DataType::UnsignedBigInt,
+        ("DECIMAL" | "NUMERIC", "") => match numbers??.as_slice() {
+            [precision] => DataType::Decimal(*precision, 0),
+            [precision, scale] => DataType::Decimal(*precision, *scale),
+            _ => return None,
+        },
+        ("FLOAT", "") if args.is_none() => DataType::Float,
+        ("BOOLEAN" | "BOOL", "") if args.is_none() => DataType::Boolean,
+        ("VARCHAR", "") => DataType::Varchar(precision??),
+        ("TEXT", "") if args.is_none() => DataType::Text,
+        ("DATE", "") if args.is_none() => DataType::Date,
+        ("TIME", "") => DataType::Time(precision?),
+        ("TIMESTAMP", "" | "WITHOUT TIME ZONE" | "WITH TIME ZONE") => DataType::Timestamp {
+            with_timezone: suffix == "WITH TIME ZONE",
+            precision: precision?,
+        },
+        ("JSON", "") if args.is_none() => DataType::Json,
+        ("BLOB", "") if args.is_none() => DataType::Binary(Some(65_535)),
+        ("LONGBLOB", "") if args.is_none() => DataType::Binary(None),
+        _ => return None,
+    };
+    Some(data_type)
+}
+
+/// A column default.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DefaultValue {
+    /// A string, quoted when rendered.
+    Literal(String),
+    Integer(i64),
+    /// Raw SQL such as `now()` or `CURRENT_TIMESTAMP`, rendered as-is.
+    Expression(String),
+}
+
+impl fmt::Display for DefaultValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DefaultValue::Literal(value) => write!(f, "{}", quote_literal(value)),
+            DefaultValue::Integer(value) => write!(f, "{}", value),
+            DefaultValue::Expression(sql) => write!(f, "{}", sql),
+        }
+    }
+}
+
+/// Renders an optional fractional-second precision as `(p)`, or nothing.
+pub(crate) struct Precision(pub Option<u32>);
+
+impl fmt::Display for Precision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(precision) => write!(f, "({})", precision),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Column {
+    pub name: String,
+    pub data_type: DataType,
+    pub nullable: bool,
+    /// Fill the column from a counter when no value is written: MySQL's
+    /// `AUTO_INCREMENT`, a Postgres identity, SQL Server's `IDENTITY` or
+    /// SQLite's `AUTOINCREMENT`. Meant for integer keys.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub auto_increment: bool,
+    /// Computes the column from other columns instead of storing written
+    /// values.
+    pub generated: Option<GeneratedColumn>,
+    /// See [`ColumnOptions::collation`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub collation: Option<String>,
+}
+
+impl Column {
+    /// Starts a nullable `TEXT` column called `name`.
+    pub fn builder(name: &str) -> ColumnBuilder {
+        ColumnBuilder {
+            column: Column {
+                name: name.to_string(),
+                data_type: DataType::Text,
+                nullable: true,
+                auto_increment: false,
+                generated: None,
+                collation: None,
+            },
+            default: None,
+            unique: false,
+        }
+    }
+}
+
+/// Builds a [`Column`] together with the [`ColumnOptions`] that describe the
+/// same column, e.g.
+/// `Column::builder("email").data_type(DataType::Text).not_null().unique()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnBuilder {
+    column: Column,
+    default: Option<String>,
+    unique: bool,
+}
+
+impl ColumnBuilder {
+    pub fn data_type(mut self, data_type: DataType) -> Self {
+        self.column.data_type = data_type;
+        self
+    }
+
+    pub fn not_null(mut self) -> Self {
+        self.column.nullable = false;
+        self
+    }
+
+    pub fn auto_increment(mut self) -> Self {
+        self.column.auto_increment = true;
+        self
+    }
+
+    pub fn generated(mut self, expression: &str, stored: bool) -> Self {
+        self.column.generated = Some(GeneratedColumn {
+            expression: expression.to_string(),
+            stored,
+        });
+        self
+    }
+
+    pub fn collation(mut self, collation: &str) -> Self {
+        self.column.collation = Some(collation.to_string());
+        self
+    }
+
+    /// The default as raw SQL, e.g. `'x'` or `now()`. Only kept in
+    /// [`ColumnBuilder::options`].
+    pub fn default(mut self, default: &str) -> Self {
+        self.default = Some(default.to_string());
+        self
+    }
+
+    /// Only kept in [`ColumnBuilder::options`].
+    pub fn unique(mut self) -> Self {
+        self.unique = true;
+        self
+    }
+
+    pub fn build(self) -> Column {
+        self.column
+    }
+
+    /// The options setting this column's nullability, default, uniqueness
+    /// and collation, e.g. for a [`ChangeColumnType`].
+    ///
+    /// [`ChangeColumnType`]: crate::migration::ChangeColumnType
+    pub fn options(&self) -> ColumnOptions {
+        ColumnOptions {
+            nullable: Some(self.column.nullable),
+            default: self.default.clone(),
+            unique: self.unique.then_some(true),
+            collation: self.column.collation.clone(),
+            using: None,
+            concurrently: false,
+        }
+    }
+}
+
+/// A generated column's expression, passed through as raw SQL. Virtual
+/// columns (`stored: false`) are computed on read; Postgres has none and
+/// stores them instead.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GeneratedColumn {
+    pub expression: String,
+    pub stored: bool,
+}
+
+impl fmt::Display for GeneratedColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "GENERATED ALWAYS AS ({}) {}",
+            self.expression,
+            if self.stored { "STORED" } else { "VIRTUAL" }
+        )
+    }
+}
+
+/// Extra attributes applied when a column's type is changed.
+///
+/// `None` leaves the corresponding attribute untouched.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ColumnOptions {
+    pub nullable: Option<bool>,
+    pub default: Option<String>,
+    pub unique: Option<bool>,
+    /// e.g. `en_US` on Postgres or `utf8mb4_general_ci` on MySQL.
+    pub collation: Option<String>,
+    /// Expression converting existing values to the new type, e.g.
+    /// `age::integer`. Only Postgres uses it.
+    pub using: Option<String>,
+    /// Build the unique index without blocking writes, where supported.
+    /// The statement then cannot run inside a transaction.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub concurrently: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_base_types() {
+        assert_eq!(DataType::from_str("INTEGER"), Ok(DataType::Integer));
+        assert_eq!("int".parse(), Ok(DataType::Integer));
+        assert_eq!(DataType::from("bigint  unsigned"), DataType::UnsignedBigInt);
+        assert_eq!("TEXT".parse(), Ok(DataType::Text));
+    }
+
+    #[test]
+    fn parses_parameterized_types() {
+        assert_eq!("DECIMAL(10,2)".parse(), Ok(DataType::Decimal(10, 2)));
+        assert_eq!("numeric(10, 2)".parse(), Ok(DataType::Decimal(10, 2)));
+        assert_eq!("VARCHAR(255)".parse(), Ok(DataType::Varchar(255)));
+        assert_eq!("TIME(3)".parse(), Ok(DataType::Time(Some(3))));
+        assert_eq!(
+            "integer []".parse(),
+            Ok(DataType::Array(Box::new(DataType::Integer)))
+        );
+        assert_eq!(
+            "TIMESTAMP(6) WITH TIME ZONE".parse(),
+            Ok(DataType::Timestamp {
+                with_timezone: true,
+                precision: Some(6)
+            })
+        );
+    }
+
+    #[test]
+    fn keeps_unknown_types_as_custom() {
+        assert_eq!(
+            "vector(384)".parse(),
+            Ok(DataType::Custom("vector(384)".to_string()))
+        );
+        assert_eq!(
+            "VARCHAR(max)".parse(),
+            Ok(DataType::Custom("VARCHAR(max)".to_string()))
+        );
+    }
+
+    #[test]
+    fn round_trips_through_display() {
+        let types = vec![
+            DataType::SmallInt,
+            DataType::UnsignedInteger,
+            DataType::Decimal(12, 4),
+            DataType::Float,
+            DataType::Boolean,
+            DataType::Date,
+            DataType::Time(None),
+            DataType::Timestamp {
+                with_timezone: false,
+                precision: Some(3),
+            },
+            DataType::Json,
+            DataType::Binary(None),
+            DataType::Array(Box::new(DataType::Varchar(20))),
+            DataType::Array(Box::new(DataType::Array(Box::new(DataType::Text)))),
+        ];
+        for data_type in types {
+            assert_eq!(data_type.to_string().parse(), Ok(data_type));
+        }
+    }
+
+    #[test]
+    fn builds_columns_with_their_options() {
+        let builder = Column::builder("email")
+            .data_type(DataType::Varchar(320))
+            .not_null()
+            .default("''")
+            .unique();
+        assert_eq!(
+            builder.options(),
+            ColumnOptions {
+                nullable: Some(false),
+                default: Some("''".to_string()),
+                unique: Some(true),
+                ..ColumnOptions::default()
+            }
+        );
+        assert_eq!(
+            builder.build(),
+            Column {
+                name: "email".to_string(),
+                data_type: DataType::Varchar(320),
+                nullable: false,
+                auto_increment: false,
+                generated: None,
+                collation: None,
+            }
+        );
+        assert!(Column::builder("bio").build().nullable);
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use super::*;
+
+    #[test]
+    fn data_type_round_trips_through_serde() {
+        let types = vec![
+            DataType::SmallInt,
+            DataType::Integer,
+            DataType::BigInt,
+            DataType::UnsignedInteger,
+            DataType::UnsignedBigInt,
+            DataType::Decimal(10, 2),
+            DataType::Float,
+            DataType::Boolean,
+            DataType::Varchar(255),
+            DataType::Text,
+            DataType::Date,
+            DataType::Time(Some(3)),
+            DataType::Timestamp {
+                with_timezone: true,
+                precision: Some(6),
+            },
+            DataType::Json,
+            DataType::Binary(Some(32)),
+            DataType::Binary(None),
+            DataType::Enum {
+                name: "user_status".to_string(),
+                variants: vec!["active".to_string(), "banned".to_string()],
+            },
+            DataType::Custom("vector(384)".to_string()),
+        ];
+        let json = serde_json::to_string(&types).unwrap();
+        let parsed: Vec<DataType> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, types);
+    }
+
+    #[test]
+    fn column_and_options_round_trip_through_serde() {
+        let column = Column {
+            name: "email".to_string(),
+            data_type: DataType::Varchar(255),
+            nullable: false,
+            auto_increment: false,
+            generated: None,
+            collation: None,
+        };
+        let json = serde_json::to_string(&column).unwrap();
+        assert_eq!(serde_json::from_str::<Column>(&json).unwrap(), column);
+
+        let options = ColumnOptions {
+            nullable: Some(false),
+            default: Some("''".to_string()),
+            unique: Some(true),
+            collation: None,
+            using: None,
+            concurrently: false,
+        };
+        let json = serde_json::to_string(&options).unwrap();
+        assert_eq!(
+            serde_json::from_str::<ColumnOptions>(&json).unwrap(),
+            options
+        );
+    }
+}
@@ -0,0 +1,395 @@
+use std::sync::Arc;
+
+use super::keyword::sql;
+use super::{
+    DefaultNaming, Dialect, Feature, KeywordCase, NamingStrategy, PostgresDialect, QuoteMode,
+    TypeAliases,
+};
+use crate::data_type::{Column, ColumnOptions, DataType};
+use crate::error::MigrationError;
+use crate::migration::{
+    AddEnumValue, ColumnPosition, CreateFunction, CreateIndex, CreateSequence, DropFunction,
+    TableOptions,
+};
+
+/// CockroachDB speaks the Postgres dialect; only the differences are
+/// implemented here and everything else defers to [`PostgresDialect`].
+///
+/// Table partitions are declared with the table rather than attached, so
+/// the partition operations return [`MigrationError::Unsupported`], as do
+/// conditional operations, which need `DO` blocks.
+#[derive(Debug, Clone, Default)]
+pub struct CockroachDialect {
+    type_aliases: TypeAliases,
+    quote_mode: QuoteMode,
+    keyword_case: KeywordCase,
+    naming: Option<Arc<dyn NamingStrategy>>,
+}
+
+impl CockroachDialect {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_quote_mode(mut self, quote_mode: QuoteMode) -> Self {
+        self.quote_mode = quote_mode;
+        self
+    }
+
+    pub fn with_keyword_case(mut self, keyword_case: KeywordCase) -> Self {
+        self.keyword_case = keyword_case;
+        self
+    }
+
+    /// Names the indexes and constraints migrations leave unnamed.
+    pub fn with_naming_strategy(mut self, strategy: impl NamingStrategy + 'static) -> Self {
+        self.naming = Some(Arc::new(strategy));
+        self
+    }
+
+    /// The Postgres dialect statements are delegated to, with the same type
+    /// aliases and naming strategy.
+    fn postgres(&self) -> PostgresDialect {
+        let mut postgres = PostgresDialect::new()
+            .with_quote_mode(self.quote_mode)
+            .with_keyword_case(self.keyword_case)
+            .with_naming(self.naming.clone());
+        *postgres.type_aliases_mut() = self.type_aliases.clone();
+        postgres
+    }
+}
+
+impl Dialect for CockroachDialect {
+    fn name(&self) -> &str {
+        "cockroach"
+    }
+
+    fn type_aliases(&self) -> &TypeAliases {
+        &self.type_aliases
+    }
+
+    fn type_aliases_mut(&mut self) -> &mut TypeAliases {
+        &mut self.type_aliases
+    }
+
+    fn quote_mode(&self) -> QuoteMode {
+        self.quote_mode
+    }
+
+    fn keyword_case(&self) -> KeywordCase {
+        self.keyword_case
+    }
+
+    fn naming_strategy(&self) -> &dyn NamingStrategy {
+        self.naming.as_deref().unwrap_or(&DefaultNaming)
+    }
+
+    fn max_identifier_length(&self) -> Option<usize> {
+        self.postgres().max_identifier_length()
+    }
+
+    fn render_type(&self, data_type: &DataType) -> String {
+        self.postgres().render_type(data_type)
+    }
+
+    fn supports(&self, feature: Feature) -> bool {
+        // Index builds never block writes, so there is nothing to ask for
+        // concurrently. Schema changes run after their transaction commits
+        // and are not rolled back with it.
+        !matches!(
+            feature,
+            Feature::ConditionalIndex | Feature::ConcurrentIndex | Feature::TransactionalDdl
+        ) && self.postgres().supports(feature)
+    }
+
+    fn placeholder(&self, index: usize) -> String {
+        self.postgres().placeholder(index)
+    }
+
+    fn column_definition(&self, column: &Column) -> String {
+        // Identities are backed by a sequence, unlike SERIAL, which defaults
+        // to unordered unique_rowid() values here.
+        self.postgres().column_definition(column)
+    }
+
+    fn add_column(
+        &self,
+        table: &str,
+        column: &Column,
+        position: Option<&ColumnPosition>,
+        if_not_exists: bool,
+    ) -> Vec<String> {
+        self.postgres()
+            .add_column(table, column, position, if_not_exists)
+    }
+
+    fn drop_column(&self, table: &str, column: &str, if_exists: bool, cascade: bool) -> String {
+        self.postgres()
+            .drop_column(table, column, if_exists, cascade)
+    }
+
+    fn rename_column(
+        &self,
+        table: &str,
+        old_name: &str,
+        new_name: &str,
+        definition: Option<&Column>,
+    ) -> String {
+        self.postgres()
+            .rename_column(table, old_name, new_name, definition)
+    }
+
+    fn type_conversion(&self, column: &str, from: &DataType, to: &DataType) -> Option<String> {
+        self.postgres().type_conversion(column, from, to)
+    }
+
+    fn change_column_type(
+        &self,
+        table: &str,
+        column: &str,
+        data_type: &DataType,
+        options: &ColumnOptions,
+        position: Option<&ColumnPosition>,
+    ) -> Result<Vec<String>, MigrationError> {
+        // Type changes that rewrite the column are still experimental and
+        // must be enabled per session. They cannot run inside an explicit
+        // transaction either.
+        let options = ColumnOptions {
+            concurrently: false,
+            ..options.clone()
+        };
+        let mut statements = vec![sql!(
+            self,
+            "SET enable_experimental_alter_column_type_general = true;"
+        )];
+        statements.extend(
+            self.postgres()
+                .change_column_type(table, column, data_type, &options, position)?,
+        );
+        Ok(statements)
+    }
+
+    fn update_column_data_batch(
+        &self,
+        table: &str,
+        id_column: &str,
+        column: &str,
+        rows: &[(String, String)],
+    ) -> String {
+        self.postgres()
+            .update_column_data_batch(table, id_column, column, rows)
+    }
+
+    fn insert_rows(
+        &self,
+        table: &str,
+        columns: &[String],
+        rows: &[Vec<String>],
+        on_conflict_do_nothing: bool,
+    ) -> String {
+        self.postgres()
+            .insert_rows(table, columns, rows, on_conflict_do_nothing)
+    }
+
+    fn select_column_data(&self, table: &str, id_column: &str, column: &str) -> String {
+        self.postgres().select_column_data(table, id_column, column)
+    }
+
+    fn create_index(&self, table: &str, index: &CreateIndex) -> String {
+        // Without a row estimate to branch on, conditional_on_rows is ignored.
+        let index = CreateIndex {
+            conditional_on_rows: None,
+            concurrently: false,
+            ..index.clone()
+        };
+        self.postgres().create_index(table, &index)
+    }
+
+    fn create_table(
+        &self,
+        table: &str,
+        columns: &[Column],
+        primary_key: &[String],
+        options: &TableOptions,
+    ) -> Vec<String> {
+        self.postgres()
+            .create_table(table, columns, primary_key, options)
+    }
+
+    fn drop_table(&self, table: &str, if_exists: bool, cascade: bool) -> String {
+        self.postgres().drop_table(table, if_exists, cascade)
+    }
+
+    fn create_sequence(
+        &self,
+        table: &str,
+        sequence: &CreateSequence,
+    ) -> Result<String, MigrationError> {
+        self.postgres().create_sequence(table, sequence)
+    }
+
+    fn drop_sequence(&self, name: &str, if_exists: bool) -> Result<String, MigrationError> {
+        self.postgres().drop_sequence(name, if_exists)
+    }
+
+    fn create_enum_type(&self, name: &str, variants: &[String]) -> Result<String, MigrationError> {
+        self.postgres().create_enum_type(name, variants)
+    }
+
+    fn add_enum_value(&self, table: &str, add: &AddEnumValue) -> Result<String, MigrationError> {
+        self.postgres().add_enum_value(table, add)
+    }
+
+    fn rename_enum_value(
+        &self,
+        type_name: &str,
+        old_value: &str,
+        new_value: &str,
+    ) -> Result<String, MigrationError> {
+        self.postgres()
+            .rename_enum_value(type_name, old_value, new_value)
+    }
+
+    fn create_materialized_view(
+        &self,
+        name: &str,
+        query: &str,
+        with_data: bool,
+    ) -> Result<String, MigrationError> {
+        self.postgres()
+            .create_materialized_view(name, query, with_data)
+    }
+
+    fn refresh_materialized_view(
+        &self,
+        name: &str,
+        concurrently: bool,
+    ) -> Result<String, MigrationError> {
+        self.postgres()
+            .refresh_materialized_view(name, concurrently)
+    }
+
+    fn drop_materialized_view(
+        &self,
+        name: &str,
+        if_exists: bool,
+    ) -> Result<String, MigrationError> {
+        self.postgres().drop_materialized_view(name, if_exists)
+    }
+
+    fn add_identity(
+        &self,
+        table: &str,
+        column: &str,
+        always: bool,
+        start: Option<i64>,
+        current_type: Option<&DataType>,
+    ) -> Result<Vec<String>, MigrationError> {
+        self.postgres()
+            .add_identity(table, column, always, start, current_type)
+    }
+
+    fn drop_identity(
+        &self,
+        table: &str,
+        column: &str,
+        current_type: Option<&DataType>,
+    ) -> Result<String, MigrationError> {
+        self.postgres().drop_identity(table, column, current_type)
+    }
+
+    fn restart_identity(
+        &self,
+        table: &str,
+        column: &str,
+        start: i64,
+    ) -> Result<String, MigrationError> {
+        self.postgres().restart_identity(table, column, start)
+    }
+
+    fn create_schema(&self, name: &str, if_not_exists: bool) -> Result<String, MigrationError> {
+        self.postgres().create_schema(name, if_not_exists)
+    }
+
+    fn drop_schema(&self, name: &str) -> Result<String, MigrationError> {
+        self.postgres().drop_schema(name)
+    }
+
+    fn create_function(&self, function: &CreateFunction) -> Result<Vec<String>, MigrationError> {
+        self.postgres().create_function(function)
+    }
+
+    fn drop_function(&self, function: &DropFunction) -> Result<String, MigrationError> {
+        self.postgres().drop_function(function)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shares_postgres_output_elsewhere() {
+        let column = Column {
+            name: "email".to_string(),
+            data_type: DataType::Text,
+            nullable: true,
+            auto_increment: false,
+            generated: None,
+            collation: None,
+        };
+        assert_eq!(
+            CockroachDialect::new().add_column("users", &column, None, true),
+            PostgresDialect::new().add_column("users", &column, None, true)
+        );
+    }
+
+    #[test]
+    fn enables_general_type_changes_first() {
+        assert_eq!(
+            CockroachDialect::new()
+                .change_column_type(
+                    "users",
+                    "age",
+                    &DataType::BigInt,
+                    &ColumnOptions::default(),
+                    None
+                )
+                .unwrap(),
+            vec![
+                "SET enable_experimental_alter_column_type_general = true;",
+                "ALTER TABLE users ALTER COLUMN age TYPE BIGINT;",
+            ]
+        );
+    }
+
+    #[test]
+    fn builds_indexes_online_without_row_checks() {
+        let index = CreateIndex {
+            name: None,
+            columns: vec!["email".into()],
+            unique: true,
+            conditional_on_rows: Some(1000),
+            concurrently: true,
+            if_not_exists: false,
+            where_clause: None,
+        };
+        let dialect = CockroachDialect::new();
+        assert_eq!(
+            dialect.create_index("users", &index),
+            "CREATE UNIQUE INDEX idx_users_email ON users (email);"
+        );
+        assert!(!dialect.supports(Feature::ConcurrentIndex));
+        assert!(dialect.supports(Feature::DropCascade));
+    }
+
+    #[test]
+    fn resolves_its_own_type_aliases() {
+        let mut dialect = CockroachDialect::new();
+        dialect.register_type_alias("money", "DECIMAL(19, 4)");
+        assert_eq!(
+            dialect.render_type(&DataType::Custom("money".to_string())),
+            "DECIMAL(19, 4)"
+        );
+    }
+}
@@ -0,0 +1,213 @@
+use std::borrow::Cow;
+use std::fmt;
+
+/// How [`Dialect`](super::Dialect) writes keywords and type names.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KeywordCase {
+    /// As the dialects spell them.
+    #[default]
+    Upper,
+    Lower,
+}
+
+impl KeywordCase {
+    /// `sql` with its keywords in this case.
+    ///
+    /// The dialects write every keyword and type name in uppercase and quote
+    /// any identifier that is not lowercase, so keywords are the words written
+    /// entirely in uppercase outside quotes, literals, comments and
+    /// dollar-quoted bodies. Only meant for SQL the dialect wrote itself.
+    pub(crate) fn apply<'a>(&self, sql: &'a str) -> Cow<'a, str> {
+        if *self == KeywordCase::Upper {
+            return Cow::Borrowed(sql);
+        }
+        let mut formatted = String::with_capacity(sql.len());
+        let mut rest = sql;
+        while let Some(c) = rest.chars().next() {
+            let len = match c {
+                '\'' | '"' | '`' => quoted_len(rest, c),
+                '[' => quoted_len(rest, ']'),
+                '-' if rest.starts_with("--") => rest.find('\n').unwrap_or(rest.len()),
+                '$' => dollar_quoted_len(rest).unwrap_or(1),
+                c if c.is_ascii_alphabetic() || c == '_' => {
+                    let len = word_len(rest);
+                    let word = &rest[..len];
+                    if is_uppercase(word) {
+                        formatted.push_str(&word.to_ascii_lowercase());
+                    } else {
+                        formatted.push_str(word);
+                    }
+                    rest = &rest[len..];
+                    continue;
+                }
+                c => c.len_utf8(),
+            };
+            formatted.push_str(&rest[..len]);
+            rest = &rest[len..];
+        }
+        Cow::Owned(formatted)
+    }
+}
+
+/// A [`sql!`] argument.
+pub(crate) trait SqlArg {
+    fn write_to(&self, case: KeywordCase, sql: &mut String);
+}
+
+impl<T: fmt::Display + ?Sized> SqlArg for T {
+    fn write_to(&self, case: KeywordCase, sql: &mut String) {
+        sql.push_str(&case.apply(&self.to_string()));
+    }
+}
+
+/// A [`sql!`] argument written as given: SQL from the caller, such as an
+/// expression or a view's query, or SQL rendered earlier that may hold some.
+pub(crate) struct Verbatim<T>(pub T);
+
+impl<T: fmt::Display> SqlArg for Verbatim<T> {
+    fn write_to(&self, _case: KeywordCase, sql: &mut String) {
+        sql.push_str(&self.0.to_string());
+    }
+}
+
+/// Fills the `{}`s of `template` with `args` in order, with the keywords of
+/// both in `case`. See [`sql!`].
+pub(crate) fn render(case: KeywordCase, template: &str, args: &[&dyn SqlArg]) -> String {
+    let template = case.apply(template);
+    let mut pieces = template.split("{}");
+    let mut sql = String::with_capacity(template.len());
+    sql.push_str(pieces.next().unwrap_or_default());
+    let mut args = args.iter();
+    for piece in pieces {
+        args.next()
+            .expect("more {} than arguments in SQL template")
+            .write_to(case, &mut sql);
+        sql.push_str(piece);
+    }
+    debug_assert!(
+        args.next().is_none(),
+        "more arguments than {{}} in SQL template"
+    );
+    sql
+}
+
+/// `format!` for SQL written by a dialect, putting the keywords of the
+/// template and arguments in the dialect's [`KeywordCase`]. Only `{}` is
+/// supported. Wrap arguments holding SQL from the caller in [`Verbatim`].
+macro_rules! sql {
+    ($dialect:expr, $template:literal $(, $arg:expr)* $(,)?) => {
+        $crate::dialect::keyword::render(
+            $dialect.keyword_case(),
+            $template,
+            &[$(&$arg as &dyn $crate::dialect::keyword::SqlArg),*],
+        )
+    };
+}
+
+pub(crate) use sql;
+
+/// Length of the quoted text at the start of `sql`, up to and including the
+/// first `close`. A doubled quote is read as two adjacent quoted texts,
+/// which covers it the same way.
+pub(crate) fn quoted_len(sql: &str, close: char) -> usize {
+    match sql[1..].find(close) {
+        Some(end) => end + 1 + close.len_utf8(),
+        None => sql.len(),
+    }
+}
+
+/// Length of the `$tag$...$tag$` body at the start of `sql`, if it starts
+/// one rather than a placeholder such as `$1`.
+pub(crate) fn dollar_quoted_len(sql: &str) -> Option<usize> {
+    let tag_len = sql[1..].find('$')? + 2;
+    let tag = &sql[..tag_len];
+    if !tag[1..tag_len - 1]
+        .chars()
+        .all(|c| c.is_ascii_alphabetic() || c == '_')
+    {
+        return None;
+    }
+    let end = sql[tag_len..].find(tag)?;
+    Some(tag_len + end + tag_len)
+}
+
+/// Length of the word at the start of `sql`.
+pub(crate) fn word_len(sql: &str) -> usize {
+    sql.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(sql.len())
+}
+
+fn is_uppercase(word: &str) -> bool {
+    word.bytes().any(|b| b.is_ascii_uppercase()) && !word.bytes().any(|b| b.is_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_type::{Column, DataType};
+    use crate::dialect::PostgresDialect;
+    use crate::migration::{
+        AddColumn, Migration, Operator, UpdateColumnData, UpdateValue, WhereCondition,
+    };
+
+    #[test]
+    fn lowercases_keywords_outside_quotes_and_bodies() {
+        assert_eq!(
+            KeywordCase::Lower.apply(
+                "DO $body$ BEGIN NULL; END $body$ -- KEEP\nSELECT 'A', \"B\", `C`, [D], $1 FROM t"
+            ),
+            "do $body$ BEGIN NULL; END $body$ -- KEEP\nselect 'A', \"B\", `C`, [D], $1 from t"
+        );
+    }
+
+    #[test]
+    fn writes_verbatim_arguments_as_given() {
+        assert_eq!(
+            render(
+                KeywordCase::Lower,
+                "ALTER TABLE {} ADD CHECK ({}){};",
+                &[&"orders", &Verbatim("TOTAL > 0"), &" NOT VALID"],
+            ),
+            "alter table orders add check (TOTAL > 0) not valid;"
+        );
+    }
+
+    #[test]
+    fn lowercases_only_the_dialects_keywords() {
+        let dialect = PostgresDialect::new().with_keyword_case(KeywordCase::Lower);
+        let mut migration = Migration::new("users", Box::new(dialect));
+        migration
+            .add_operation(AddColumn {
+                column: Column::builder("Status")
+                    .data_type(DataType::Varchar(20))
+                    .not_null()
+                    .collation("C")
+                    .build(),
+                position: None,
+                if_not_exists: false,
+            })
+            .add_operation(UpdateColumnData {
+                column: "Status".to_string(),
+                value: UpdateValue::Fixed("'INACTIVE'".to_string()),
+                conditions: vec![WhereCondition {
+                    column: "last_login".to_string(),
+                    operator: Operator::Lt,
+                    value: UpdateValue::Fixed("NOW() - INTERVAL '1 YEAR'".to_string()),
+                }],
+            });
+        let statements: Vec<String> = migration
+            .generate_sql()
+            .unwrap()
+            .into_iter()
+            .map(|statement| statement.sql)
+            .collect();
+        assert_eq!(
+            statements,
+            [
+                "alter table users add column \"Status\" varchar(20) collate \"C\" not null;",
+                "update users set \"Status\" = 'INACTIVE' \
+                 where last_login < NOW() - INTERVAL '1 YEAR';",
+            ]
+        );
+    }
+}
@@ -0,0 +1,431 @@
+use std::sync::Arc;
+
+use super::keyword::sql;
+use super::{
+    mysql, DefaultNaming, Dialect, Feature, KeywordCase, MySqlDialect, NamingStrategy, QuoteMode,
+    TypeAliases,
+};
+use crate::data_type::{Column, ColumnOptions, DataType, DefaultValue};
+use crate::error::MigrationError;
+use crate::migration::{
+    AddEnumValue, ColumnPosition, CreateFunction, CreateIndex, DropFunction, Operator,
+    PartitionBound, Precondition, TableOptions,
+};
+
+/// MariaDB shares most of its syntax with MySQL; only the differences are
+/// implemented here and everything else defers to [`MySqlDialect`].
+#[derive(Debug, Clone, Default)]
+pub struct MariaDbDialect {
+    type_aliases: TypeAliases,
+    quote_mode: QuoteMode,
+    keyword_case: KeywordCase,
+    naming: Option<Arc<dyn NamingStrategy>>,
+}
+
+impl MariaDbDialect {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_quote_mode(mut self, quote_mode: QuoteMode) -> Self {
+        self.quote_mode = quote_mode;
+        self
+    }
+
+    pub fn with_keyword_case(mut self, keyword_case: KeywordCase) -> Self {
+        self.keyword_case = keyword_case;
+        self
+    }
+
+    /// Names the indexes and constraints migrations leave unnamed.
+    pub fn with_naming_strategy(mut self, strategy: impl NamingStrategy + 'static) -> Self {
+        self.naming = Some(Arc::new(strategy));
+        self
+    }
+
+    /// The MySQL dialect shared statements are delegated to.
+    fn mysql(&self) -> MySqlDialect {
+        MySqlDialect::new()
+            .with_quote_mode(self.quote_mode)
+            .with_keyword_case(self.keyword_case)
+            .with_naming(self.naming.clone())
+    }
+}
+
+impl Dialect for MariaDbDialect {
+    fn name(&self) -> &str {
+        "mariadb"
+    }
+
+    fn type_aliases(&self) -> &TypeAliases {
+        &self.type_aliases
+    }
+
+    fn type_aliases_mut(&mut self) -> &mut TypeAliases {
+        &mut self.type_aliases
+    }
+
+    fn quote_mode(&self) -> QuoteMode {
+        self.quote_mode
+    }
+
+    fn keyword_case(&self) -> KeywordCase {
+        self.keyword_case
+    }
+
+    fn naming_strategy(&self) -> &dyn NamingStrategy {
+        self.naming.as_deref().unwrap_or(&DefaultNaming)
+    }
+
+    fn max_identifier_length(&self) -> Option<usize> {
+        self.mysql().max_identifier_length()
+    }
+
+    fn render_type(&self, data_type: &DataType) -> String {
+        match data_type {
+            // MariaDB's JSON is only an alias, spell out what it stores.
+            DataType::Json | DataType::Array(_) => "LONGTEXT".to_string(),
+            DataType::Custom(name) => self.type_aliases.resolve(name),
+            other => self.mysql().render_type(other),
+        }
+    }
+
+    fn supports(&self, feature: Feature) -> bool {
+        // The column and index guards date back to MariaDB 10.0.2 and
+        // 10.1.4.
+        matches!(
+            feature,
+            Feature::AddColumnIfNotExists
+                | Feature::DropColumnIfExists
+                | Feature::CreateIndexIfNotExists
+                | Feature::DropIndexIfExists
+        ) || self.mysql().supports(feature)
+    }
+
+    fn table_options(&self, options: &TableOptions) -> String {
+        self.mysql().table_options(options)
+    }
+
+    fn column_definition(&self, column: &Column) -> String {
+        mysql::column_definition(self, column)
+    }
+
+    fn add_column(
+        &self,
+        table: &str,
+        column: &Column,
+        position: Option<&ColumnPosition>,
+        if_not_exists: bool,
+    ) -> Vec<String> {
+        vec![mysql::add_column(
+            self,
+            table,
+            column,
+            position,
+            if_not_exists,
+        )]
+    }
+
+    fn drop_column(&self, table: &str, column: &str, if_exists: bool, _cascade: bool) -> String {
+        sql!(
+            self,
+            "ALTER TABLE {} DROP COLUMN {}{};",
+            self.table_identifier(table),
+            if if_exists { "IF EXISTS " } else { "" },
+            self.identifier(column)
+        )
+    }
+
+    fn rename_column(
+        &self,
+        table: &str,
+        old_name: &str,
+        new_name: &str,
+        definition: Option<&Column>,
+    ) -> String {
+        mysql::rename_column(self, table, old_name, new_name, definition)
+    }
+
+    fn change_column_type(
+        &self,
+        table: &str,
+        column: &str,
+        data_type: &DataType,
+        options: &ColumnOptions,
+        position: Option<&ColumnPosition>,
+    ) -> Result<Vec<String>, MigrationError> {
+        Ok(vec![mysql::change_column_type(
+            self, table, column, data_type, options, position,
+        )])
+    }
+
+    fn set_not_null(
+        &self,
+        table: &str,
+        column: &str,
+        current_type: Option<&DataType>,
+    ) -> Result<String, MigrationError> {
+        mysql::modify_nullability(self, table, column, current_type, false)
+    }
+
+    fn drop_not_null(
+        &self,
+        table: &str,
+        column: &str,
+        current_type: Option<&DataType>,
+    ) -> Result<String, MigrationError> {
+        mysql::modify_nullability(self, table, column, current_type, true)
+    }
+
+    fn set_column_default(
+        &self,
+        table: &str,
+        column: &str,
+        default: &DefaultValue,
+    ) -> Result<String, MigrationError> {
+        self.mysql().set_column_default(table, column, default)
+    }
+
+    fn string_literal(&self, value: &str) -> String {
+        self.mysql().string_literal(value)
+    }
+
+    fn render_condition(&self, column: &str, operator: Operator, value: &str) -> String {
+        self.mysql().render_condition(column, operator, value)
+    }
+
+    fn insert_rows(
+        &self,
+        table: &str,
+        columns: &[String],
+        rows: &[Vec<String>],
+        on_conflict_do_nothing: bool,
+    ) -> String {
+        self.mysql()
+            .insert_rows(table, columns, rows, on_conflict_do_nothing)
+    }
+
+    fn select_column_data(&self, table: &str, id_column: &str, column: &str) -> String {
+        self.mysql().select_column_data(table, id_column, column)
+    }
+
+    fn create_index(&self, table: &str, index: &CreateIndex) -> String {
+        mysql::create_index(self, table, index, index.if_not_exists)
+    }
+
+    fn drop_index(&self, table: &str, name: &str, if_exists: bool) -> String {
+        mysql::drop_index(self, table, name, if_exists)
+    }
+
+    fn drop_table(&self, table: &str, if_exists: bool, cascade: bool) -> String {
+        self.mysql().drop_table(table, if_exists, cascade)
+    }
+
+    fn drop_unique_constraint(&self, table: &str, name: &str) -> String {
+        self.mysql().drop_unique_constraint(table, name)
+    }
+
+    fn add_primary_key(
+        &self,
+        table: &str,
+        name: &str,
+        columns: &[String],
+    ) -> Result<String, MigrationError> {
+        self.mysql().add_primary_key(table, name, columns)
+    }
+
+    fn drop_primary_key(
+        &self,
+        table: &str,
+        _name: &str,
+        auto_increment_column: Option<&Column>,
+    ) -> Result<String, MigrationError> {
+        Ok(mysql::drop_primary_key(self, table, auto_increment_column))
+    }
+
+    fn restart_sequence(&self, table: &str, sequence: &str, value: Option<u64>) -> String {
+        self.mysql().restart_sequence(table, sequence, value)
+    }
+
+    fn drop_check_constraint(&self, table: &str, name: &str) -> Result<String, MigrationError> {
+        // MariaDB has no DROP CHECK, but supports the standard form.
+        Ok(sql!(
+            self,
+            "ALTER TABLE {} DROP CONSTRAINT {};",
+            self.table_identifier(table),
+            self.identifier(name)
+        ))
+    }
+
+    fn add_enum_value(&self, table: &str, add: &AddEnumValue) -> Result<String, MigrationError> {
+        mysql::add_enum_value(self, table, add)
+    }
+
+    fn grantee(&self, role: &str, host: Option<&str>) -> String {
+        self.mysql().grantee(role, host)
+    }
+
+    fn add_identity(
+        &self,
+        table: &str,
+        column: &str,
+        _always: bool,
+        start: Option<i64>,
+        current_type: Option<&DataType>,
+    ) -> Result<Vec<String>, MigrationError> {
+        mysql::modify_auto_increment(self, table, column, start, current_type)
+    }
+
+    fn drop_identity(
+        &self,
+        table: &str,
+        column: &str,
+        current_type: Option<&DataType>,
+    ) -> Result<String, MigrationError> {
+        mysql::modify_nullability(self, table, column, current_type, false)
+    }
+
+    fn restart_identity(
+        &self,
+        table: &str,
+        column: &str,
+        start: i64,
+    ) -> Result<String, MigrationError> {
+        self.mysql().restart_identity(table, column, start)
+    }
+
+    fn reorder_column(
+        &self,
+        table: &str,
+        column: &Column,
+        position: &ColumnPosition,
+    ) -> Result<String, MigrationError> {
+        Ok(mysql::reorder_column(self, table, column, position))
+    }
+
+    fn create_schema(&self, name: &str, if_not_exists: bool) -> Result<String, MigrationError> {
+        self.mysql().create_schema(name, if_not_exists)
+    }
+
+    fn drop_schema(&self, name: &str) -> Result<String, MigrationError> {
+        self.mysql().drop_schema(name)
+    }
+
+    fn concat_columns(&self, columns: &[String], separator: &str) -> String {
+        mysql::concat_ws(self, columns, separator)
+    }
+
+    fn conditional(
+        &self,
+        table: &str,
+        precondition: &Precondition,
+        sql: &str,
+    ) -> Result<Vec<String>, MigrationError> {
+        Ok(mysql::conditional(self, table, precondition, sql))
+    }
+
+    fn create_partition(
+        &self,
+        table: &str,
+        partition: &str,
+        bound: &PartitionBound,
+    ) -> Result<String, MigrationError> {
+        mysql::create_partition(self, table, partition, bound)
+    }
+
+    fn drop_partition(&self, table: &str, partition: &str) -> Result<String, MigrationError> {
+        self.mysql().drop_partition(table, partition)
+    }
+
+    fn create_function(&self, function: &CreateFunction) -> Result<Vec<String>, MigrationError> {
+        mysql::create_function(self, function, true)
+    }
+
+    fn drop_function(&self, function: &DropFunction) -> Result<String, MigrationError> {
+        Ok(mysql::drop_function(self, function))
+    }
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        self.mysql().quote_identifier(identifier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renames_with_rename_column() {
+        assert_eq!(
+            MariaDbDialect::new().rename_column("users", "phone", "contact_number", None),
+            "ALTER TABLE users RENAME COLUMN phone TO contact_number;"
+        );
+    }
+
+    #[test]
+    fn renders_json_as_longtext() {
+        let column = Column {
+            name: "settings".to_string(),
+            data_type: DataType::Json,
+            nullable: true,
+            auto_increment: false,
+            generated: None,
+            collation: None,
+        };
+        assert_eq!(
+            MariaDbDialect::new().add_column("users", &column, None, false),
+            ["ALTER TABLE users ADD COLUMN settings LONGTEXT;"]
+        );
+        assert_eq!(
+            MariaDbDialect::new()
+                .change_column_type(
+                    "users",
+                    "settings",
+                    &DataType::Json,
+                    &ColumnOptions::default(),
+                    None
+                )
+                .unwrap(),
+            vec!["ALTER TABLE users MODIFY COLUMN settings LONGTEXT;"]
+        );
+    }
+
+    #[test]
+    fn shares_mysql_output_elsewhere() {
+        let column = Column {
+            name: "views".to_string(),
+            data_type: DataType::UnsignedInteger,
+            nullable: false,
+            auto_increment: false,
+            generated: None,
+            collation: None,
+        };
+        assert_eq!(
+            MariaDbDialect::new().add_column("posts", &column, None, false),
+            MySqlDialect::new().add_column("posts", &column, None, false)
+        );
+        assert_eq!(
+            MariaDbDialect::new().drop_column("posts", "views", false, false),
+            "ALTER TABLE posts DROP COLUMN views;"
+        );
+    }
+
+    #[test]
+    fn drops_check_constraint_with_drop_constraint() {
+        assert_eq!(
+            MariaDbDialect::new()
+                .drop_check_constraint("products", "price_positive")
+                .unwrap(),
+            "ALTER TABLE products DROP CONSTRAINT price_positive;"
+        );
+    }
+    #[test]
+    fn extends_mysql_features_with_column_guards() {
+        let mariadb = MariaDbDialect::new();
+        let mysql = MySqlDialect::new();
+        assert!(mariadb.supports(Feature::DropColumnIfExists));
+        assert!(!mysql.supports(Feature::DropColumnIfExists));
+        assert!(mariadb.supports(Feature::ColumnPosition));
+        assert!(!mariadb.supports(Feature::TransactionalDdl));
+    }
+}
@@ -0,0 +1,1264 @@
+mod cockroach;
+pub(crate) mod keyword;
+mod mariadb;
+mod mssql;
+mod mysql;
+mod naming;
+mod postgres;
+mod sqlite;
+
+pub use cockroach::CockroachDialect;
+pub use keyword::KeywordCase;
+use keyword::{sql, Verbatim};
+pub use mariadb::MariaDbDialect;
+pub use mssql::MssqlDialect;
+pub use mysql::{Algorithm, Lock, MySqlDialect, OnlineDdl};
+pub use naming::{DefaultNaming, NameKind, NamingStrategy};
+pub use postgres::PostgresDialect;
+pub use sqlite::SqliteDialect;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::data_type::{Column, ColumnOptions, DataType, DefaultValue, GeneratedColumn};
+use crate::error::MigrationError;
+use crate::migration::{
+    AddEnumValue, AddForeignKey, ColumnPosition, CreateFunction, CreateIndex, CreateSequence,
+    DropFunction, Operator, Param, PartitionBound, Precondition, Privilege, ReferentialAction,
+    TableOptions, UpdateValue, WhereCondition,
+};
+
+/// SQL spellings registered for [`DataType::Custom`] names.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TypeAliases(HashMap<String, String>);
+
+impl TypeAliases {
+    pub fn insert(&mut self, alias: &str, sql: &str) {
+        self.0.insert(alias.to_string(), sql.to_string());
+    }
+
+    /// The SQL registered for `name`, or `name` itself.
+    pub fn resolve(&self, name: &str) -> String {
+        self.0
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| name.to_string())
+    }
+}
+
+/// The built-in dialects, for choosing one by name, e.g. from configuration
+/// or a command line flag. Names are those of [`Dialect::name`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DialectKind {
+    Postgres,
+    Cockroach,
+    MySql,
+    MariaDb,
+    Mssql,
+    Sqlite,
+}
+
+impl DialectKind {
+    pub const ALL: [DialectKind; 6] = [
+        DialectKind::Postgres,
+        DialectKind::Cockroach,
+        DialectKind::MySql,
+        DialectKind::MariaDb,
+        DialectKind::Mssql,
+        DialectKind::Sqlite,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DialectKind::Postgres => "postgres",
+            DialectKind::Cockroach => "cockroach",
+            DialectKind::MySql => "mysql",
+            DialectKind::MariaDb => "mariadb",
+            DialectKind::Mssql => "mssql",
+            DialectKind::Sqlite => "sqlite",
+        }
+    }
+
+    /// A new dialect of this kind with default settings.
+    pub fn dialect(self) -> Box<dyn Dialect> {
+        match self {
+            DialectKind::Postgres => Box::new(PostgresDialect::new()),
+            DialectKind::Cockroach => Box::new(CockroachDialect::new()),
+            DialectKind::MySql => Box::new(MySqlDialect::new()),
+            DialectKind::MariaDb => Box::new(MariaDbDialect::new()),
+            DialectKind::Mssql => Box::new(MssqlDialect::new()),
+            DialectKind::Sqlite => Box::new(SqliteDialect::new()),
+        }
+    }
+}
+
+impl fmt::Display for DialectKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Parses a [`DialectKind::name`], ignoring case.
+impl FromStr for DialectKind {
+    type Err = MigrationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DialectKind::ALL
+            .into_iter()
+            .find(|kind| kind.name().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| MigrationError::UnknownDialect {
+                name: s.to_string(),
+            })
+    }
+}
+
+/// Capabilities that differ between databases, for [`Dialect::supports`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Feature {
+    /// `FIRST`/`AFTER` placement of new columns.
+    ColumnPosition,
+    /// Indexes covering only a prefix of a column.
+    IndexPrefix,
+    /// Index creation that depends on the table's row count.
+    ConditionalIndex,
+    /// `DROP TABLE ... CASCADE` and `DROP COLUMN ... CASCADE`, dropping
+    /// dependent objects.
+    DropCascade,
+    /// Generated columns computed on read instead of stored.
+    VirtualColumns,
+    /// Inserts that skip rows conflicting with existing ones.
+    InsertIgnore,
+    /// `ADD COLUMN IF NOT EXISTS`.
+    AddColumnIfNotExists,
+    /// `DROP COLUMN IF EXISTS`.
+    DropColumnIfExists,
+    /// Schema changes that can be rolled back with the surrounding
+    /// transaction.
+    TransactionalDdl,
+    /// Accounts qualified by the host they connect from.
+    GranteeHost,
+    /// Identity columns that reject explicitly inserted values.
+    IdentityAlways,
+    /// `CREATE INDEX CONCURRENTLY`, building an index without blocking
+    /// writes.
+    ConcurrentIndex,
+    /// Constraints added `NOT VALID` and validated by a later statement.
+    NotValidConstraints,
+    /// `ALTER COLUMN ... TYPE ... USING`, converting existing values with
+    /// an expression.
+    TypeConversion,
+    /// Storage engine, character set and collation given per table.
+    TableOptions,
+    /// Several column changes in one `ALTER TABLE`, rebuilding the table
+    /// once.
+    CombinedAlter,
+    /// `CREATE INDEX IF NOT EXISTS`.
+    CreateIndexIfNotExists,
+    /// `DROP INDEX IF EXISTS`.
+    DropIndexIfExists,
+    /// Indexes over only the rows matching a `WHERE` filter.
+    PartialIndex,
+}
+
+/// When [`Dialect::identifier`] quotes table and column names.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuoteMode {
+    /// Only names that are reserved words or contain anything besides
+    /// lowercase letters, digits and `_`.
+    #[default]
+    WhenNeeded,
+    /// Every name.
+    Always,
+}
+
+/// Renders migration operations as SQL for a specific database.
+pub trait Dialect {
+    /// Short lowercase identifier, e.g. `postgres`.
+    fn name(&self) -> &str;
+
+    fn type_aliases(&self) -> &TypeAliases;
+
+    fn type_aliases_mut(&mut self) -> &mut TypeAliases;
+
+    /// Makes `DataType::Custom(alias)` render as `sql` in this dialect, so
+    /// one abstract type can map to a different native type per database.
+    fn register_type_alias(&mut self, alias: &str, sql: &str) {
+        self.type_aliases_mut().insert(alias, sql);
+    }
+
+    /// Renders a column type. Dialects override this for types they spell
+    /// differently from [`DataType`]'s `Display` output, and must resolve
+    /// `Custom` types through [`Dialect::type_aliases`].
+    fn render_type(&self, data_type: &DataType) -> String {
+        match data_type {
+            DataType::Custom(name) => self.type_aliases().resolve(name),
+            other => other.to_string(),
+        }
+    }
+
+    /// Quotes an identifier, doubling any embedded quote characters.
+    fn quote_identifier(&self, identifier: &str) -> String {
+        format!("\"{}\"", identifier.replace('"', "\"\""))
+    }
+
+    fn quote_mode(&self) -> QuoteMode {
+        QuoteMode::WhenNeeded
+    }
+
+    fn keyword_case(&self) -> KeywordCase {
+        KeywordCase::Upper
+    }
+
+    /// A table or column name as written in generated SQL, quoted according
+    /// to [`Dialect::quote_mode`].
+    fn identifier(&self, name: &str) -> String {
+        if self.quote_mode() == QuoteMode::Always || needs_quoting(name) {
+            self.quote_identifier(name)
+        } else {
+            name.to_string()
+        }
+    }
+
+    /// [`Dialect::identifier`] for a table, quoting the schema of a
+    /// `schema.table` name separately.
+    fn table_identifier(&self, table: &str) -> String {
+        match split_table(table) {
+            (Some(schema), name) => {
+                format!("{}.{}", self.identifier(schema), self.identifier(name))
+            }
+            (None, name) => self.identifier(name),
+        }
+    }
+
+    /// Names the indexes and constraints migrations leave unnamed; see
+    /// [`Dialect::generated_name`].
+    fn naming_strategy(&self) -> &dyn NamingStrategy {
+        &DefaultNaming
+    }
+
+    /// Longest identifier the database accepts, in bytes, if generated names
+    /// could reach it.
+    fn max_identifier_length(&self) -> Option<usize> {
+        None
+    }
+
+    /// Name for an index or constraint a migration did not name, from
+    /// [`Dialect::naming_strategy`]. Names over
+    /// [`Dialect::max_identifier_length`] are cut short and end in a hash of
+    /// the full name instead.
+    fn generated_name(&self, kind: NameKind, table: &str, parts: &[String]) -> String {
+        let name = self
+            .naming_strategy()
+            .name(kind, split_table(table).1, parts);
+        match self.max_identifier_length() {
+            Some(max_length) => naming::shorten(&name, max_length),
+            None => name,
+        }
+    }
+
+    /// Whether the dialect can express `feature`. Operations that use an
+    /// unsupported feature leave it out and report it from
+    /// [`MigrationStep::ignored_options`](crate::migration::MigrationStep::ignored_options).
+    fn supports(&self, _feature: Feature) -> bool {
+        false
+    }
+
+    /// ` COLLATE ...` for a column's collation, or nothing without one.
+    fn collate(&self, collation: Option<&str>) -> String {
+        collation
+            .map(|collation| sql!(self, " COLLATE {}", Verbatim(collation)))
+            .unwrap_or_default()
+    }
+
+    /// The column as written in `CREATE TABLE` or `ADD COLUMN`: name, type
+    /// and constraints.
+    fn column_definition(&self, column: &Column) -> String {
+        let generated = match &column.generated {
+            Some(generated) => generated_clause(self, generated),
+            None => String::new(),
+        };
+        sql!(
+            self,
+            "{} {}{}{}{}",
+            self.identifier(&column.name),
+            self.render_type(&column.data_type),
+            Verbatim(self.collate(column.collation.as_deref())),
+            Verbatim(generated),
+            if column.nullable { "" } else { " NOT NULL" }
+        )
+    }
+
+    /// Dialects without [`Feature::AddColumnIfNotExists`] ignore
+    /// `if_not_exists`. Postgres creates an enum column's type first, hence
+    /// the separate statements.
+    fn add_column(
+        &self,
+        table: &str,
+        column: &Column,
+        position: Option<&ColumnPosition>,
+        if_not_exists: bool,
+    ) -> Vec<String>;
+
+    /// Dialects without [`Feature::DropColumnIfExists`] ignore `if_exists`.
+    fn drop_column(&self, table: &str, column: &str, if_exists: bool, cascade: bool) -> String;
+
+    /// `definition` is for dialects that must restate the column.
+    fn rename_column(
+        &self,
+        table: &str,
+        old_name: &str,
+        new_name: &str,
+        definition: Option<&Column>,
+    ) -> String;
+
+    /// The expression converting `column` from one type to the other, when
+    /// the database cannot do it implicitly. `None` where it can, or where
+    /// [`Feature::TypeConversion`] is not supported.
+    fn type_conversion(&self, _column: &str, _from: &DataType, _to: &DataType) -> Option<String> {
+        None
+    }
+
+    /// One statement per change, in order: the type first, then whatever
+    /// `options` set. `position` is honoured where
+    /// [`Feature::ColumnPosition`] is supported.
+    fn change_column_type(
+        &self,
+        table: &str,
+        column: &str,
+        data_type: &DataType,
+        options: &ColumnOptions,
+        position: Option<&ColumnPosition>,
+    ) -> Result<Vec<String>, MigrationError>;
+
+    /// `current_type` is for dialects that must restate the column's type.
+    fn set_not_null(
+        &self,
+        table: &str,
+        column: &str,
+        _current_type: Option<&DataType>,
+    ) -> Result<String, MigrationError> {
+        Ok(sql!(
+            self,
+            "ALTER TABLE {} ALTER COLUMN {} SET NOT NULL;",
+            self.table_identifier(table),
+            self.identifier(column)
+        ))
+    }
+
+    fn drop_not_null(
+        &self,
+        table: &str,
+        column: &str,
+        _current_type: Option<&DataType>,
+    ) -> Result<String, MigrationError> {
+        Ok(sql!(
+            self,
+            "ALTER TABLE {} ALTER COLUMN {} DROP NOT NULL;",
+            self.table_identifier(table),
+            self.identifier(column)
+        ))
+    }
+
+    /// Replaces `NULL`s in `column` with `value`, which is written as-is.
+    fn backfill_nulls(&self, table: &str, column: &str, value: &str) -> String {
+        let column = self.identifier(column);
+        sql!(
+            self,
+            "UPDATE {} SET {} = {} WHERE {} IS NULL;",
+            self.table_identifier(table),
+            column,
+            Verbatim(value),
+            column
+        )
+    }
+
+    fn set_column_default(
+        &self,
+        table: &str,
+        column: &str,
+        default: &DefaultValue,
+    ) -> Result<String, MigrationError> {
+        Ok(sql!(
+            self,
+            "ALTER TABLE {} ALTER COLUMN {} SET DEFAULT {};",
+            self.table_identifier(table),
+            self.identifier(column),
+            Verbatim(default)
+        ))
+    }
+
+    fn drop_column_default(&self, table: &str, column: &str) -> Result<String, MigrationError> {
+        Ok(sql!(
+            self,
+            "ALTER TABLE {} ALTER COLUMN {} DROP DEFAULT;",
+            self.table_identifier(table),
+            self.identifier(column)
+        ))
+    }
+
+    /// Quotes `value` as a string literal.
+    fn string_literal(&self, value: &str) -> String {
+        quote_literal(value)
+    }
+
+    /// The SQL for a value written by an update or compared in a condition.
+    fn render_value(&self, value: &UpdateValue) -> String {
+        match value {
+            UpdateValue::Fixed(sql) => sql.clone(),
+            UpdateValue::Literal(value) => self.string_literal(value),
+            UpdateValue::Column(column) => self.identifier(column),
+        }
+    }
+
+    /// Renders one `WHERE` comparison. `value` is already rendered SQL.
+    fn render_condition(&self, column: &str, operator: Operator, value: &str) -> String {
+        sql!(
+            self,
+            "{} {} {}",
+            self.identifier(column),
+            operator.as_str(),
+            Verbatim(value)
+        )
+    }
+
+    /// ` WHERE ...` joining every condition with `AND`, or nothing without
+    /// conditions.
+    fn where_clause(&self, conditions: &[WhereCondition]) -> String {
+        render_where(self, conditions, |value| self.render_value(value))
+    }
+
+    fn update_column_data(
+        &self,
+        table: &str,
+        column: &str,
+        value: &UpdateValue,
+        conditions: &[WhereCondition],
+    ) -> String {
+        sql!(
+            self,
+            "UPDATE {} SET {} = {}{};",
+            self.table_identifier(table),
+            self.identifier(column),
+            Verbatim(self.render_value(value)),
+            Verbatim(self.where_clause(conditions))
+        )
+    }
+
+    /// An expression joining `columns` with `separator`, treating `NULL`s
+    /// as empty.
+    fn concat_columns(&self, columns: &[String], separator: &str) -> String {
+        let columns: Vec<String> = columns
+            .iter()
+            .map(|column| sql!(self, "COALESCE({}, '')", self.identifier(column)))
+            .collect();
+        columns.join(&sql!(self, " || {} || ", quote_literal(separator)))
+    }
+
+    /// Bind placeholder for the 1-based parameter `index`.
+    fn placeholder(&self, _index: usize) -> String {
+        "?".to_string()
+    }
+
+    /// [`Dialect::update_column_data`] with literal values bound as
+    /// parameters. Fixed values are SQL and stay in the statement.
+    fn update_column_data_parameterized(
+        &self,
+        table: &str,
+        column: &str,
+        value: &UpdateValue,
+        conditions: &[WhereCondition],
+    ) -> (String, Vec<Param>) {
+        let mut params = Vec::new();
+        let value = bind_literal(self, &mut params, value);
+        let where_clause = render_where(self, conditions, |value| {
+            bind_literal(self, &mut params, value)
+        });
+        let sql = sql!(
+            self,
+            "UPDATE {} SET {} = {}{};",
+            self.table_identifier(table),
+            self.identifier(column),
+            Verbatim(value),
+            Verbatim(where_clause)
+        );
+        (sql, params)
+    }
+
+    /// Updates a single row identified by `id`, used for values computed by
+    /// an external process.
+    fn update_column_data_by_id(
+        &self,
+        table: &str,
+        id_column: &str,
+        id: &str,
+        column: &str,
+        value: &UpdateValue,
+    ) -> String {
+        sql!(
+            self,
+            "UPDATE {} SET {} = {} WHERE {} = {};",
+            self.table_identifier(table),
+            self.identifier(column),
+            Verbatim(self.render_value(value)),
+            self.identifier(id_column),
+            Verbatim(id)
+        )
+    }
+
+    /// Updates many rows identified by id in one statement. `rows` holds
+    /// `(id, value)` pairs.
+    fn update_column_data_batch(
+        &self,
+        table: &str,
+        id_column: &str,
+        column: &str,
+        rows: &[(String, String)],
+    ) -> String {
+        let cases: Vec<String> = rows
+            .iter()
+            .map(|(id, value)| sql!(self, "WHEN {} THEN {}", Verbatim(id), Verbatim(value)))
+            .collect();
+        let ids: Vec<&str> = rows.iter().map(|(id, _)| id.as_str()).collect();
+        let id_column = self.identifier(id_column);
+        sql!(
+            self,
+            "UPDATE {} SET {} = CASE {} {} END WHERE {} IN ({});",
+            self.table_identifier(table),
+            self.identifier(column),
+            id_column,
+            Verbatim(cases.join(" ")),
+            id_column,
+            Verbatim(ids.join(", "))
+        )
+    }
+
+    /// Multi-row insert. `on_conflict_do_nothing` is ignored by dialects
+    /// that do not [support](Feature::InsertIgnore) it.
+    fn insert_rows(
+        &self,
+        table: &str,
+        columns: &[String],
+        rows: &[Vec<String>],
+        _on_conflict_do_nothing: bool,
+    ) -> String {
+        sql!(
+            self,
+            "INSERT INTO {} ({}) VALUES {};",
+            self.table_identifier(table),
+            identifier_list(self, columns),
+            Verbatim(values_list(rows))
+        )
+    }
+
+    /// Deletes the rows matching every condition, or all rows without any.
+    fn delete_rows(&self, table: &str, conditions: &[WhereCondition]) -> String {
+        sql!(
+            self,
+            "DELETE FROM {}{};",
+            self.table_identifier(table),
+            Verbatim(self.where_clause(conditions))
+        )
+    }
+
+    /// [`Dialect::delete_rows`] with literal condition values bound as
+    /// parameters.
+    fn delete_rows_parameterized(
+        &self,
+        table: &str,
+        conditions: &[WhereCondition],
+    ) -> (String, Vec<Param>) {
+        let mut params = Vec::new();
+        let where_clause = render_where(self, conditions, |value| {
+            bind_literal(self, &mut params, value)
+        });
+        let sql = sql!(
+            self,
+            "DELETE FROM {}{};",
+            self.table_identifier(table),
+            Verbatim(where_clause)
+        );
+        (sql, params)
+    }
+
+    /// Deletes the rows whose `key_columns` match one of `keys`. A single key
+    /// column uses a plain `IN` list, several compare row values.
+    fn delete_rows_by_key(
+        &self,
+        table: &str,
+        key_columns: &[String],
+        keys: &[Vec<String>],
+    ) -> String {
+        let table = self.table_identifier(table);
+        if let [key_column] = key_columns {
+            let keys: Vec<&str> = keys.iter().map(|key| key[0].as_str()).collect();
+            return sql!(
+                self,
+                "DELETE FROM {} WHERE {} IN ({});",
+                table,
+                self.identifier(key_column),
+                Verbatim(keys.join(", "))
+            );
+        }
+        let keys: Vec<String> = keys
+            .iter()
+            .map(|key| format!("({})", key.join(", ")))
+            .collect();
+        sql!(
+            self,
+            "DELETE FROM {} WHERE ({}) IN ({});",
+            table,
+            identifier_list(self, key_columns),
+            Verbatim(keys.join(", "))
+        )
+    }
+
+    /// Query handed to an external process so it can read the current values.
+    fn select_column_data(&self, table: &str, id_column: &str, column: &str) -> String;
+
+    fn create_index(&self, table: &str, index: &CreateIndex) -> String;
+
+    /// `if_exists` is honoured where [`Feature::DropIndexIfExists`] is
+    /// supported. Indexes live in their table's schema.
+    fn drop_index(&self, table: &str, name: &str, if_exists: bool) -> String {
+        let name = match split_table(table).0 {
+            Some(schema) => format!("{}.{}", self.identifier(schema), self.identifier(name)),
+            None => self.identifier(name),
+        };
+        sql!(
+            self,
+            "DROP INDEX {}{};",
+            if if_exists { "IF EXISTS " } else { "" },
+            name
+        )
+    }
+
+    fn create_table(
+        &self,
+        table: &str,
+        columns: &[Column],
+        primary_key: &[String],
+        options: &TableOptions,
+    ) -> Vec<String> {
+        let definitions = columns
+            .iter()
+            .map(|column| self.column_definition(column))
+            .collect();
+        vec![create_table(self, table, definitions, primary_key, options)]
+    }
+
+    /// Clauses ending the `ALTER TABLE` of column additions, drops and type
+    /// changes, e.g. MySQL's `, ALGORITHM=INPLACE, LOCK=NONE`.
+    fn alter_options(&self) -> String {
+        String::new()
+    }
+
+    /// Table options written after the column list, e.g. MySQL's
+    /// ` ENGINE=InnoDB`. Nothing where [`Feature::TableOptions`] is not
+    /// supported.
+    fn table_options(&self, _options: &TableOptions) -> String {
+        String::new()
+    }
+
+    fn drop_table(&self, table: &str, if_exists: bool, cascade: bool) -> String;
+
+    /// Sets the next value `sequence` hands out, or restarts it from its
+    /// start value.
+    fn restart_sequence(&self, _table: &str, sequence: &str, value: Option<u64>) -> String {
+        let sequence = self.table_identifier(sequence);
+        match value {
+            Some(value) => sql!(self, "ALTER SEQUENCE {} RESTART WITH {};", sequence, value),
+            None => sql!(self, "ALTER SEQUENCE {} RESTART;", sequence),
+        }
+    }
+
+    fn create_sequence(
+        &self,
+        _table: &str,
+        _sequence: &CreateSequence,
+    ) -> Result<String, MigrationError> {
+        Err(MigrationError::unsupported(self.name(), "CREATE SEQUENCE"))
+    }
+
+    fn drop_sequence(&self, _name: &str, _if_exists: bool) -> Result<String, MigrationError> {
+        Err(MigrationError::unsupported(self.name(), "DROP SEQUENCE"))
+    }
+
+    fn create_enum_type(
+        &self,
+        _name: &str,
+        _variants: &[String],
+    ) -> Result<String, MigrationError> {
+        Err(MigrationError::unsupported(self.name(), "CREATE TYPE"))
+    }
+
+    fn add_enum_value(&self, _table: &str, _add: &AddEnumValue) -> Result<String, MigrationError> {
+        Err(MigrationError::unsupported(
+            self.name(),
+            "ALTER TYPE ADD VALUE",
+        ))
+    }
+
+    fn rename_enum_value(
+        &self,
+        _type_name: &str,
+        _old_value: &str,
+        _new_value: &str,
+    ) -> Result<String, MigrationError> {
+        Err(MigrationError::unsupported(
+            self.name(),
+            "ALTER TYPE RENAME VALUE",
+        ))
+    }
+
+    /// Dialects without `CREATE OR REPLACE VIEW` drop the view first.
+    fn create_view(&self, name: &str, query: &str, or_replace: bool) -> Vec<String> {
+        vec![sql!(
+            self,
+            "CREATE {}VIEW {} AS {};",
+            if or_replace { "OR REPLACE " } else { "" },
+            self.table_identifier(name),
+            Verbatim(query)
+        )]
+    }
+
+    fn drop_view(&self, name: &str, if_exists: bool) -> String {
+        sql!(
+            self,
+            "DROP VIEW {}{};",
+            if if_exists { "IF EXISTS " } else { "" },
+            self.table_identifier(name)
+        )
+    }
+
+    fn create_materialized_view(
+        &self,
+        _name: &str,
+        _query: &str,
+        _with_data: bool,
+    ) -> Result<String, MigrationError> {
+        Err(MigrationError::unsupported(
+            self.name(),
+            "CREATE MATERIALIZED VIEW",
+        ))
+    }
+
+    fn refresh_materialized_view(
+        &self,
+        _name: &str,
+        _concurrently: bool,
+    ) -> Result<String, MigrationError> {
+        Err(MigrationError::unsupported(
+            self.name(),
+            "REFRESH MATERIALIZED VIEW",
+        ))
+    }
+
+    fn drop_materialized_view(
+        &self,
+        _name: &str,
+        _if_exists: bool,
+    ) -> Result<String, MigrationError> {
+        Err(MigrationError::unsupported(
+            self.name(),
+            "DROP MATERIALIZED VIEW",
+        ))
+    }
+
+    /// The account or role privileges are granted to.
+    fn grantee(&self, role: &str, _host: Option<&str>) -> String {
+        self.identifier(role)
+    }
+
+    fn grant_privileges(&self, table: &str, privileges: &[Privilege], grantee: &str) -> String {
+        sql!(
+            self,
+            "GRANT {} ON {} TO {};",
+            privilege_list(privileges),
+            self.table_identifier(table),
+            grantee
+        )
+    }
+
+    fn revoke_privileges(&self, table: &str, privileges: &[Privilege], grantee: &str) -> String {
+        sql!(
+            self,
+            "REVOKE {} ON {} FROM {};",
+            privilege_list(privileges),
+            self.table_identifier(table),
+            grantee
+        )
+    }
+
+    fn create_function(&self, _function: &CreateFunction) -> Result<Vec<String>, MigrationError> {
+        Err(MigrationError::unsupported(self.name(), "CREATE FUNCTION"))
+    }
+
+    fn drop_function(&self, _function: &DropFunction) -> Result<String, MigrationError> {
+        Err(MigrationError::unsupported(self.name(), "DROP FUNCTION"))
+    }
+
+    fn add_check_constraint(
+        &self,
+        table: &str,
+        name: &str,
+        expression: &str,
+    ) -> Result<String, MigrationError> {
+        Ok(sql!(
+            self,
+            "ALTER TABLE {} ADD CONSTRAINT {} CHECK ({});",
+            self.table_identifier(table),
+            self.identifier(name),
+            Verbatim(expression)
+        ))
+    }
+
+    /// Checks existing rows against a constraint added `NOT VALID`.
+    fn validate_constraint(&self, table: &str, name: &str) -> String {
+        sql!(
+            self,
+            "ALTER TABLE {} VALIDATE CONSTRAINT {};",
+            self.table_identifier(table),
+            self.identifier(name)
+        )
+    }
+
+    fn drop_check_constraint(&self, table: &str, name: &str) -> Result<String, MigrationError> {
+        Ok(drop_constraint(self, table, name))
+    }
+
+    fn add_unique_constraint(&self, table: &str, name: &str, columns: &[String]) -> String {
+        sql!(
+            self,
+            "ALTER TABLE {} ADD CONSTRAINT {} UNIQUE ({});",
+            self.table_identifier(table),
+            self.identifier(name),
+            identifier_list(self, columns)
+        )
+    }
+
+    fn drop_unique_constraint(&self, table: &str, name: &str) -> String {
+        drop_constraint(self, table, name)
+    }
+
+    fn add_primary_key(
+        &self,
+        table: &str,
+        name: &str,
+        columns: &[String],
+    ) -> Result<String, MigrationError> {
+        Ok(sql!(
+            self,
+            "ALTER TABLE {} ADD CONSTRAINT {} PRIMARY KEY ({});",
+            self.table_identifier(table),
+            self.identifier(name),
+            identifier_list(self, columns)
+        ))
+    }
+
+    /// `auto_increment_column` is the redefinition of the key's
+    /// auto-incremented column, for dialects that must clear that first.
+    fn drop_primary_key(
+        &self,
+        table: &str,
+        name: &str,
+        _auto_increment_column: Option<&Column>,
+    ) -> Result<String, MigrationError> {
+        Ok(drop_constraint(self, table, name))
+    }
+
+    fn add_foreign_key(
+        &self,
+        table: &str,
+        name: &str,
+        foreign_key: &AddForeignKey,
+    ) -> Result<String, MigrationError> {
+        let mut sql = sql!(
+            self,
+            "ALTER TABLE {} ADD CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({})",
+            self.table_identifier(table),
+            self.identifier(name),
+            identifier_list(self, &foreign_key.columns),
+            self.table_identifier(&foreign_key.referenced_table),
+            identifier_list(self, &foreign_key.referenced_columns)
+        );
+        if let Some(action) = foreign_key.on_delete {
+            sql.push_str(&sql!(
+                self,
+                " ON DELETE {}",
+                self.referential_action(action)
+            ));
+        }
+        if let Some(action) = foreign_key.on_update {
+            sql.push_str(&sql!(
+                self,
+                " ON UPDATE {}",
+                self.referential_action(action)
+            ));
+        }
+        sql.push(';');
+        Ok(sql)
+    }
+
+    fn referential_action(&self, action: ReferentialAction) -> &'static str {
+        action.as_str()
+    }
+
+    fn attach_partition(
+        &self,
+        _table: &str,
+        _partition: &str,
+        _bound: &PartitionBound,
+    ) -> Result<String, MigrationError> {
+        Err(MigrationError::unsupported(self.name(), "ATTACH PARTITION"))
+    }
+
+    fn detach_partition(
+        &self,
+        _table: &str,
+        _partition: &str,
+        _concurrently: bool,
+    ) -> Result<String, MigrationError> {
+        Err(MigrationError::unsupported(self.name(), "DETACH PARTITION"))
+    }
+
+    fn create_partition(
+        &self,
+        _table: &str,
+        _partition: &str,
+        _bound: &PartitionBound,
+    ) -> Result<String, MigrationError> {
+        Err(MigrationError::unsupported(self.name(), "CREATE PARTITION"))
+    }
+
+    fn drop_partition(&self, _table: &str, _partition: &str) -> Result<String, MigrationError> {
+        Err(MigrationError::unsupported(self.name(), "DROP PARTITION"))
+    }
+
+    fn add_identity(
+        &self,
+        _table: &str,
+        _column: &str,
+        _always: bool,
+        _start: Option<i64>,
+        _current_type: Option<&DataType>,
+    ) -> Result<Vec<String>, MigrationError> {
+        Err(MigrationError::unsupported(self.name(), "ADD IDENTITY"))
+    }
+
+    fn drop_identity(
+        &self,
+        _table: &str,
+        _column: &str,
+        _current_type: Option<&DataType>,
+    ) -> Result<String, MigrationError> {
+        Err(MigrationError::unsupported(self.name(), "DROP IDENTITY"))
+    }
+
+    fn restart_identity(
+        &self,
+        _table: &str,
+        _column: &str,
+        _start: i64,
+    ) -> Result<String, MigrationError> {
+        Err(MigrationError::unsupported(self.name(), "RESTART IDENTITY"))
+    }
+
+    /// Moves a column, restating its definition.
+    fn reorder_column(
+        &self,
+        _table: &str,
+        _column: &Column,
+        _position: &ColumnPosition,
+    ) -> Result<String, MigrationError> {
+        Err(MigrationError::unsupported(
+            self.name(),
+            "column reordering",
+        ))
+    }
+
+    fn create_schema(&self, _name: &str, _if_not_exists: bool) -> Result<String, MigrationError> {
+        Err(MigrationError::unsupported(self.name(), "CREATE SCHEMA"))
+    }
+
+    fn drop_schema(&self, _name: &str) -> Result<String, MigrationError> {
+        Err(MigrationError::unsupported(self.name(), "DROP SCHEMA"))
+    }
+
+    /// Wraps `sql` so that it only runs when `precondition` holds. MySQL
+    /// needs several statements for this.
+    fn conditional(
+        &self,
+        _table: &str,
+        _precondition: &Precondition,
+        _sql: &str,
+    ) -> Result<Vec<String>, MigrationError> {
+        Err(MigrationError::unsupported(
+            self.name(),
+            "conditional operations",
+        ))
+    }
+}
+
+fn privilege_list(privileges: &[Privilege]) -> String {
+    let privileges: Vec<String> = privileges.iter().map(|p| p.to_string()).collect();
+    privileges.join(", ")
+}
+
+/// `CREATE TABLE` from rendered column definitions.
+pub(crate) fn create_table<D: Dialect + ?Sized>(
+    dialect: &D,
+    table: &str,
+    mut definitions: Vec<String>,
+    primary_key: &[String],
+    options: &TableOptions,
+) -> String {
+    if !primary_key.is_empty() {
+        definitions.push(sql!(
+            dialect,
+            "PRIMARY KEY ({})",
+            identifier_list(dialect, primary_key)
+        ));
+    }
+    sql!(
+        dialect,
+        "CREATE TABLE {} ({}){};",
+        dialect.table_identifier(table),
+        Verbatim(definitions.join(", ")),
+        Verbatim(dialect.table_options(options))
+    )
+}
+
+/// `names` as a comma-separated list of [`Dialect::identifier`]s.
+pub(crate) fn identifier_list<D: Dialect + ?Sized>(dialect: &D, names: &[String]) -> String {
+    let names: Vec<String> = names.iter().map(|name| dialect.identifier(name)).collect();
+    names.join(", ")
+}
+
+fn drop_constraint<D: Dialect + ?Sized>(dialect: &D, table: &str, name: &str) -> String {
+    sql!(
+        dialect,
+        "ALTER TABLE {} DROP CONSTRAINT {};",
+        dialect.table_identifier(table),
+        dialect.identifier(name)
+    )
+}
+
+/// `table` with its schema and name each quoted unconditionally, for SQL
+/// handed to other tools.
+pub(crate) fn quoted_table<D: Dialect + ?Sized>(dialect: &D, table: &str) -> String {
+    match split_table(table) {
+        (Some(schema), name) => format!(
+            "{}.{}",
+            dialect.quote_identifier(schema),
+            dialect.quote_identifier(name)
+        ),
+        (None, name) => dialect.quote_identifier(name),
+    }
+}
+
+/// ` GENERATED ALWAYS AS (...)` for a generated column, stored or virtual.
+pub(crate) fn generated_clause<D: Dialect + ?Sized>(
+    dialect: &D,
+    generated: &GeneratedColumn,
+) -> String {
+    sql!(
+        dialect,
+        " GENERATED ALWAYS AS ({}) {}",
+        Verbatim(&generated.expression),
+        if generated.stored {
+            "STORED"
+        } else {
+            "VIRTUAL"
+        }
+    )
+}
+
+/// The `WHERE` filter of a partial index, or nothing.
+pub(crate) fn index_filter<D: Dialect + ?Sized>(dialect: &D, index: &CreateIndex) -> String {
+    index
+        .where_clause
+        .as_ref()
+        .map(|filter| sql!(dialect, " WHERE {}", Verbatim(filter)))
+        .unwrap_or_default()
+}
+
+/// Renders `rows` as the tuples of a `VALUES` list.
+pub(crate) fn values_list(rows: &[Vec<String>]) -> String {
+    let rows: Vec<String> = rows
+        .iter()
+        .map(|row| format!("({})", row.join(", ")))
+        .collect();
+    rows.join(", ")
+}
+
+/// Words that cannot be used as bare names in at least one dialect.
+const RESERVED_WORDS: &[&str] = &[
+    "ADD",
+    "ALL",
+    "ALTER",
+    "AND",
+    "ANY",
+    "AS",
+    "ASC",
+    "BETWEEN",
+    "BY",
+    "CASE",
+    "CHECK",
+    "COLUMN",
+    "CONSTRAINT",
+    "CREATE",
+    "CROSS",
+    "DEFAULT",
+    "DELETE",
+    "DESC",
+    "DISTINCT",
+    "DROP",
+    "ELSE",
+    "END",
+    "EXISTS",
+    "FOR",
+    "FOREIGN",
+    "FROM",
+    "FULL",
+    "GRANT",
+    "GROUP",
+    "HAVING",
+    "IN",
+    "INDEX",
+    "INNER",
+    "INSERT",
+    "INTO",
+    "IS",
+    "JOIN",
+    "KEY",
+    "LEFT",
+    "LIKE",
+    "LIMIT",
+    "NOT",
+    "NULL",
+    "ON",
+    "OR",
+    "ORDER",
+    "OUTER",
+    "PRIMARY",
+    "REFERENCES",
+    "RIGHT",
+    "SELECT",
+    "SET",
+    "TABLE",
+    "THEN",
+    "TO",
+    "UNION",
+    "UNIQUE",
+    "UPDATE",
+    "USER",
+    "USING",
+    "VALUES",
+    "WHEN",
+    "WHERE",
+    "WITH",
+];
+
+/// Whether `name` must be quoted to be read back unchanged: it is a reserved
+/// word, or it has characters that bare names cannot contain or that some
+/// databases fold to another case.
+fn needs_quoting(name: &str) -> bool {
+    let bare = name
+        .chars()
+        .next()
+        .is_some_and(|first| first.is_ascii_lowercase() || first == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    !bare
+        || RESERVED_WORDS
+            .binary_search(&name.to_ascii_uppercase().as_str())
+            .is_ok()
+}
+
+/// Splits `schema.table` into its schema, if any, and the bare table name.
+/// Names derived from the table use the bare name.
+pub(crate) fn split_table(table: &str) -> (Option<&str>, &str) {
+    match table.rsplit_once('.') {
+        Some((schema, name)) => (Some(schema), name),
+        None => (None, table),
+    }
+}
+
+/// Renders `value` for a parameterized statement: a literal becomes the next
+/// placeholder and is pushed onto `params`, anything else is written inline.
+fn bind_literal<D: Dialect + ?Sized>(
+    dialect: &D,
+    params: &mut Vec<Param>,
+    value: &UpdateValue,
+) -> String {
+    match value {
+        UpdateValue::Literal(value) => {
+            params.push(Param(value.clone()));
+            dialect.placeholder(params.len())
+        }
+        value => dialect.render_value(value),
+    }
+}
+
+/// Renders ` WHERE ...` for `conditions`, or nothing without conditions,
+/// with each condition value rendered by `value`.
+fn render_where<D: Dialect + ?Sized>(
+    dialect: &D,
+    conditions: &[WhereCondition],
+    mut value: impl FnMut(&UpdateValue) -> String,
+) -> String {
+    if conditions.is_empty() {
+        return String::new();
+    }
+    let conditions: Vec<String> = conditions
+        .iter()
+        .map(|condition| {
+            dialect.render_condition(
+                &condition.column,
+                condition.operator,
+                &value(&condition.value),
+            )
+        })
+        .collect();
+    sql!(
+        dialect,
+        " WHERE {}",
+        Verbatim(conditions.join(&sql!(dialect, " AND ")))
+    )
+}
+
+/// Quotes `value` as a SQL string literal, doubling embedded single quotes.
+pub(crate) fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dialect_kinds_round_trip_through_their_names() {
+        for kind in DialectKind::ALL {
+            assert_eq!(kind.to_string().parse::<DialectKind>(), Ok(kind));
+            assert_eq!(kind.dialect().name(), kind.name());
+        }
+        assert_eq!("MySQL".parse::<DialectKind>(), Ok(DialectKind::MySql));
+    }
+
+    #[test]
+    fn rejects_unknown_dialect_names() {
+        let err = "oracle".parse::<DialectKind>().unwrap_err();
+        assert_eq!(
+            err,
+            MigrationError::UnknownDialect {
+                name: "oracle".to_string()
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "unknown dialect \"oracle\", expected one of postgres, cockroach, mysql, mariadb, mssql, sqlite"
+        );
+    }
+}
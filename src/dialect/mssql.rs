@@ -0,0 +1,706 @@
+use std::sync::Arc;
+
+use super::keyword::{sql, Verbatim};
+use super::{
+    identifier_list, index_filter, quote_literal, quoted_table, split_table, DefaultNaming,
+    Dialect, Feature, KeywordCase, NamingStrategy, QuoteMode, TypeAliases,
+};
+use crate::data_type::{Column, ColumnOptions, DataType, DefaultValue, Precision};
+use crate::error::MigrationError;
+use crate::migration::{ColumnPosition, CreateIndex, Operator, ReferentialAction};
+
+#[derive(Debug, Clone, Default)]
+pub struct MssqlDialect {
+    type_aliases: TypeAliases,
+    quote_mode: QuoteMode,
+    keyword_case: KeywordCase,
+    naming: Option<Arc<dyn NamingStrategy>>,
+}
+
+impl MssqlDialect {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_quote_mode(mut self, quote_mode: QuoteMode) -> Self {
+        self.quote_mode = quote_mode;
+        self
+    }
+
+    pub fn with_keyword_case(mut self, keyword_case: KeywordCase) -> Self {
+        self.keyword_case = keyword_case;
+        self
+    }
+
+    /// Names the indexes and constraints migrations leave unnamed.
+    pub fn with_naming_strategy(mut self, strategy: impl NamingStrategy + 'static) -> Self {
+        self.naming = Some(Arc::new(strategy));
+        self
+    }
+
+    /// ALTER COLUMN always restates the type.
+    fn alter_nullability(
+        &self,
+        table: &str,
+        column: &str,
+        current_type: Option<&DataType>,
+        nullable: bool,
+    ) -> Result<String, MigrationError> {
+        let data_type = current_type.ok_or_else(|| MigrationError::MissingColumnType {
+            dialect: self.name().to_string(),
+            column: column.to_string(),
+        })?;
+        Ok(sql!(
+            self,
+            "ALTER TABLE {} ALTER COLUMN {} {} {};",
+            self.table_identifier(table),
+            self.identifier(column),
+            self.render_type(data_type),
+            if nullable { "NULL" } else { "NOT NULL" }
+        ))
+    }
+}
+
+impl Dialect for MssqlDialect {
+    fn name(&self) -> &str {
+        "mssql"
+    }
+
+    fn type_aliases(&self) -> &TypeAliases {
+        &self.type_aliases
+    }
+
+    fn type_aliases_mut(&mut self) -> &mut TypeAliases {
+        &mut self.type_aliases
+    }
+
+    fn quote_mode(&self) -> QuoteMode {
+        self.quote_mode
+    }
+
+    fn keyword_case(&self) -> KeywordCase {
+        self.keyword_case
+    }
+
+    fn naming_strategy(&self) -> &dyn NamingStrategy {
+        self.naming.as_deref().unwrap_or(&DefaultNaming)
+    }
+
+    fn max_identifier_length(&self) -> Option<usize> {
+        Some(128)
+    }
+
+    fn render_type(&self, data_type: &DataType) -> String {
+        match data_type {
+            // No unsigned integers either, so widen like Postgres does.
+            DataType::UnsignedInteger => "BIGINT".to_string(),
+            DataType::UnsignedBigInt => "DECIMAL(20,0)".to_string(),
+            DataType::Boolean => "BIT".to_string(),
+            DataType::Float => "FLOAT".to_string(),
+            DataType::Text | DataType::Json | DataType::Array(_) => "NVARCHAR(MAX)".to_string(),
+            // TIMESTAMP is a row version counter in SQL Server, not a time.
+            DataType::Timestamp {
+                with_timezone,
+                precision,
+            } => format!(
+                "{}{}",
+                if *with_timezone {
+                    "DATETIMEOFFSET"
+                } else {
+                    "DATETIME2"
+                },
+                Precision(*precision)
+            ),
+            DataType::Binary(Some(length)) if *length <= 8_000 => format!("VARBINARY({})", length),
+            DataType::Binary(_) => "VARBINARY(MAX)".to_string(),
+            DataType::Enum { variants, .. } => {
+                let longest = variants.iter().map(|v| v.chars().count()).max();
+                format!("VARCHAR({})", longest.unwrap_or(1).max(1))
+            }
+            DataType::Custom(name) => self.type_aliases.resolve(name),
+            other => other.to_string(),
+        }
+    }
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        format!("[{}]", identifier.replace(']', "]]"))
+    }
+
+    fn placeholder(&self, index: usize) -> String {
+        format!("@p{}", index)
+    }
+
+    fn supports(&self, feature: Feature) -> bool {
+        // DROP ... IF EXISTS needs SQL Server 2016. Partial indexes are
+        // called filtered indexes.
+        matches!(
+            feature,
+            Feature::VirtualColumns
+                | Feature::DropColumnIfExists
+                | Feature::DropIndexIfExists
+                | Feature::TransactionalDdl
+                | Feature::PartialIndex
+        )
+    }
+
+    fn column_definition(&self, column: &Column) -> String {
+        // Computed columns take no type, and only persisted ones can be
+        // NOT NULL.
+        let name = self.identifier(&column.name);
+        if let Some(generated) = &column.generated {
+            return sql!(
+                self,
+                "{} AS ({}){}",
+                name,
+                Verbatim(&generated.expression),
+                match (generated.stored, column.nullable) {
+                    (true, true) => " PERSISTED",
+                    (true, false) => " PERSISTED NOT NULL",
+                    (false, _) => "",
+                }
+            );
+        }
+        sql!(
+            self,
+            "{} {}{}{}{}{}",
+            name,
+            self.render_type(&column.data_type),
+            Verbatim(self.collate(column.collation.as_deref())),
+            if column.auto_increment {
+                " IDENTITY(1,1)"
+            } else {
+                ""
+            },
+            if column.nullable { "" } else { " NOT NULL" },
+            enum_check(&name, &column.data_type)
+        )
+    }
+
+    fn add_column(
+        &self,
+        table: &str,
+        column: &Column,
+        _position: Option<&ColumnPosition>,
+        _if_not_exists: bool,
+    ) -> Vec<String> {
+        vec![sql!(
+            self,
+            "ALTER TABLE {} ADD {};",
+            self.table_identifier(table),
+            Verbatim(self.column_definition(column))
+        )]
+    }
+
+    fn drop_column(&self, table: &str, column: &str, if_exists: bool, _cascade: bool) -> String {
+        sql!(
+            self,
+            "ALTER TABLE {} DROP COLUMN {}{};",
+            self.table_identifier(table),
+            if if_exists { "IF EXISTS " } else { "" },
+            self.identifier(column)
+        )
+    }
+
+    fn rename_column(
+        &self,
+        table: &str,
+        old_name: &str,
+        new_name: &str,
+        _definition: Option<&Column>,
+    ) -> String {
+        // The new name is taken literally, brackets included.
+        sql!(
+            self,
+            "EXEC sp_rename {}, {}, 'COLUMN';",
+            quote_literal(&format!(
+                "{}.{}",
+                self.table_identifier(table),
+                self.identifier(old_name)
+            )),
+            quote_literal(new_name)
+        )
+    }
+
+    fn change_column_type(
+        &self,
+        table: &str,
+        column: &str,
+        data_type: &DataType,
+        options: &ColumnOptions,
+        _position: Option<&ColumnPosition>,
+    ) -> Result<Vec<String>, MigrationError> {
+        // ALTER COLUMN only takes the type and nullability; defaults and
+        // uniqueness are separate constraints, named after the unquoted
+        // table and column.
+        let (name, quoted_table, quoted_column) = (
+            format!("{}_{}", split_table(table).1, column),
+            self.table_identifier(table),
+            self.identifier(column),
+        );
+        let mut statements = vec![sql!(
+            self,
+            "ALTER TABLE {} ALTER COLUMN {} {}{}{};",
+            quoted_table,
+            quoted_column,
+            self.render_type(data_type),
+            Verbatim(self.collate(options.collation.as_deref())),
+            match options.nullable {
+                Some(true) => " NULL",
+                Some(false) => " NOT NULL",
+                None => "",
+            }
+        )];
+
+        if let Some(default) = &options.default {
+            statements.push(sql!(
+                self,
+                "ALTER TABLE {} ADD CONSTRAINT {} DEFAULT {} FOR {};",
+                quoted_table,
+                Verbatim(format!("DF_{}", name)),
+                Verbatim(default),
+                quoted_column
+            ));
+        }
+
+        if options.unique == Some(true) {
+            statements.push(sql!(
+                self,
+                "ALTER TABLE {} ADD CONSTRAINT {} UNIQUE ({});",
+                quoted_table,
+                Verbatim(format!("UQ_{}", name)),
+                quoted_column
+            ));
+        }
+
+        Ok(statements)
+    }
+
+    fn set_not_null(
+        &self,
+        table: &str,
+        column: &str,
+        current_type: Option<&DataType>,
+    ) -> Result<String, MigrationError> {
+        self.alter_nullability(table, column, current_type, false)
+    }
+
+    fn drop_not_null(
+        &self,
+        table: &str,
+        column: &str,
+        current_type: Option<&DataType>,
+    ) -> Result<String, MigrationError> {
+        self.alter_nullability(table, column, current_type, true)
+    }
+
+    fn set_column_default(
+        &self,
+        table: &str,
+        column: &str,
+        default: &DefaultValue,
+    ) -> Result<String, MigrationError> {
+        // Named like the constraint change_column_type adds, so either can be
+        // dropped by drop_column_default.
+        Ok(sql!(
+            self,
+            "ALTER TABLE {} ADD CONSTRAINT {} DEFAULT {} FOR {};",
+            self.table_identifier(table),
+            Verbatim(default_constraint(table, column)),
+            Verbatim(default),
+            self.identifier(column)
+        ))
+    }
+
+    fn drop_column_default(&self, table: &str, column: &str) -> Result<String, MigrationError> {
+        Ok(sql!(
+            self,
+            "ALTER TABLE {} DROP CONSTRAINT {};",
+            self.table_identifier(table),
+            Verbatim(default_constraint(table, column))
+        ))
+    }
+
+    fn concat_columns(&self, columns: &[String], separator: &str) -> String {
+        // CONCAT_WS needs SQL Server 2017 and skips NULLs.
+        sql!(
+            self,
+            "CONCAT_WS({}, {})",
+            quote_literal(separator),
+            identifier_list(self, columns)
+        )
+    }
+
+    fn create_schema(&self, name: &str, if_not_exists: bool) -> Result<String, MigrationError> {
+        // CREATE SCHEMA must be alone in its batch, hence EXEC.
+        Ok(if if_not_exists {
+            sql!(
+                self,
+                "IF SCHEMA_ID({}) IS NULL EXEC({});",
+                self.string_literal(name),
+                quote_literal(&sql!(self, "CREATE SCHEMA {}", self.identifier(name)))
+            )
+        } else {
+            sql!(self, "CREATE SCHEMA {};", self.identifier(name))
+        })
+    }
+
+    fn drop_schema(&self, name: &str) -> Result<String, MigrationError> {
+        Ok(sql!(self, "DROP SCHEMA {};", self.identifier(name)))
+    }
+
+    fn string_literal(&self, value: &str) -> String {
+        // N keeps characters outside the database's code page.
+        format!("N{}", quote_literal(value))
+    }
+
+    fn render_condition(&self, column: &str, operator: Operator, value: &str) -> String {
+        let column = self.identifier(column);
+        match operator {
+            // IS NOT DISTINCT FROM needs SQL Server 2022; ILIKE does not exist.
+            Operator::NullSafeEq => sql!(
+                self,
+                "({} = {} OR ({} IS NULL AND {} IS NULL))",
+                column,
+                Verbatim(value),
+                column,
+                Verbatim(value)
+            ),
+            Operator::ILike => sql!(self, "LOWER({}) LIKE LOWER({})", column, Verbatim(value)),
+            other => sql!(self, "{} {} {}", column, other.as_str(), Verbatim(value)),
+        }
+    }
+
+    fn select_column_data(&self, table: &str, id_column: &str, column: &str) -> String {
+        sql!(
+            self,
+            "SELECT {}, {} FROM {};",
+            self.quote_identifier(id_column),
+            self.quote_identifier(column),
+            quoted_table(self, table)
+        )
+    }
+
+    fn create_index(&self, table: &str, index: &CreateIndex) -> String {
+        // Prefix lengths and row thresholds have no T-SQL equivalent.
+        let columns: Vec<String> = index
+            .columns
+            .iter()
+            .map(|c| self.identifier(&c.name))
+            .collect();
+        sql!(
+            self,
+            "CREATE {}INDEX {} ON {} ({}){};",
+            if index.unique { "UNIQUE " } else { "" },
+            self.identifier(&index.index_name(table, self)),
+            self.table_identifier(table),
+            columns.join(", "),
+            Verbatim(index_filter(self, index))
+        )
+    }
+
+    fn drop_index(&self, table: &str, name: &str, if_exists: bool) -> String {
+        sql!(
+            self,
+            "DROP INDEX {}{} ON {};",
+            if if_exists { "IF EXISTS " } else { "" },
+            self.identifier(name),
+            self.table_identifier(table)
+        )
+    }
+
+    fn referential_action(&self, action: ReferentialAction) -> &'static str {
+        // NO ACTION is checked immediately here, so it is what RESTRICT means
+        // elsewhere.
+        match action {
+            ReferentialAction::Restrict => "NO ACTION",
+            other => other.as_str(),
+        }
+    }
+
+    fn create_view(&self, name: &str, query: &str, or_replace: bool) -> Vec<String> {
+        vec![sql!(
+            self,
+            "CREATE {}VIEW {} AS {};",
+            if or_replace { "OR ALTER " } else { "" },
+            self.table_identifier(name),
+            Verbatim(query)
+        )]
+    }
+
+    fn drop_table(&self, table: &str, if_exists: bool, _cascade: bool) -> String {
+        // There is no CASCADE; dependent foreign keys must be dropped first.
+        sql!(
+            self,
+            "DROP TABLE {}{};",
+            if if_exists { "IF EXISTS " } else { "" },
+            self.table_identifier(table)
+        )
+    }
+}
+
+/// Name of the default constraint on `column`, after the unquoted table so
+/// set_column_default and drop_column_default agree on it.
+fn default_constraint(table: &str, column: &str) -> String {
+    format!("DF_{}_{}", split_table(table).1, column)
+}
+
+/// SQL Server has no enum type, so the allowed values become a CHECK.
+fn enum_check(column: &str, data_type: &DataType) -> String {
+    match data_type {
+        DataType::Enum { variants, .. } => {
+            let variants: Vec<String> = variants.iter().map(|v| quote_literal(v)).collect();
+            format!(" CHECK ({} IN ({}))", column, variants.join(", "))
+        }
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_type::GeneratedColumn;
+    use crate::migration::UpdateValue;
+
+    #[test]
+    fn adds_columns_without_column_keyword() {
+        let column = Column {
+            name: "views".to_string(),
+            data_type: DataType::UnsignedInteger,
+            nullable: false,
+            auto_increment: false,
+            generated: None,
+            collation: None,
+        };
+        assert_eq!(
+            MssqlDialect::new().add_column("posts", &column, None, false),
+            ["ALTER TABLE posts ADD views BIGINT NOT NULL;"]
+        );
+    }
+
+    #[test]
+    fn declares_enum_values_as_check() {
+        let column = Column {
+            name: "status".to_string(),
+            data_type: DataType::Enum {
+                name: "user_status".to_string(),
+                variants: vec!["active".to_string(), "can't login".to_string()],
+            },
+            nullable: false,
+            auto_increment: false,
+            generated: None,
+            collation: None,
+        };
+        assert_eq!(
+            MssqlDialect::new().add_column("users", &column, None, false),
+            ["ALTER TABLE users ADD status VARCHAR(11) NOT NULL \
+             CHECK (status IN ('active', 'can''t login'));"]
+        );
+    }
+
+    #[test]
+    fn drops_columns() {
+        assert_eq!(
+            MssqlDialect::new().drop_column("users", "legacy_flag", false, false),
+            "ALTER TABLE users DROP COLUMN legacy_flag;"
+        );
+    }
+
+    #[test]
+    fn renames_columns_with_sp_rename() {
+        assert_eq!(
+            MssqlDialect::new().rename_column("users", "phone", "contact_number", None),
+            "EXEC sp_rename 'users.phone', 'contact_number', 'COLUMN';"
+        );
+    }
+
+    #[test]
+    fn changes_type_and_adds_constraints_separately() {
+        let options = ColumnOptions {
+            nullable: Some(false),
+            default: Some("0".to_string()),
+            unique: Some(true),
+            collation: None,
+            using: None,
+            concurrently: false,
+        };
+        assert_eq!(
+            MssqlDialect::new()
+                .change_column_type("posts", "views", &DataType::BigInt, &options, None)
+                .unwrap(),
+            vec![
+                "ALTER TABLE posts ALTER COLUMN views BIGINT NOT NULL;",
+                "ALTER TABLE posts ADD CONSTRAINT DF_posts_views DEFAULT 0 FOR views;",
+                "ALTER TABLE posts ADD CONSTRAINT UQ_posts_views UNIQUE (views);",
+            ]
+        );
+    }
+
+    #[test]
+    fn renders_types_sql_server_spells_differently() {
+        let dialect = MssqlDialect::new();
+        assert_eq!(dialect.render_type(&DataType::Boolean), "BIT");
+        assert_eq!(
+            dialect.render_type(&DataType::Timestamp {
+                with_timezone: false,
+                precision: None,
+            }),
+            "DATETIME2"
+        );
+        assert_eq!(
+            dialect.render_type(&DataType::Timestamp {
+                with_timezone: true,
+                precision: Some(3),
+            }),
+            "DATETIMEOFFSET(3)"
+        );
+        assert_eq!(dialect.render_type(&DataType::Json), "NVARCHAR(MAX)");
+        assert_eq!(
+            dialect.render_type(&DataType::Binary(Some(32))),
+            "VARBINARY(32)"
+        );
+        assert_eq!(
+            dialect.render_type(&DataType::Binary(None)),
+            "VARBINARY(MAX)"
+        );
+        assert_eq!(
+            dialect.render_type(&DataType::UnsignedBigInt),
+            "DECIMAL(20,0)"
+        );
+    }
+
+    #[test]
+    fn ignores_row_threshold_and_prefix_on_index() {
+        let mut index = CreateIndex {
+            name: None,
+            columns: vec!["bio".into()],
+            unique: true,
+            conditional_on_rows: Some(1_000_000),
+            concurrently: false,
+            if_not_exists: false,
+            where_clause: None,
+        };
+        index.columns[0].prefix_length = Some(64);
+        assert_eq!(
+            MssqlDialect::new().create_index("users", &index),
+            "CREATE UNIQUE INDEX idx_users_bio ON users (bio);"
+        );
+    }
+
+    #[test]
+    fn drops_table_ignoring_cascade() {
+        assert_eq!(
+            MssqlDialect::new().drop_table("users", true, true),
+            "DROP TABLE IF EXISTS users;"
+        );
+    }
+
+    #[test]
+    fn quotes_identifiers_in_external_process_select() {
+        assert_eq!(
+            MssqlDialect::new().select_column_data("orders", "id", "order"),
+            "SELECT [id], [order] FROM [orders];"
+        );
+        assert_eq!(
+            MssqlDialect::new().select_column_data("dbo.orders", "id", "order"),
+            "SELECT [id], [order] FROM [dbo].[orders];"
+        );
+        assert_eq!(MssqlDialect::new().quote_identifier("we]ird"), "[we]]ird]");
+    }
+
+    #[test]
+    fn updates_single_row_by_id() {
+        assert_eq!(
+            MssqlDialect::new().update_column_data_by_id(
+                "posts",
+                "id",
+                "7",
+                "slug",
+                &UpdateValue::Fixed("'a'".to_string())
+            ),
+            "UPDATE posts SET slug = 'a' WHERE id = 7;"
+        );
+    }
+    #[test]
+    fn spells_restrict_as_no_action() {
+        assert_eq!(
+            MssqlDialect::new().referential_action(ReferentialAction::Restrict),
+            "NO ACTION"
+        );
+        assert_eq!(
+            MssqlDialect::new().referential_action(ReferentialAction::SetNull),
+            "SET NULL"
+        );
+    }
+    #[test]
+    fn names_placeholders_by_position() {
+        assert_eq!(MssqlDialect::new().placeholder(1), "@p1");
+        assert_eq!(MssqlDialect::new().placeholder(12), "@p12");
+    }
+    #[test]
+    fn replaces_view_with_create_or_alter() {
+        assert_eq!(
+            MssqlDialect::new().create_view("active_users", "SELECT id FROM users", true),
+            ["CREATE OR ALTER VIEW active_users AS SELECT id FROM users;"]
+        );
+    }
+    #[test]
+    fn manages_defaults_as_named_constraints() {
+        assert_eq!(
+            MssqlDialect::new()
+                .set_column_default("users", "status", &DefaultValue::Literal("new".to_string()))
+                .unwrap(),
+            "ALTER TABLE users ADD CONSTRAINT DF_users_status DEFAULT 'new' FOR status;"
+        );
+        assert_eq!(
+            MssqlDialect::new()
+                .drop_column_default("users", "status")
+                .unwrap(),
+            "ALTER TABLE users DROP CONSTRAINT DF_users_status;"
+        );
+    }
+    #[test]
+    fn restates_type_to_change_nullability() {
+        assert_eq!(
+            MssqlDialect::new()
+                .set_not_null("users", "email", Some(&DataType::Varchar(255)))
+                .unwrap(),
+            "ALTER TABLE users ALTER COLUMN email VARCHAR(255) NOT NULL;"
+        );
+        assert!(MssqlDialect::new()
+            .drop_not_null("users", "email", None)
+            .is_err());
+    }
+    #[test]
+    fn spells_out_null_safe_equality() {
+        assert_eq!(
+            MssqlDialect::new().render_condition("a", Operator::NullSafeEq, "b"),
+            "(a = b OR (a IS NULL AND b IS NULL))"
+        );
+    }
+    #[test]
+    fn adds_computed_columns_without_a_type() {
+        let column = Column {
+            name: "total".to_string(),
+            data_type: DataType::Decimal(10, 2),
+            nullable: false,
+            auto_increment: false,
+            generated: Some(GeneratedColumn {
+                expression: "price * quantity".to_string(),
+                stored: true,
+            }),
+            collation: None,
+        };
+        assert_eq!(
+            MssqlDialect::new().add_column("order_lines", &column, None, false),
+            ["ALTER TABLE order_lines ADD total AS (price * quantity) PERSISTED NOT NULL;"]
+        );
+    }
+
+    #[test]
+    fn quotes_reserved_names_when_concatenating() {
+        assert_eq!(
+            MssqlDialect::new().concat_columns(&["Group".to_string(), "last".to_string()], " "),
+            "CONCAT_WS(' ', [Group], last)"
+        );
+    }
+}
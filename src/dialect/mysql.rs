@@ -0,0 +1,1263 @@
+use std::fmt;
+use std::sync::Arc;
+
+use super::keyword::{sql, Verbatim};
+use super::{
+    generated_clause, identifier_list, quote_literal, quoted_table, split_table, values_list,
+    DefaultNaming, Dialect, Feature, KeywordCase, NamingStrategy, QuoteMode, TypeAliases,
+};
+use crate::data_type::{Column, ColumnOptions, DataType, DefaultValue, Precision};
+use crate::error::MigrationError;
+use crate::migration::{
+    AddEnumValue, ColumnPosition, CreateFunction, CreateIndex, DropFunction, Operator,
+    PartitionBound, Precondition, TableOptions,
+};
+
+/// How `ALTER TABLE` may rebuild the table; see [`OnlineDdl`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Default,
+    Instant,
+    Inplace,
+    Copy,
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Algorithm::Default => "DEFAULT",
+            Algorithm::Instant => "INSTANT",
+            Algorithm::Inplace => "INPLACE",
+            Algorithm::Copy => "COPY",
+        })
+    }
+}
+
+/// How much concurrent access `ALTER TABLE` allows; see [`OnlineDdl`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lock {
+    Default,
+    None,
+    Shared,
+    Exclusive,
+}
+
+impl fmt::Display for Lock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Lock::Default => "DEFAULT",
+            Lock::None => "NONE",
+            Lock::Shared => "SHARED",
+            Lock::Exclusive => "EXCLUSIVE",
+        })
+    }
+}
+
+/// `ALGORITHM` and `LOCK` clauses added to column changes. MySQL refuses
+/// the statement when the change cannot be made that way, instead of
+/// silently falling back to copying and locking the table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OnlineDdl {
+    pub algorithm: Option<Algorithm>,
+    pub lock: Option<Lock>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MySqlDialect {
+    type_aliases: TypeAliases,
+    quote_mode: QuoteMode,
+    keyword_case: KeywordCase,
+    naming: Option<Arc<dyn NamingStrategy>>,
+    online_ddl: OnlineDdl,
+}
+
+impl MySqlDialect {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_quote_mode(mut self, quote_mode: QuoteMode) -> Self {
+        self.quote_mode = quote_mode;
+        self
+    }
+
+    pub fn with_keyword_case(mut self, keyword_case: KeywordCase) -> Self {
+        self.keyword_case = keyword_case;
+        self
+    }
+
+    /// Names the indexes and constraints migrations leave unnamed.
+    pub fn with_naming_strategy(mut self, strategy: impl NamingStrategy + 'static) -> Self {
+        self.naming = Some(Arc::new(strategy));
+        self
+    }
+
+    /// Shares the naming strategy of a dialect delegating to this one.
+    pub(super) fn with_naming(mut self, naming: Option<Arc<dyn NamingStrategy>>) -> Self {
+        self.naming = naming;
+        self
+    }
+
+    /// Adds `ALGORITHM`/`LOCK` clauses to column additions, drops and type
+    /// changes.
+    pub fn with_online_ddl(mut self, online_ddl: OnlineDdl) -> Self {
+        self.online_ddl = online_ddl;
+        self
+    }
+
+    /// Puts [`Dialect::alter_options`] before the statement's `;`.
+    fn online(&self, sql: String) -> String {
+        match sql.strip_suffix(';') {
+            Some(sql) => sql!(self, "{}{};", Verbatim(sql), self.alter_options()),
+            None => sql,
+        }
+    }
+}
+
+impl Dialect for MySqlDialect {
+    fn name(&self) -> &str {
+        "mysql"
+    }
+
+    fn type_aliases(&self) -> &TypeAliases {
+        &self.type_aliases
+    }
+
+    fn type_aliases_mut(&mut self) -> &mut TypeAliases {
+        &mut self.type_aliases
+    }
+
+    fn quote_mode(&self) -> QuoteMode {
+        self.quote_mode
+    }
+
+    fn keyword_case(&self) -> KeywordCase {
+        self.keyword_case
+    }
+
+    fn naming_strategy(&self) -> &dyn NamingStrategy {
+        self.naming.as_deref().unwrap_or(&DefaultNaming)
+    }
+
+    fn max_identifier_length(&self) -> Option<usize> {
+        Some(64)
+    }
+
+    fn render_type(&self, data_type: &DataType) -> String {
+        match data_type {
+            // TIMESTAMP converts to and from the session time zone, which is
+            // the closest MySQL gets to a zoned type; DATETIME is stored as-is.
+            DataType::Timestamp {
+                with_timezone,
+                precision,
+            } => format!(
+                "{}{}",
+                if *with_timezone {
+                    "TIMESTAMP"
+                } else {
+                    "DATETIME"
+                },
+                Precision(*precision)
+            ),
+            DataType::Array(_) => "JSON".to_string(),
+            DataType::Custom(name) => self.type_aliases.resolve(name),
+            other => other.to_string(),
+        }
+    }
+
+    fn supports(&self, feature: Feature) -> bool {
+        // DDL commits implicitly, and unlike MariaDB there is no
+        // IF [NOT] EXISTS for columns.
+        matches!(
+            feature,
+            Feature::ColumnPosition
+                | Feature::IndexPrefix
+                | Feature::VirtualColumns
+                | Feature::InsertIgnore
+                | Feature::GranteeHost
+                | Feature::TableOptions
+                | Feature::CombinedAlter
+        )
+    }
+
+    fn alter_options(&self) -> String {
+        let mut sql = String::new();
+        if let Some(algorithm) = self.online_ddl.algorithm {
+            sql.push_str(&sql!(self, ", ALGORITHM={}", algorithm));
+        }
+        if let Some(lock) = self.online_ddl.lock {
+            sql.push_str(&sql!(self, ", LOCK={}", lock));
+        }
+        sql
+    }
+
+    fn table_options(&self, options: &TableOptions) -> String {
+        let mut sql = String::new();
+        if let Some(engine) = &options.engine {
+            sql.push_str(&sql!(self, " ENGINE={}", Verbatim(engine)));
+        }
+        if let Some(charset) = &options.charset {
+            sql.push_str(&sql!(self, " DEFAULT CHARSET={}", Verbatim(charset)));
+        }
+        if let Some(collation) = &options.collation {
+            sql.push_str(&sql!(self, " COLLATE={}", Verbatim(collation)));
+        }
+        sql
+    }
+
+    fn column_definition(&self, column: &Column) -> String {
+        column_definition(self, column)
+    }
+
+    fn add_column(
+        &self,
+        table: &str,
+        column: &Column,
+        position: Option<&ColumnPosition>,
+        _if_not_exists: bool,
+    ) -> Vec<String> {
+        // MySQL has no IF [NOT] EXISTS for columns, unlike MariaDB.
+        vec![self.online(add_column(self, table, column, position, false))]
+    }
+
+    fn drop_column(&self, table: &str, column: &str, _if_exists: bool, _cascade: bool) -> String {
+        self.online(sql!(
+            self,
+            "ALTER TABLE {} DROP COLUMN {};",
+            self.table_identifier(table),
+            self.identifier(column)
+        ))
+    }
+
+    fn rename_column(
+        &self,
+        table: &str,
+        old_name: &str,
+        new_name: &str,
+        definition: Option<&Column>,
+    ) -> String {
+        rename_column(self, table, old_name, new_name, definition)
+    }
+
+    fn change_column_type(
+        &self,
+        table: &str,
+        column: &str,
+        data_type: &DataType,
+        options: &ColumnOptions,
+        position: Option<&ColumnPosition>,
+    ) -> Result<Vec<String>, MigrationError> {
+        Ok(vec![self.online(change_column_type(
+            self, table, column, data_type, options, position,
+        ))])
+    }
+
+    fn set_not_null(
+        &self,
+        table: &str,
+        column: &str,
+        current_type: Option<&DataType>,
+    ) -> Result<String, MigrationError> {
+        modify_nullability(self, table, column, current_type, false)
+    }
+
+    fn drop_not_null(
+        &self,
+        table: &str,
+        column: &str,
+        current_type: Option<&DataType>,
+    ) -> Result<String, MigrationError> {
+        modify_nullability(self, table, column, current_type, true)
+    }
+
+    fn set_column_default(
+        &self,
+        table: &str,
+        column: &str,
+        default: &DefaultValue,
+    ) -> Result<String, MigrationError> {
+        // Expression defaults must be parenthesised (8.0.13+).
+        let default = match default {
+            DefaultValue::Expression(sql) => format!("({})", sql),
+            other => other.to_string(),
+        };
+        Ok(sql!(
+            self,
+            "ALTER TABLE {} ALTER COLUMN {} SET DEFAULT {};",
+            self.table_identifier(table),
+            self.identifier(column),
+            Verbatim(default)
+        ))
+    }
+
+    fn string_literal(&self, value: &str) -> String {
+        // Backslashes start escape sequences unless the NO_BACKSLASH_ESCAPES
+        // SQL mode is set, which this assumes it is not.
+        quote_literal(&value.replace('\\', "\\\\"))
+    }
+
+    fn render_condition(&self, column: &str, operator: Operator, value: &str) -> String {
+        let column = self.identifier(column);
+        match operator {
+            Operator::NullSafeEq => sql!(self, "{} <=> {}", column, Verbatim(value)),
+            // No ILIKE; lowering both sides works whatever the collation.
+            Operator::ILike => sql!(self, "LOWER({}) LIKE LOWER({})", column, Verbatim(value)),
+            other => sql!(self, "{} {} {}", column, other.as_str(), Verbatim(value)),
+        }
+    }
+
+    fn insert_rows(
+        &self,
+        table: &str,
+        columns: &[String],
+        rows: &[Vec<String>],
+        on_conflict_do_nothing: bool,
+    ) -> String {
+        sql!(
+            self,
+            "INSERT{} INTO {} ({}) VALUES {};",
+            if on_conflict_do_nothing {
+                " IGNORE"
+            } else {
+                ""
+            },
+            self.table_identifier(table),
+            identifier_list(self, columns),
+            Verbatim(values_list(rows))
+        )
+    }
+
+    fn select_column_data(&self, table: &str, id_column: &str, column: &str) -> String {
+        sql!(
+            self,
+            "SELECT {}, {} FROM {};",
+            self.quote_identifier(id_column),
+            self.quote_identifier(column),
+            quoted_table(self, table)
+        )
+    }
+
+    fn create_index(&self, table: &str, index: &CreateIndex) -> String {
+        create_index(self, table, index, false)
+    }
+
+    fn drop_index(&self, table: &str, name: &str, _if_exists: bool) -> String {
+        drop_index(self, table, name, false)
+    }
+
+    fn drop_table(&self, table: &str, if_exists: bool, _cascade: bool) -> String {
+        // MySQL parses CASCADE on DROP TABLE but does nothing with it, so it
+        // is left out rather than suggesting dependents get dropped.
+        sql!(
+            self,
+            "DROP TABLE {}{};",
+            if if_exists { "IF EXISTS " } else { "" },
+            self.table_identifier(table)
+        )
+    }
+
+    fn restart_sequence(&self, table: &str, _sequence: &str, value: Option<u64>) -> String {
+        // Values at or below the current maximum id are raised to one past it,
+        // so 1 means "right after the existing rows".
+        sql!(
+            self,
+            "ALTER TABLE {} AUTO_INCREMENT = {};",
+            self.table_identifier(table),
+            value.unwrap_or(1)
+        )
+    }
+
+    fn drop_unique_constraint(&self, table: &str, name: &str) -> String {
+        // Unique constraints are indexes; DROP CONSTRAINT needs 8.0.19.
+        sql!(
+            self,
+            "ALTER TABLE {} DROP INDEX {};",
+            self.table_identifier(table),
+            self.identifier(name)
+        )
+    }
+
+    fn add_primary_key(
+        &self,
+        table: &str,
+        _name: &str,
+        columns: &[String],
+    ) -> Result<String, MigrationError> {
+        Ok(sql!(
+            self,
+            "ALTER TABLE {} ADD PRIMARY KEY ({});",
+            self.table_identifier(table),
+            identifier_list(self, columns)
+        ))
+    }
+
+    fn drop_primary_key(
+        &self,
+        table: &str,
+        _name: &str,
+        auto_increment_column: Option<&Column>,
+    ) -> Result<String, MigrationError> {
+        Ok(drop_primary_key(self, table, auto_increment_column))
+    }
+
+    fn drop_check_constraint(&self, table: &str, name: &str) -> Result<String, MigrationError> {
+        // DROP CONSTRAINT only arrived in 8.0.19; DROP CHECK works from 8.0.16,
+        // the first release that enforces checks at all.
+        Ok(sql!(
+            self,
+            "ALTER TABLE {} DROP CHECK {};",
+            self.table_identifier(table),
+            self.identifier(name)
+        ))
+    }
+
+    fn add_enum_value(&self, table: &str, add: &AddEnumValue) -> Result<String, MigrationError> {
+        add_enum_value(self, table, add)
+    }
+
+    fn grantee(&self, role: &str, host: Option<&str>) -> String {
+        match host {
+            Some(host) => format!("{}@{}", quote_literal(role), quote_literal(host)),
+            None => quote_literal(role),
+        }
+    }
+
+    fn create_partition(
+        &self,
+        table: &str,
+        partition: &str,
+        bound: &PartitionBound,
+    ) -> Result<String, MigrationError> {
+        create_partition(self, table, partition, bound)
+    }
+
+    fn drop_partition(&self, table: &str, partition: &str) -> Result<String, MigrationError> {
+        Ok(sql!(
+            self,
+            "ALTER TABLE {} DROP PARTITION {};",
+            self.table_identifier(table),
+            self.identifier(partition)
+        ))
+    }
+
+    fn add_identity(
+        &self,
+        table: &str,
+        column: &str,
+        _always: bool,
+        start: Option<i64>,
+        current_type: Option<&DataType>,
+    ) -> Result<Vec<String>, MigrationError> {
+        modify_auto_increment(self, table, column, start, current_type)
+    }
+
+    fn drop_identity(
+        &self,
+        table: &str,
+        column: &str,
+        current_type: Option<&DataType>,
+    ) -> Result<String, MigrationError> {
+        // Restating the column without AUTO_INCREMENT removes it.
+        modify_nullability(self, table, column, current_type, false)
+    }
+
+    fn restart_identity(
+        &self,
+        table: &str,
+        _column: &str,
+        start: i64,
+    ) -> Result<String, MigrationError> {
+        // A table has at most one AUTO_INCREMENT column.
+        Ok(sql!(
+            self,
+            "ALTER TABLE {} AUTO_INCREMENT = {};",
+            self.table_identifier(table),
+            start
+        ))
+    }
+
+    fn reorder_column(
+        &self,
+        table: &str,
+        column: &Column,
+        position: &ColumnPosition,
+    ) -> Result<String, MigrationError> {
+        Ok(reorder_column(self, table, column, position))
+    }
+
+    fn create_schema(&self, name: &str, if_not_exists: bool) -> Result<String, MigrationError> {
+        // A MySQL schema is a database.
+        Ok(sql!(
+            self,
+            "CREATE DATABASE {}{};",
+            if if_not_exists { "IF NOT EXISTS " } else { "" },
+            self.identifier(name)
+        ))
+    }
+
+    fn drop_schema(&self, name: &str) -> Result<String, MigrationError> {
+        Ok(sql!(self, "DROP DATABASE {};", self.identifier(name)))
+    }
+
+    fn concat_columns(&self, columns: &[String], separator: &str) -> String {
+        concat_ws(self, columns, separator)
+    }
+
+    fn conditional(
+        &self,
+        table: &str,
+        precondition: &Precondition,
+        sql: &str,
+    ) -> Result<Vec<String>, MigrationError> {
+        Ok(conditional(self, table, precondition, sql))
+    }
+
+    fn create_function(&self, function: &CreateFunction) -> Result<Vec<String>, MigrationError> {
+        // MySQL has no OR REPLACE for routines.
+        create_function(self, function, false)
+    }
+
+    fn drop_function(&self, function: &DropFunction) -> Result<String, MigrationError> {
+        Ok(drop_function(self, function))
+    }
+
+    fn quote_identifier(&self, identifier: &str) -> String {
+        format!("`{}`", identifier.replace('`', "``"))
+    }
+}
+
+// Column definitions are shared with MariaDB, which renders some types
+// differently, so they take the dialect whose `render_type` should be used.
+
+pub(super) fn add_column(
+    dialect: &dyn Dialect,
+    table: &str,
+    column: &Column,
+    position: Option<&ColumnPosition>,
+    if_not_exists: bool,
+) -> String {
+    sql!(
+        dialect,
+        "ALTER TABLE {} ADD COLUMN {}{}{};",
+        dialect.table_identifier(table),
+        if if_not_exists { "IF NOT EXISTS " } else { "" },
+        Verbatim(dialect.column_definition(column)),
+        position_clause(dialect, position)
+    )
+}
+
+fn position_clause(dialect: &dyn Dialect, position: Option<&ColumnPosition>) -> String {
+    match position {
+        Some(ColumnPosition::First) => sql!(dialect, " FIRST"),
+        Some(ColumnPosition::After(anchor)) => {
+            sql!(dialect, " AFTER {}", dialect.identifier(anchor))
+        }
+        None => String::new(),
+    }
+}
+
+/// `RENAME COLUMN` needs MySQL 8.0 or MariaDB 10.5.2. Given the column's
+/// definition, `CHANGE COLUMN` restates it under the new name instead.
+pub(super) fn rename_column(
+    dialect: &dyn Dialect,
+    table: &str,
+    old_name: &str,
+    new_name: &str,
+    definition: Option<&Column>,
+) -> String {
+    match definition {
+        Some(definition) => sql!(
+            dialect,
+            "ALTER TABLE {} CHANGE COLUMN {} {};",
+            dialect.table_identifier(table),
+            dialect.identifier(old_name),
+            Verbatim(dialect.column_definition(&Column {
+                name: new_name.to_string(),
+                ..definition.clone()
+            }))
+        ),
+        None => sql!(
+            dialect,
+            "ALTER TABLE {} RENAME COLUMN {} TO {};",
+            dialect.table_identifier(table),
+            dialect.identifier(old_name),
+            dialect.identifier(new_name)
+        ),
+    }
+}
+
+pub(super) fn reorder_column(
+    dialect: &dyn Dialect,
+    table: &str,
+    column: &Column,
+    position: &ColumnPosition,
+) -> String {
+    sql!(
+        dialect,
+        "ALTER TABLE {} MODIFY COLUMN {}{};",
+        dialect.table_identifier(table),
+        Verbatim(dialect.column_definition(column)),
+        position_clause(dialect, Some(position))
+    )
+}
+
+/// `if_not_exists` is for MariaDB; MySQL has no such guard.
+pub(super) fn create_index(
+    dialect: &dyn Dialect,
+    table: &str,
+    index: &CreateIndex,
+    if_not_exists: bool,
+) -> String {
+    // No cheap row estimate to branch on, so conditional_on_rows is ignored.
+    let columns: Vec<String> = index
+        .columns
+        .iter()
+        .map(|column| match column.prefix_length {
+            Some(length) => format!("{}({})", dialect.identifier(&column.name), length),
+            None => dialect.identifier(&column.name),
+        })
+        .collect();
+    sql!(
+        dialect,
+        "CREATE {}INDEX {}{} ON {} ({});",
+        if index.unique { "UNIQUE " } else { "" },
+        if if_not_exists { "IF NOT EXISTS " } else { "" },
+        dialect.identifier(&index.index_name(table, dialect)),
+        dialect.table_identifier(table),
+        columns.join(", ")
+    )
+}
+
+/// `if_exists` is for MariaDB; MySQL has no such guard.
+pub(super) fn drop_index(
+    dialect: &dyn Dialect,
+    table: &str,
+    name: &str,
+    if_exists: bool,
+) -> String {
+    sql!(
+        dialect,
+        "DROP INDEX {}{} ON {};",
+        if if_exists { "IF EXISTS " } else { "" },
+        dialect.identifier(name),
+        dialect.table_identifier(table)
+    )
+}
+
+pub(super) fn column_definition(dialect: &dyn Dialect, column: &Column) -> String {
+    let generated = match &column.generated {
+        Some(generated) => generated_clause(dialect, generated),
+        None => String::new(),
+    };
+    sql!(
+        dialect,
+        "{} {}{}{}{}{}",
+        dialect.identifier(&column.name),
+        dialect.render_type(&column.data_type),
+        Verbatim(dialect.collate(column.collation.as_deref())),
+        Verbatim(generated),
+        if column.nullable { "" } else { " NOT NULL" },
+        if column.auto_increment {
+            " AUTO_INCREMENT"
+        } else {
+            ""
+        }
+    )
+}
+
+pub(super) fn change_column_type(
+    dialect: &dyn Dialect,
+    table: &str,
+    column: &str,
+    data_type: &DataType,
+    options: &ColumnOptions,
+    position: Option<&ColumnPosition>,
+) -> String {
+    let mut sql = sql!(
+        dialect,
+        "ALTER TABLE {} MODIFY COLUMN {} {}",
+        dialect.table_identifier(table),
+        dialect.identifier(column),
+        dialect.render_type(data_type)
+    );
+
+    sql.push_str(&dialect.collate(options.collation.as_deref()));
+
+    if let Some(nullable) = options.nullable {
+        sql.push_str(&sql!(
+            dialect,
+            "{}",
+            if nullable { " NULL" } else { " NOT NULL" }
+        ));
+    }
+
+    if let Some(default) = &options.default {
+        sql.push_str(&sql!(dialect, " DEFAULT {}", Verbatim(default)));
+    }
+
+    if options.unique == Some(true) {
+        sql.push_str(&sql!(dialect, " UNIQUE"));
+    }
+
+    sql.push_str(&position_clause(dialect, position));
+    sql.push(';');
+    sql
+}
+
+pub(super) fn modify_nullability(
+    dialect: &dyn Dialect,
+    table: &str,
+    column: &str,
+    current_type: Option<&DataType>,
+    nullable: bool,
+) -> Result<String, MigrationError> {
+    let data_type = current_type.ok_or_else(|| MigrationError::MissingColumnType {
+        dialect: dialect.name().to_string(),
+        column: column.to_string(),
+    })?;
+    Ok(sql!(
+        dialect,
+        "ALTER TABLE {} MODIFY COLUMN {} {} {};",
+        dialect.table_identifier(table),
+        dialect.identifier(column),
+        dialect.render_type(data_type),
+        if nullable { "NULL" } else { "NOT NULL" }
+    ))
+}
+
+pub(super) fn modify_auto_increment(
+    dialect: &dyn Dialect,
+    table: &str,
+    column: &str,
+    start: Option<i64>,
+    current_type: Option<&DataType>,
+) -> Result<Vec<String>, MigrationError> {
+    let data_type = current_type.ok_or_else(|| MigrationError::MissingColumnType {
+        dialect: dialect.name().to_string(),
+        column: column.to_string(),
+    })?;
+    let table = dialect.table_identifier(table);
+    let mut statements = vec![sql!(
+        dialect,
+        "ALTER TABLE {} MODIFY COLUMN {} {} NOT NULL AUTO_INCREMENT;",
+        table,
+        dialect.identifier(column),
+        dialect.render_type(data_type)
+    )];
+    if let Some(start) = start {
+        statements.push(sql!(
+            dialect,
+            "ALTER TABLE {} AUTO_INCREMENT = {};",
+            table,
+            start
+        ));
+    }
+    Ok(statements)
+}
+
+pub(super) fn add_enum_value(
+    dialect: &dyn Dialect,
+    table: &str,
+    add: &AddEnumValue,
+) -> Result<String, MigrationError> {
+    // Enums are part of the column type, so the column is redefined with the
+    // full variant list; the position is already given by that list.
+    let column = add.column.as_ref().ok_or_else(|| {
+        MigrationError::unsupported(
+            dialect.name(),
+            "ALTER TYPE ADD VALUE without the column definition",
+        )
+    })?;
+    Ok(sql!(
+        dialect,
+        "ALTER TABLE {} MODIFY COLUMN {} {}{};",
+        dialect.table_identifier(table),
+        dialect.identifier(&column.name),
+        dialect.render_type(&column.data_type),
+        if column.nullable { "" } else { " NOT NULL" }
+    ))
+}
+
+/// `CONCAT_WS` skips `NULL` arguments; `||` is logical OR in MySQL.
+pub(super) fn concat_ws(dialect: &dyn Dialect, columns: &[String], separator: &str) -> String {
+    sql!(
+        dialect,
+        "CONCAT_WS({}, {})",
+        quote_literal(separator),
+        identifier_list(dialect, columns)
+    )
+}
+
+/// MySQL has no anonymous blocks, so the statement is chosen at run time and
+/// executed as a prepared statement. `DO 0` does nothing.
+pub(super) fn conditional(
+    dialect: &dyn Dialect,
+    table: &str,
+    precondition: &Precondition,
+    sql: &str,
+) -> Vec<String> {
+    let (column, comparison) = match precondition {
+        Precondition::ColumnExists(column) => (column, "> 0"),
+        Precondition::ColumnMissing(column) => (column, "= 0"),
+    };
+    let (database, bare_table) = split_table(table);
+    vec![
+        sql!(
+            dialect,
+            "SET @drift_sql = IF((SELECT COUNT(*) FROM information_schema.columns WHERE table_schema = {} AND table_name = {} AND column_name = {}) {}, {}, 'DO 0');",
+            database.map_or("DATABASE()".to_string(), quote_literal),
+            quote_literal(bare_table),
+            quote_literal(column),
+            comparison,
+            quote_literal(sql.trim_end().trim_end_matches(';'))
+        ),
+        sql!(dialect, "PREPARE drift_stmt FROM @drift_sql;"),
+        sql!(dialect, "EXECUTE drift_stmt;"),
+        sql!(dialect, "DEALLOCATE PREPARE drift_stmt;"),
+    ]
+}
+
+pub(super) fn create_partition(
+    dialect: &dyn Dialect,
+    table: &str,
+    partition: &str,
+    bound: &PartitionBound,
+) -> Result<String, MigrationError> {
+    let values = match bound {
+        // Each range partition starts where the previous one ends.
+        PartitionBound::Range { to, .. } => sql!(dialect, "VALUES LESS THAN ({})", Verbatim(to)),
+        PartitionBound::List(values) => {
+            sql!(dialect, "VALUES IN ({})", Verbatim(values.join(", ")))
+        }
+        PartitionBound::Default => {
+            return Err(MigrationError::unsupported(
+                dialect.name(),
+                "DEFAULT PARTITION",
+            ))
+        }
+    };
+    Ok(sql!(
+        dialect,
+        "ALTER TABLE {} ADD PARTITION (PARTITION {} {});",
+        dialect.table_identifier(table),
+        dialect.identifier(partition),
+        Verbatim(values)
+    ))
+}
+
+pub(super) fn create_function(
+    dialect: &dyn Dialect,
+    function: &CreateFunction,
+    or_replace_supported: bool,
+) -> Result<Vec<String>, MigrationError> {
+    if !function.language.eq_ignore_ascii_case("sql") {
+        return Err(MigrationError::unsupported(
+            dialect.name(),
+            &format!("LANGUAGE {}", function.language),
+        ));
+    }
+    let (kind, returns) = match &function.returns {
+        Some(returns) => ("FUNCTION", sql!(dialect, " RETURNS {}", Verbatim(returns))),
+        None => ("PROCEDURE", String::new()),
+    };
+    let create = sql!(
+        dialect,
+        "CREATE {}{} {}({}){} {};",
+        if function.or_replace && or_replace_supported {
+            "OR REPLACE "
+        } else {
+            ""
+        },
+        kind,
+        Verbatim(&function.name),
+        Verbatim(&function.args),
+        Verbatim(returns),
+        Verbatim(&function.body)
+    );
+    if function.or_replace && !or_replace_supported {
+        Ok(vec![
+            sql!(
+                dialect,
+                "DROP {} IF EXISTS {};",
+                kind,
+                Verbatim(&function.name)
+            ),
+            create,
+        ])
+    } else {
+        Ok(vec![create])
+    }
+}
+
+pub(super) fn drop_function(dialect: &dyn Dialect, function: &DropFunction) -> String {
+    // Routines cannot be overloaded, so the arguments are not needed.
+    sql!(
+        dialect,
+        "DROP {} {}{};",
+        if function.procedure {
+            "PROCEDURE"
+        } else {
+            "FUNCTION"
+        },
+        if function.if_exists { "IF EXISTS " } else { "" },
+        Verbatim(&function.name)
+    )
+}
+
+pub(super) fn drop_primary_key(
+    dialect: &dyn Dialect,
+    table: &str,
+    auto_increment_column: Option<&Column>,
+) -> String {
+    match auto_increment_column {
+        // An AUTO_INCREMENT column must be a key, so it is redefined without
+        // it in the same statement.
+        Some(column) => sql!(
+            dialect,
+            "ALTER TABLE {} MODIFY COLUMN {} {}{}, DROP PRIMARY KEY;",
+            dialect.table_identifier(table),
+            dialect.identifier(&column.name),
+            dialect.render_type(&column.data_type),
+            if column.nullable { "" } else { " NOT NULL" }
+        ),
+        None => sql!(
+            dialect,
+            "ALTER TABLE {} DROP PRIMARY KEY;",
+            dialect.table_identifier(table)
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::migration::{UpdateValue, WhereCondition};
+
+    #[test]
+    fn adds_unsigned_columns() {
+        let column = Column {
+            name: "views".to_string(),
+            data_type: DataType::UnsignedBigInt,
+            nullable: false,
+            auto_increment: false,
+            generated: None,
+            collation: None,
+        };
+        assert_eq!(
+            MySqlDialect::new().add_column("posts", &column, None, false),
+            ["ALTER TABLE posts ADD COLUMN views BIGINT UNSIGNED NOT NULL;"]
+        );
+    }
+
+    #[test]
+    fn widens_signed_integer_to_unsigned() {
+        let options = ColumnOptions {
+            nullable: Some(false),
+            default: Some("0".to_string()),
+            unique: None,
+            collation: None,
+            using: None,
+            concurrently: false,
+        };
+        assert_eq!(
+            MySqlDialect::new()
+                .change_column_type("posts", "views", &DataType::UnsignedInteger, &options, None)
+                .unwrap(),
+            vec!["ALTER TABLE posts MODIFY COLUMN views INTEGER UNSIGNED NOT NULL DEFAULT 0;"]
+        );
+    }
+
+    #[test]
+    fn declares_enum_values_inline() {
+        let column = Column {
+            name: "status".to_string(),
+            data_type: DataType::Enum {
+                name: "user_status".to_string(),
+                variants: vec!["active".to_string(), "can't login".to_string()],
+            },
+            nullable: false,
+            auto_increment: false,
+            generated: None,
+            collation: None,
+        };
+        assert_eq!(
+            MySqlDialect::new().add_column("users", &column, None, false),
+            ["ALTER TABLE users ADD COLUMN status ENUM('active','can''t login') NOT NULL;"]
+        );
+    }
+
+    #[test]
+    fn ignores_row_threshold_on_index() {
+        let index = CreateIndex {
+            name: None,
+            columns: vec!["created_at".into()],
+            unique: false,
+            conditional_on_rows: Some(1_000_000),
+            concurrently: false,
+            if_not_exists: false,
+            where_clause: None,
+        };
+        assert_eq!(
+            MySqlDialect::new().create_index("events", &index),
+            "CREATE INDEX idx_events_created_at ON events (created_at);"
+        );
+    }
+
+    #[test]
+    fn drops_table_ignoring_cascade() {
+        let dialect = MySqlDialect::new();
+        assert_eq!(
+            dialect.drop_table("users", false, false),
+            "DROP TABLE users;"
+        );
+        assert_eq!(
+            dialect.drop_table("users", true, false),
+            "DROP TABLE IF EXISTS users;"
+        );
+        assert_eq!(
+            dialect.drop_table("users", false, true),
+            "DROP TABLE users;"
+        );
+        assert_eq!(
+            dialect.drop_table("users", true, true),
+            "DROP TABLE IF EXISTS users;"
+        );
+    }
+
+    #[test]
+    fn quotes_identifiers_in_external_process_select() {
+        assert_eq!(
+            MySqlDialect::new().select_column_data("orders", "id", "order"),
+            "SELECT `id`, `order` FROM `orders`;"
+        );
+        assert_eq!(
+            MySqlDialect::new().select_column_data("analytics.users", "id", "order"),
+            "SELECT `id`, `order` FROM `analytics`.`users`;"
+        );
+        assert_eq!(MySqlDialect::new().quote_identifier("we`ird"), "`we``ird`");
+    }
+
+    #[test]
+    fn batches_updates_with_case_expression() {
+        let rows = vec![
+            ("1".to_string(), "'a'".to_string()),
+            ("2".to_string(), "'b'".to_string()),
+        ];
+        assert_eq!(
+            MySqlDialect::new().update_column_data_batch("posts", "id", "slug", &rows),
+            "UPDATE posts SET slug = CASE id WHEN 1 THEN 'a' WHEN 2 THEN 'b' END WHERE id IN (1, 2);"
+        );
+    }
+    #[test]
+    fn renders_binary_as_blob_sized_to_length() {
+        assert_eq!(
+            MySqlDialect::new().render_type(&DataType::Binary(Some(32))),
+            "BLOB"
+        );
+        assert_eq!(
+            MySqlDialect::new().render_type(&DataType::Binary(Some(1_000_000))),
+            "LONGBLOB"
+        );
+        assert_eq!(
+            MySqlDialect::new().render_type(&DataType::Binary(None)),
+            "LONGBLOB"
+        );
+    }
+    #[test]
+    fn renders_timestamps_as_datetime_unless_zoned() {
+        let timestamp = |with_timezone, precision| DataType::Timestamp {
+            with_timezone,
+            precision,
+        };
+        let dialect = MySqlDialect::new();
+        assert_eq!(dialect.render_type(&timestamp(false, None)), "DATETIME");
+        assert_eq!(
+            dialect.render_type(&timestamp(false, Some(6))),
+            "DATETIME(6)"
+        );
+        assert_eq!(dialect.render_type(&timestamp(true, None)), "TIMESTAMP");
+        assert_eq!(
+            dialect.render_type(&timestamp(true, Some(6))),
+            "TIMESTAMP(6)"
+        );
+        assert_eq!(dialect.render_type(&DataType::Date), "DATE");
+        assert_eq!(dialect.render_type(&DataType::Time(None)), "TIME");
+    }
+    #[test]
+    fn renames_without_restating_the_type() {
+        assert_eq!(
+            MySqlDialect::new().rename_column("users", "phone", "contact_number", None),
+            "ALTER TABLE users RENAME COLUMN phone TO contact_number;"
+        );
+    }
+    #[test]
+    fn quotes_reserved_and_mixed_case_identifiers_when_needed() {
+        let dialect = MySqlDialect::new();
+        assert_eq!(
+            dialect.rename_column("order", "group", "userId", None),
+            "ALTER TABLE `order` RENAME COLUMN `group` TO `userId`;"
+        );
+        assert_eq!(
+            dialect.delete_rows(
+                "users",
+                &[WhereCondition {
+                    column: "Key".to_string(),
+                    operator: Operator::Eq,
+                    value: UpdateValue::Fixed("1".to_string()),
+                }],
+            ),
+            "DELETE FROM users WHERE `Key` = 1;"
+        );
+    }
+
+    #[test]
+    fn quotes_reserved_names_in_table_level_changes() {
+        let dialect = MySqlDialect::new();
+        assert_eq!(
+            dialect.concat_columns(&["Group".to_string(), "last".to_string()], " "),
+            "CONCAT_WS(' ', `Group`, last)"
+        );
+        assert_eq!(
+            dialect.restart_sequence("order", "order_id_seq", None),
+            "ALTER TABLE `order` AUTO_INCREMENT = 1;"
+        );
+        assert_eq!(
+            dialect.drop_unique_constraint("order", "Group"),
+            "ALTER TABLE `order` DROP INDEX `Group`;"
+        );
+        assert_eq!(
+            dialect
+                .add_primary_key("order", "PRIMARY", &["Group".to_string()])
+                .unwrap(),
+            "ALTER TABLE `order` ADD PRIMARY KEY (`Group`);"
+        );
+        assert_eq!(
+            dialect.drop_check_constraint("order", "chk_total").unwrap(),
+            "ALTER TABLE `order` DROP CHECK chk_total;"
+        );
+        assert_eq!(
+            dialect
+                .add_identity("order", "Group", false, Some(100), Some(&DataType::BigInt))
+                .unwrap(),
+            [
+                "ALTER TABLE `order` MODIFY COLUMN `Group` BIGINT NOT NULL AUTO_INCREMENT;",
+                "ALTER TABLE `order` AUTO_INCREMENT = 100;"
+            ]
+        );
+        assert_eq!(
+            dialect.drop_partition("order", "P2024").unwrap(),
+            "ALTER TABLE `order` DROP PARTITION `P2024`;"
+        );
+    }
+
+    #[test]
+    fn quotes_every_identifier_in_always_mode() {
+        let dialect = MySqlDialect::new().with_quote_mode(QuoteMode::Always);
+        assert_eq!(
+            dialect.drop_column("users", "we`ird", false, false),
+            "ALTER TABLE `users` DROP COLUMN `we``ird`;"
+        );
+        assert_eq!(
+            dialect
+                .change_column_type(
+                    "users",
+                    "email",
+                    &DataType::Text,
+                    &ColumnOptions::default(),
+                    Some(&ColumnPosition::After("name".to_string())),
+                )
+                .unwrap(),
+            vec!["ALTER TABLE `users` MODIFY COLUMN `email` TEXT AFTER `name`;"]
+        );
+    }
+
+    #[test]
+    fn adds_online_ddl_clauses_to_column_changes() {
+        let cases = [
+            (OnlineDdl::default(), ""),
+            (
+                OnlineDdl {
+                    algorithm: Some(Algorithm::Instant),
+                    lock: None,
+                },
+                ", ALGORITHM=INSTANT",
+            ),
+            (
+                OnlineDdl {
+                    algorithm: None,
+                    lock: Some(Lock::Shared),
+                },
+                ", LOCK=SHARED",
+            ),
+            (
+                OnlineDdl {
+                    algorithm: Some(Algorithm::Inplace),
+                    lock: Some(Lock::None),
+                },
+                ", ALGORITHM=INPLACE, LOCK=NONE",
+            ),
+            (
+                OnlineDdl {
+                    algorithm: Some(Algorithm::Copy),
+                    lock: Some(Lock::Exclusive),
+                },
+                ", ALGORITHM=COPY, LOCK=EXCLUSIVE",
+            ),
+            (
+                OnlineDdl {
+                    algorithm: Some(Algorithm::Default),
+                    lock: Some(Lock::Default),
+                },
+                ", ALGORITHM=DEFAULT, LOCK=DEFAULT",
+            ),
+        ];
+        let column = Column::builder("nickname").build();
+        for (online_ddl, clauses) in cases {
+            let dialect = MySqlDialect::new().with_online_ddl(online_ddl);
+            assert_eq!(
+                dialect.add_column("users", &column, None, false),
+                [format!(
+                    "ALTER TABLE users ADD COLUMN nickname TEXT{};",
+                    clauses
+                )]
+            );
+            assert_eq!(
+                dialect.drop_column("users", "nickname", false, false),
+                format!("ALTER TABLE users DROP COLUMN nickname{};", clauses)
+            );
+            assert_eq!(
+                dialect
+                    .change_column_type(
+                        "users",
+                        "nickname",
+                        &DataType::Varchar(50),
+                        &ColumnOptions::default(),
+                        None,
+                    )
+                    .unwrap(),
+                vec![format!(
+                    "ALTER TABLE users MODIFY COLUMN nickname VARCHAR(50){};",
+                    clauses
+                )]
+            );
+        }
+    }
+
+    #[test]
+    fn leaves_data_updates_without_online_ddl_clauses() {
+        let dialect = MySqlDialect::new().with_online_ddl(OnlineDdl {
+            algorithm: Some(Algorithm::Inplace),
+            lock: Some(Lock::None),
+        });
+        assert_eq!(
+            dialect.update_column_data(
+                "users",
+                "nickname",
+                &UpdateValue::Fixed("''".to_string()),
+                &[]
+            ),
+            "UPDATE users SET nickname = '';"
+        );
+        assert_eq!(crate::dialect::PostgresDialect::new().alter_options(), "");
+    }
+}
@@ -0,0 +1,155 @@
+use std::fmt;
+
+use crate::migration::checksum;
+
+/// What a generated name is for; see [`NamingStrategy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NameKind {
+    Index,
+    /// The unique index Postgres adds when a type change sets `unique`.
+    UniqueIndex,
+    UniqueConstraint,
+    CheckConstraint,
+    ForeignKey,
+    PrimaryKey,
+}
+
+/// Names the indexes and constraints a migration leaves unnamed.
+///
+/// [`Dialect::generated_name`](super::Dialect::generated_name) shortens the
+/// result to the dialect's identifier length limit, so strategies need not.
+pub trait NamingStrategy: fmt::Debug {
+    /// `table` is unqualified. `parts` are the columns involved, except for
+    /// check constraints where it holds a hash of the expression.
+    fn name(&self, kind: NameKind, table: &str, parts: &[String]) -> String;
+}
+
+/// `idx_users_email`, `users_email_unique`, `uq_users_email`,
+/// `chk_users_1a2b3c4d`, `fk_users_team_id` and `users_pkey`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DefaultNaming;
+
+impl NamingStrategy for DefaultNaming {
+    fn name(&self, kind: NameKind, table: &str, parts: &[String]) -> String {
+        let parts = parts.join("_");
+        match kind {
+            NameKind::Index => format!("idx_{}_{}", table, parts),
+            NameKind::UniqueIndex => format!("{}_{}_unique", table, parts),
+            NameKind::UniqueConstraint => format!("uq_{}_{}", table, parts),
+            NameKind::CheckConstraint => format!("chk_{}_{}", table, parts),
+            NameKind::ForeignKey => format!("fk_{}_{}", table, parts),
+            NameKind::PrimaryKey => format!("{}_pkey", table),
+        }
+    }
+}
+
+/// Cuts `name` to at most `max_length` bytes, replacing the end with a hash
+/// of the whole name so that names sharing a long prefix stay distinct.
+pub(crate) fn shorten(name: &str, max_length: usize) -> String {
+    if name.len() <= max_length {
+        return name.to_string();
+    }
+    let suffix = format!("_{:08x}", checksum(&[name.to_string()]) as u32);
+    let mut end = max_length.saturating_sub(suffix.len());
+    while !name.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}{}", &name[..end], suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_type::{ColumnOptions, DataType};
+    use crate::dialect::{Dialect, MariaDbDialect, MySqlDialect, PostgresDialect, SqliteDialect};
+    use crate::migration::{ChangeColumnType, CreateIndex, MigrationStep};
+
+    /// Indexes as `ix_`/`ux_{table}_{columns}`, everything else as usual.
+    #[derive(Debug)]
+    struct UxNaming;
+
+    impl NamingStrategy for UxNaming {
+        fn name(&self, kind: NameKind, table: &str, parts: &[String]) -> String {
+            match kind {
+                NameKind::Index => format!("ix_{}_{}", table, parts.join("_")),
+                NameKind::UniqueIndex => format!("ux_{}_{}", table, parts.join("_")),
+                _ => DefaultNaming.name(kind, table, parts),
+            }
+        }
+    }
+
+    fn email_index() -> CreateIndex {
+        CreateIndex {
+            name: None,
+            columns: vec!["email".into()],
+            unique: true,
+            conditional_on_rows: None,
+            concurrently: false,
+            if_not_exists: false,
+            where_clause: None,
+        }
+    }
+
+    #[test]
+    fn names_through_a_custom_strategy() {
+        let dialect = PostgresDialect::new().with_naming_strategy(UxNaming);
+        let unique_email = ChangeColumnType {
+            column: "email".to_string(),
+            data_type: DataType::Text,
+            options: ColumnOptions {
+                unique: Some(true),
+                ..ColumnOptions::default()
+            },
+            previous: None,
+            position: None,
+        };
+        assert_eq!(
+            unique_email.statements("app.users", &dialect).unwrap()[1],
+            "CREATE UNIQUE INDEX ux_users_email ON app.users (email);"
+        );
+        assert_eq!(
+            email_index().index_name("app.users", &dialect),
+            "ix_users_email"
+        );
+        assert_eq!(
+            email_index().index_name(
+                "users",
+                &MariaDbDialect::new().with_naming_strategy(UxNaming)
+            ),
+            "ix_users_email"
+        );
+    }
+
+    #[test]
+    fn shortens_names_over_the_identifier_limit() {
+        let table = "customer_subscription_billing_adjustment_history_entries";
+        let parts = ["external_reference_id".to_string()];
+
+        let postgres = PostgresDialect::new().generated_name(NameKind::Index, table, &parts);
+        assert_eq!(postgres.len(), 63);
+        assert!(postgres.starts_with(&format!("idx_{}", table)[..54]));
+        assert_eq!(
+            postgres,
+            PostgresDialect::new().generated_name(NameKind::Index, table, &parts)
+        );
+        assert_ne!(
+            postgres,
+            PostgresDialect::new().generated_name(
+                NameKind::Index,
+                table,
+                &["external_reference_code".to_string()]
+            )
+        );
+
+        assert_eq!(
+            MySqlDialect::new()
+                .generated_name(NameKind::Index, table, &parts)
+                .len(),
+            64
+        );
+        assert_eq!(
+            SqliteDialect::new().generated_name(NameKind::Index, table, &parts),
+            format!("idx_{}_external_reference_id", table)
+        );
+    }
+}
@@ -0,0 +1,1320 @@
+use std::sync::Arc;
+
+use super::keyword::{sql, Verbatim};
+use super::{
+    create_table, identifier_list, index_filter, quote_literal, quoted_table, split_table,
+    values_list, DefaultNaming, Dialect, Feature, KeywordCase, NameKind, NamingStrategy, QuoteMode,
+    TypeAliases,
+};
+use crate::data_type::{Column, ColumnOptions, DataType, Precision};
+use crate::error::MigrationError;
+use crate::migration::{
+    AddEnumValue, ColumnPosition, CreateFunction, CreateIndex, CreateSequence, DropFunction,
+    EnumValuePosition, PartitionBound, Precondition, TableOptions,
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct PostgresDialect {
+    type_aliases: TypeAliases,
+    quote_mode: QuoteMode,
+    keyword_case: KeywordCase,
+    naming: Option<Arc<dyn NamingStrategy>>,
+}
+
+impl PostgresDialect {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_quote_mode(mut self, quote_mode: QuoteMode) -> Self {
+        self.quote_mode = quote_mode;
+        self
+    }
+
+    pub fn with_keyword_case(mut self, keyword_case: KeywordCase) -> Self {
+        self.keyword_case = keyword_case;
+        self
+    }
+
+    /// Names the indexes and constraints migrations leave unnamed.
+    pub fn with_naming_strategy(mut self, strategy: impl NamingStrategy + 'static) -> Self {
+        self.naming = Some(Arc::new(strategy));
+        self
+    }
+
+    /// Shares the naming strategy of a dialect delegating to this one.
+    pub(super) fn with_naming(mut self, naming: Option<Arc<dyn NamingStrategy>>) -> Self {
+        self.naming = naming;
+        self
+    }
+}
+
+impl Dialect for PostgresDialect {
+    fn name(&self) -> &str {
+        "postgres"
+    }
+
+    fn type_aliases(&self) -> &TypeAliases {
+        &self.type_aliases
+    }
+
+    fn type_aliases_mut(&mut self) -> &mut TypeAliases {
+        &mut self.type_aliases
+    }
+
+    fn quote_mode(&self) -> QuoteMode {
+        self.quote_mode
+    }
+
+    fn keyword_case(&self) -> KeywordCase {
+        self.keyword_case
+    }
+
+    fn naming_strategy(&self) -> &dyn NamingStrategy {
+        self.naming.as_deref().unwrap_or(&DefaultNaming)
+    }
+
+    fn max_identifier_length(&self) -> Option<usize> {
+        Some(63)
+    }
+
+    fn render_type(&self, data_type: &DataType) -> String {
+        // Postgres has no unsigned integers, so use the next type that can hold
+        // the full unsigned range.
+        match data_type {
+            DataType::UnsignedInteger => "BIGINT".to_string(),
+            DataType::UnsignedBigInt => "NUMERIC(20)".to_string(),
+            DataType::Enum { name, .. } => self.table_identifier(name),
+            DataType::Binary(_) => "BYTEA".to_string(),
+            DataType::Array(inner) => format!("{}[]", self.render_type(inner)),
+            DataType::Timestamp {
+                with_timezone,
+                precision,
+            } => format!(
+                "{}{}",
+                if *with_timezone {
+                    "TIMESTAMPTZ"
+                } else {
+                    "TIMESTAMP"
+                },
+                Precision(*precision)
+            ),
+            DataType::Custom(name) => self.type_aliases.resolve(name),
+            other => other.to_string(),
+        }
+    }
+
+    fn supports(&self, feature: Feature) -> bool {
+        matches!(
+            feature,
+            Feature::ConditionalIndex
+                | Feature::DropCascade
+                | Feature::InsertIgnore
+                | Feature::AddColumnIfNotExists
+                | Feature::DropColumnIfExists
+                | Feature::TransactionalDdl
+                | Feature::IdentityAlways
+                | Feature::ConcurrentIndex
+                | Feature::NotValidConstraints
+                | Feature::TypeConversion
+                | Feature::CreateIndexIfNotExists
+                | Feature::DropIndexIfExists
+                | Feature::PartialIndex
+        )
+    }
+
+    fn collate(&self, collation: Option<&str>) -> String {
+        // Collation names are identifiers, and case-sensitive.
+        collation
+            .map(|collation| sql!(self, " COLLATE {}", self.quote_identifier(collation)))
+            .unwrap_or_default()
+    }
+
+    fn type_conversion(&self, column: &str, from: &DataType, to: &DataType) -> Option<String> {
+        needs_explicit_cast(from, to).then(|| {
+            sql!(
+                self,
+                "{}::{}",
+                self.identifier(column),
+                self.render_type(to)
+            )
+        })
+    }
+
+    fn placeholder(&self, index: usize) -> String {
+        format!("${}", index)
+    }
+
+    fn column_definition(&self, column: &Column) -> String {
+        // Generated columns are always stored. Identities allow explicit
+        // values, e.g. when importing rows with their ids.
+        let generated = match &column.generated {
+            Some(generated) => sql!(
+                self,
+                " GENERATED ALWAYS AS ({}) STORED",
+                Verbatim(&generated.expression)
+            ),
+            None if column.auto_increment => sql!(self, " GENERATED BY DEFAULT AS IDENTITY"),
+            None => String::new(),
+        };
+        sql!(
+            self,
+            "{} {}{}{}{}",
+            self.identifier(&column.name),
+            self.render_type(&column.data_type),
+            Verbatim(self.collate(column.collation.as_deref())),
+            Verbatim(generated),
+            if column.nullable { "" } else { " NOT NULL" }
+        )
+    }
+
+    fn add_column(
+        &self,
+        table: &str,
+        column: &Column,
+        _position: Option<&ColumnPosition>,
+        if_not_exists: bool,
+    ) -> Vec<String> {
+        // Postgres always appends columns; there is no way to reorder them.
+        let add_column = sql!(
+            self,
+            "ALTER TABLE {} ADD COLUMN {}{};",
+            self.table_identifier(table),
+            if if_not_exists { "IF NOT EXISTS " } else { "" },
+            Verbatim(self.column_definition(column))
+        );
+
+        match create_enum_type(self, &column.data_type) {
+            Some(create_type) => vec![create_type, add_column],
+            None => vec![add_column],
+        }
+    }
+
+    fn drop_column(&self, table: &str, column: &str, if_exists: bool, cascade: bool) -> String {
+        sql!(
+            self,
+            "ALTER TABLE {} DROP COLUMN {}{}{};",
+            self.table_identifier(table),
+            if if_exists { "IF EXISTS " } else { "" },
+            self.identifier(column),
+            if cascade { " CASCADE" } else { "" }
+        )
+    }
+
+    fn rename_column(
+        &self,
+        table: &str,
+        old_name: &str,
+        new_name: &str,
+        _definition: Option<&Column>,
+    ) -> String {
+        sql!(
+            self,
+            "ALTER TABLE {} RENAME COLUMN {} TO {};",
+            self.table_identifier(table),
+            self.identifier(old_name),
+            self.identifier(new_name)
+        )
+    }
+
+    fn change_column_type(
+        &self,
+        table: &str,
+        column: &str,
+        data_type: &DataType,
+        options: &ColumnOptions,
+        _position: Option<&ColumnPosition>,
+    ) -> Result<Vec<String>, MigrationError> {
+        let index_name = self.generated_name(NameKind::UniqueIndex, table, &[column.to_string()]);
+        let (table, column) = (self.table_identifier(table), self.identifier(column));
+        let mut statements: Vec<String> = create_enum_type(self, data_type).into_iter().collect();
+        statements.push(sql!(
+            self,
+            "ALTER TABLE {} ALTER COLUMN {} TYPE {}{}{};",
+            table,
+            column,
+            self.render_type(data_type),
+            Verbatim(self.collate(options.collation.as_deref())),
+            Verbatim(
+                options
+                    .using
+                    .as_ref()
+                    .map(|using| sql!(self, " USING {}", Verbatim(using)))
+                    .unwrap_or_default()
+            )
+        ));
+
+        if let Some(nullable) = options.nullable {
+            let action = if nullable {
+                "DROP NOT NULL"
+            } else {
+                "SET NOT NULL"
+            };
+            statements.push(sql!(
+                self,
+                "ALTER TABLE {} ALTER COLUMN {} {};",
+                table,
+                column,
+                action
+            ));
+        }
+
+        if let Some(default) = &options.default {
+            statements.push(sql!(
+                self,
+                "ALTER TABLE {} ALTER COLUMN {} SET DEFAULT {};",
+                table,
+                column,
+                Verbatim(default)
+            ));
+        }
+
+        if options.unique == Some(true) {
+            // Index names cannot be schema-qualified; the index is created in
+            // the table's schema.
+            statements.push(sql!(
+                self,
+                "CREATE UNIQUE INDEX {}{} ON {} ({});",
+                if options.concurrently {
+                    "CONCURRENTLY "
+                } else {
+                    ""
+                },
+                self.identifier(&index_name),
+                table,
+                column
+            ));
+        }
+
+        Ok(statements)
+    }
+
+    fn insert_rows(
+        &self,
+        table: &str,
+        columns: &[String],
+        rows: &[Vec<String>],
+        on_conflict_do_nothing: bool,
+    ) -> String {
+        sql!(
+            self,
+            "INSERT INTO {} ({}) VALUES {}{};",
+            self.table_identifier(table),
+            identifier_list(self, columns),
+            Verbatim(values_list(rows)),
+            if on_conflict_do_nothing {
+                " ON CONFLICT DO NOTHING"
+            } else {
+                ""
+            }
+        )
+    }
+
+    fn select_column_data(&self, table: &str, id_column: &str, column: &str) -> String {
+        sql!(
+            self,
+            "SELECT {}, {} FROM {};",
+            self.quote_identifier(id_column),
+            self.quote_identifier(column),
+            quoted_table(self, table)
+        )
+    }
+
+    fn create_index(&self, table: &str, index: &CreateIndex) -> String {
+        let name = self.identifier(&index.index_name(table, self));
+        let table = &self.table_identifier(table);
+        let columns: Vec<String> = index
+            .columns
+            .iter()
+            .map(|c| self.identifier(&c.name))
+            .collect();
+        let create_index = sql!(
+            self,
+            "CREATE {}INDEX {}{}{} ON {} ({}){};",
+            if index.unique { "UNIQUE " } else { "" },
+            if index.concurrently {
+                "CONCURRENTLY "
+            } else {
+                ""
+            },
+            if index.if_not_exists {
+                "IF NOT EXISTS "
+            } else {
+                ""
+            },
+            name,
+            table,
+            columns.join(", "),
+            Verbatim(index_filter(self, index))
+        );
+
+        match index.conditional_on_rows {
+            // reltuples is the planner's row estimate, cheap to read on
+            // tables of any size.
+            Some(threshold) => sql!(
+                self,
+                "DO {}\n\
+                 BEGIN\n    \
+                 IF (SELECT reltuples FROM pg_class WHERE oid = {}::regclass) > {} THEN\n        \
+                 {}\n    \
+                 END IF;\n\
+                 END\n\
+                 {};",
+                "$$",
+                quote_literal(table),
+                threshold,
+                Verbatim(create_index),
+                "$$"
+            ),
+            None => create_index,
+        }
+    }
+
+    fn create_table(
+        &self,
+        table: &str,
+        columns: &[Column],
+        primary_key: &[String],
+        options: &TableOptions,
+    ) -> Vec<String> {
+        // Columns sharing an enum share its type, which is created once.
+        let mut enum_types: Vec<(&str, &[String])> = Vec::new();
+        for enum_type in columns
+            .iter()
+            .filter_map(|column| enum_type(&column.data_type))
+        {
+            if !enum_types.iter().any(|(name, _)| *name == enum_type.0) {
+                enum_types.push(enum_type);
+            }
+        }
+        let mut statements: Vec<String> = enum_types
+            .into_iter()
+            .map(|(name, variants)| create_missing_type(self, name, variants))
+            .collect();
+        let definitions = columns
+            .iter()
+            .map(|column| self.column_definition(column))
+            .collect();
+        statements.push(create_table(self, table, definitions, primary_key, options));
+        statements
+    }
+
+    fn drop_table(&self, table: &str, if_exists: bool, cascade: bool) -> String {
+        sql!(
+            self,
+            "DROP TABLE {}{}{};",
+            if if_exists { "IF EXISTS " } else { "" },
+            self.table_identifier(table),
+            if cascade { " CASCADE" } else { "" }
+        )
+    }
+
+    fn create_sequence(
+        &self,
+        table: &str,
+        sequence: &CreateSequence,
+    ) -> Result<String, MigrationError> {
+        let mut sql = sql!(
+            self,
+            "CREATE SEQUENCE {}",
+            self.table_identifier(&sequence.name)
+        );
+        if let Some(increment) = sequence.increment {
+            sql.push_str(&sql!(self, " INCREMENT BY {}", increment));
+        }
+        if let Some(start) = sequence.start {
+            sql.push_str(&sql!(self, " START WITH {}", start));
+        }
+        if let Some(column) = &sequence.owned_by {
+            sql.push_str(&sql!(
+                self,
+                " OWNED BY {}.{}",
+                self.table_identifier(table),
+                self.identifier(column)
+            ));
+        }
+        sql.push(';');
+        Ok(sql)
+    }
+
+    fn drop_sequence(&self, name: &str, if_exists: bool) -> Result<String, MigrationError> {
+        Ok(sql!(
+            self,
+            "DROP SEQUENCE {}{};",
+            if if_exists { "IF EXISTS " } else { "" },
+            self.table_identifier(name)
+        ))
+    }
+
+    fn create_materialized_view(
+        &self,
+        name: &str,
+        query: &str,
+        with_data: bool,
+    ) -> Result<String, MigrationError> {
+        Ok(sql!(
+            self,
+            "CREATE MATERIALIZED VIEW {} AS {}{};",
+            self.table_identifier(name),
+            Verbatim(query),
+            if with_data { "" } else { " WITH NO DATA" }
+        ))
+    }
+
+    fn refresh_materialized_view(
+        &self,
+        name: &str,
+        concurrently: bool,
+    ) -> Result<String, MigrationError> {
+        Ok(sql!(
+            self,
+            "REFRESH MATERIALIZED VIEW {}{};",
+            if concurrently { "CONCURRENTLY " } else { "" },
+            self.table_identifier(name)
+        ))
+    }
+
+    fn drop_materialized_view(
+        &self,
+        name: &str,
+        if_exists: bool,
+    ) -> Result<String, MigrationError> {
+        Ok(sql!(
+            self,
+            "DROP MATERIALIZED VIEW {}{};",
+            if if_exists { "IF EXISTS " } else { "" },
+            self.table_identifier(name)
+        ))
+    }
+
+    fn add_identity(
+        &self,
+        table: &str,
+        column: &str,
+        always: bool,
+        start: Option<i64>,
+        _current_type: Option<&DataType>,
+    ) -> Result<Vec<String>, MigrationError> {
+        Ok(vec![sql!(
+            self,
+            "ALTER TABLE {} ALTER COLUMN {} ADD GENERATED {} AS IDENTITY{};",
+            self.table_identifier(table),
+            self.identifier(column),
+            if always { "ALWAYS" } else { "BY DEFAULT" },
+            Verbatim(
+                start
+                    .map(|start| sql!(self, " (START WITH {})", start))
+                    .unwrap_or_default()
+            )
+        )])
+    }
+
+    fn drop_identity(
+        &self,
+        table: &str,
+        column: &str,
+        _current_type: Option<&DataType>,
+    ) -> Result<String, MigrationError> {
+        Ok(sql!(
+            self,
+            "ALTER TABLE {} ALTER COLUMN {} DROP IDENTITY;",
+            self.table_identifier(table),
+            self.identifier(column)
+        ))
+    }
+
+    fn restart_identity(
+        &self,
+        table: &str,
+        column: &str,
+        start: i64,
+    ) -> Result<String, MigrationError> {
+        Ok(sql!(
+            self,
+            "ALTER TABLE {} ALTER COLUMN {} RESTART WITH {};",
+            self.table_identifier(table),
+            self.identifier(column),
+            start
+        ))
+    }
+
+    fn create_schema(&self, name: &str, if_not_exists: bool) -> Result<String, MigrationError> {
+        Ok(sql!(
+            self,
+            "CREATE SCHEMA {}{};",
+            if if_not_exists { "IF NOT EXISTS " } else { "" },
+            self.identifier(name)
+        ))
+    }
+
+    fn drop_schema(&self, name: &str) -> Result<String, MigrationError> {
+        Ok(sql!(self, "DROP SCHEMA {};", self.identifier(name)))
+    }
+
+    fn conditional(
+        &self,
+        table: &str,
+        precondition: &Precondition,
+        sql: &str,
+    ) -> Result<Vec<String>, MigrationError> {
+        let check = match precondition {
+            Precondition::ColumnExists(column) => {
+                sql!(self, "EXISTS ({})", column_check(table, column))
+            }
+            Precondition::ColumnMissing(column) => {
+                sql!(self, "NOT EXISTS ({})", column_check(table, column))
+            }
+        };
+        let body: Vec<String> = sql.lines().map(|line| format!("    {}", line)).collect();
+        let tag = dollar_quote_tag(sql);
+        Ok(vec![sql!(
+            self,
+            "DO {}\nBEGIN\n  IF {} THEN\n{}\n  END IF;\nEND\n{};",
+            tag,
+            Verbatim(check),
+            Verbatim(body.join("\n")),
+            tag
+        )])
+    }
+
+    fn create_function(&self, function: &CreateFunction) -> Result<Vec<String>, MigrationError> {
+        let (kind, returns) = match &function.returns {
+            Some(returns) => ("FUNCTION", sql!(self, " RETURNS {}", Verbatim(returns))),
+            None => ("PROCEDURE", String::new()),
+        };
+        let tag = dollar_quote_tag(&function.body);
+        Ok(vec![sql!(
+            self,
+            "CREATE {}{} {}({}){} LANGUAGE {} AS {}\n{}\n{};",
+            if function.or_replace {
+                "OR REPLACE "
+            } else {
+                ""
+            },
+            kind,
+            Verbatim(&function.name),
+            Verbatim(&function.args),
+            Verbatim(returns),
+            Verbatim(&function.language),
+            tag,
+            Verbatim(&function.body),
+            tag
+        )])
+    }
+
+    fn drop_function(&self, function: &DropFunction) -> Result<String, MigrationError> {
+        Ok(sql!(
+            self,
+            "DROP {} {}{}{};",
+            if function.procedure {
+                "PROCEDURE"
+            } else {
+                "FUNCTION"
+            },
+            if function.if_exists { "IF EXISTS " } else { "" },
+            Verbatim(&function.name),
+            Verbatim(match &function.args {
+                Some(args) => format!("({})", args),
+                None => String::new(),
+            })
+        ))
+    }
+
+    fn create_enum_type(&self, name: &str, variants: &[String]) -> Result<String, MigrationError> {
+        Ok(create_type(self, name, variants))
+    }
+
+    fn add_enum_value(&self, _table: &str, add: &AddEnumValue) -> Result<String, MigrationError> {
+        let position = match &add.position {
+            Some(EnumValuePosition::Before(anchor)) => {
+                sql!(self, " BEFORE {}", quote_literal(anchor))
+            }
+            Some(EnumValuePosition::After(anchor)) => {
+                sql!(self, " AFTER {}", quote_literal(anchor))
+            }
+            None => String::new(),
+        };
+        Ok(sql!(
+            self,
+            "ALTER TYPE {} ADD VALUE {}{};",
+            self.table_identifier(&add.type_name),
+            quote_literal(&add.value),
+            Verbatim(position)
+        ))
+    }
+
+    fn rename_enum_value(
+        &self,
+        type_name: &str,
+        old_value: &str,
+        new_value: &str,
+    ) -> Result<String, MigrationError> {
+        Ok(sql!(
+            self,
+            "ALTER TYPE {} RENAME VALUE {} TO {};",
+            self.table_identifier(type_name),
+            quote_literal(old_value),
+            quote_literal(new_value)
+        ))
+    }
+
+    fn attach_partition(
+        &self,
+        table: &str,
+        partition: &str,
+        bound: &PartitionBound,
+    ) -> Result<String, MigrationError> {
+        Ok(sql!(
+            self,
+            "ALTER TABLE {} ATTACH PARTITION {} {};",
+            self.table_identifier(table),
+            self.table_identifier(partition),
+            Verbatim(partition_bound(self, bound))
+        ))
+    }
+
+    fn detach_partition(
+        &self,
+        table: &str,
+        partition: &str,
+        concurrently: bool,
+    ) -> Result<String, MigrationError> {
+        Ok(sql!(
+            self,
+            "ALTER TABLE {} DETACH PARTITION {}{};",
+            self.table_identifier(table),
+            self.table_identifier(partition),
+            if concurrently { " CONCURRENTLY" } else { "" }
+        ))
+    }
+
+    fn create_partition(
+        &self,
+        table: &str,
+        partition: &str,
+        bound: &PartitionBound,
+    ) -> Result<String, MigrationError> {
+        Ok(sql!(
+            self,
+            "CREATE TABLE {} PARTITION OF {} {};",
+            self.table_identifier(partition),
+            self.table_identifier(table),
+            Verbatim(partition_bound(self, bound))
+        ))
+    }
+
+    fn drop_partition(&self, _table: &str, partition: &str) -> Result<String, MigrationError> {
+        // Partitions are tables; dropping one detaches it first.
+        Ok(sql!(
+            self,
+            "DROP TABLE {};",
+            self.table_identifier(partition)
+        ))
+    }
+
+    fn update_column_data_batch(
+        &self,
+        table: &str,
+        id_column: &str,
+        column: &str,
+        rows: &[(String, String)],
+    ) -> String {
+        let values: Vec<String> = rows
+            .iter()
+            .map(|(id, value)| format!("({}, {})", id, value))
+            .collect();
+        let table = self.table_identifier(table);
+        sql!(self,
+            "UPDATE {} SET {} = batch.value FROM (VALUES {}) AS batch(id, value) WHERE {}.{} = batch.id;",
+            table,
+            self.identifier(column),
+            Verbatim(values.join(", ")),
+            table,
+            self.identifier(id_column)
+        )
+    }
+}
+
+/// Postgres enums are standalone types that must exist before a column can
+/// use them.
+fn create_enum_type(dialect: &PostgresDialect, data_type: &DataType) -> Option<String> {
+    enum_type(data_type).map(|(name, variants)| create_missing_type(dialect, name, variants))
+}
+
+/// `CREATE TYPE`, skipped when the type already exists because an earlier
+/// migration or another column created it. There is no `IF NOT EXISTS`.
+fn create_missing_type(dialect: &PostgresDialect, name: &str, variants: &[String]) -> String {
+    sql!(
+        dialect,
+        "DO {}\n\
+         BEGIN\n    \
+         IF to_regtype({}) IS NULL THEN\n        \
+         {}\n    \
+         END IF;\n\
+         END\n\
+         {};",
+        "$$",
+        quote_literal(&dialect.table_identifier(name)),
+        Verbatim(create_type(dialect, name, variants)),
+        "$$"
+    )
+}
+
+/// The name and variants of the enum `data_type` is, or is an array of.
+fn enum_type(data_type: &DataType) -> Option<(&str, &[String])> {
+    match data_type {
+        DataType::Enum { name, variants } => Some((name, variants)),
+        DataType::Array(inner) => enum_type(inner),
+        _ => None,
+    }
+}
+
+/// Whether Postgres lacks an assignment cast from `from` to `to`, so a type
+/// change fails without `USING`. Custom types are left alone.
+fn needs_explicit_cast(from: &DataType, to: &DataType) -> bool {
+    let integral = |data_type: &DataType| {
+        matches!(
+            data_type,
+            DataType::SmallInt
+                | DataType::Integer
+                | DataType::BigInt
+                | DataType::UnsignedInteger
+                | DataType::UnsignedBigInt
+        )
+    };
+    match (from, to) {
+        (DataType::Varchar(_) | DataType::Text, to) => !matches!(
+            to,
+            DataType::Varchar(_) | DataType::Text | DataType::Custom(_)
+        ),
+        (DataType::Boolean, other) | (other, DataType::Boolean) => integral(other),
+        _ => false,
+    }
+}
+
+fn column_check(table: &str, column: &str) -> String {
+    let (schema, table) = split_table(table);
+    format!(
+        "SELECT 1 FROM information_schema.columns WHERE table_schema = {} AND table_name = {} AND column_name = {}",
+        schema.map_or("current_schema()".to_string(), quote_literal),
+        quote_literal(table),
+        quote_literal(column)
+    )
+}
+
+/// The first of `$$`, `$body$`, `$body1$`, ... that does not occur in `body`.
+fn dollar_quote_tag(body: &str) -> String {
+    if !body.contains("$$") {
+        return "$$".to_string();
+    }
+    let mut tag = "$body$".to_string();
+    let mut suffix = 1;
+    while body.contains(&tag) {
+        tag = format!("$body{}$", suffix);
+        suffix += 1;
+    }
+    tag
+}
+
+/// `FOR VALUES ...` or `DEFAULT`, with the bound values as given.
+fn partition_bound(dialect: &PostgresDialect, bound: &PartitionBound) -> String {
+    match bound {
+        PartitionBound::Range { from, to } => sql!(
+            dialect,
+            "FOR VALUES FROM ({}) TO ({})",
+            Verbatim(from),
+            Verbatim(to)
+        ),
+        PartitionBound::List(values) => {
+            sql!(dialect, "FOR VALUES IN ({})", Verbatim(values.join(", ")))
+        }
+        PartitionBound::Default => sql!(dialect, "DEFAULT"),
+    }
+}
+
+fn create_type(dialect: &PostgresDialect, name: &str, variants: &[String]) -> String {
+    let variants: Vec<String> = variants.iter().map(|v| quote_literal(v)).collect();
+    sql!(
+        dialect,
+        "CREATE TYPE {} AS ENUM ({});",
+        dialect.table_identifier(name),
+        variants.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::MySqlDialect;
+    use crate::migration::{AddForeignKey, Operator, Privilege, UpdateValue, WhereCondition};
+
+    #[test]
+    fn widens_unsigned_integers() {
+        let dialect = PostgresDialect::new();
+        assert_eq!(dialect.render_type(&DataType::UnsignedInteger), "BIGINT");
+        assert_eq!(
+            dialect.render_type(&DataType::UnsignedBigInt),
+            "NUMERIC(20)"
+        );
+        assert_eq!(dialect.render_type(&DataType::Integer), "INTEGER");
+    }
+
+    #[test]
+    fn renders_arrays_natively_and_as_json_elsewhere() {
+        let mut dialect = PostgresDialect::new();
+        dialect.register_type_alias("embedding", "vector(384)");
+        assert_eq!(
+            dialect.render_type(&DataType::Array(Box::new(DataType::Integer))),
+            "INTEGER[]"
+        );
+        assert_eq!(
+            dialect.render_type(&DataType::Array(Box::new(DataType::Array(Box::new(
+                DataType::Timestamp {
+                    with_timezone: true,
+                    precision: None
+                }
+            ))))),
+            "TIMESTAMPTZ[][]"
+        );
+        assert_eq!(
+            dialect.render_type(&DataType::Array(Box::new(DataType::Custom(
+                "embedding".to_string()
+            )))),
+            "vector(384)[]"
+        );
+        assert_eq!(
+            MySqlDialect::new().render_type(&DataType::Array(Box::new(DataType::Integer))),
+            "JSON"
+        );
+    }
+
+    #[test]
+    fn adds_unsigned_column_as_wider_signed_type() {
+        let column = Column {
+            name: "views".to_string(),
+            data_type: DataType::UnsignedInteger,
+            nullable: false,
+            auto_increment: false,
+            generated: None,
+            collation: None,
+        };
+        assert_eq!(
+            PostgresDialect::new().add_column("posts", &column, None, false),
+            ["ALTER TABLE posts ADD COLUMN views BIGINT NOT NULL;"]
+        );
+    }
+
+    fn status_enum() -> DataType {
+        DataType::Enum {
+            name: "user_status".to_string(),
+            variants: vec![
+                "active".to_string(),
+                "banned".to_string(),
+                "can't login".to_string(),
+            ],
+        }
+    }
+
+    const CREATE_STATUS_TYPE: &str = "DO $$
+BEGIN
+    IF to_regtype('user_status') IS NULL THEN
+        CREATE TYPE user_status AS ENUM ('active', 'banned', 'can''t login');
+    END IF;
+END
+$$;";
+
+    #[test]
+    fn creates_missing_enum_type_before_adding_column() {
+        let column = Column {
+            name: "status".to_string(),
+            data_type: status_enum(),
+            nullable: false,
+            auto_increment: false,
+            generated: None,
+            collation: None,
+        };
+        assert_eq!(
+            PostgresDialect::new().add_column("users", &column, None, false),
+            [
+                CREATE_STATUS_TYPE,
+                "ALTER TABLE users ADD COLUMN status user_status NOT NULL;"
+            ]
+        );
+    }
+
+    #[test]
+    fn creates_missing_enum_type_before_creating_table() {
+        let status = Column {
+            name: "status".to_string(),
+            data_type: status_enum(),
+            nullable: false,
+            auto_increment: false,
+            generated: None,
+            collation: None,
+        };
+        assert_eq!(
+            PostgresDialect::new().create_table("users", &[status], &[], &TableOptions::default()),
+            [
+                CREATE_STATUS_TYPE,
+                "CREATE TABLE users (status user_status NOT NULL);"
+            ]
+        );
+    }
+
+    #[test]
+    fn creates_shared_enum_type_once() {
+        let column = |name: &str| Column {
+            name: name.to_string(),
+            data_type: status_enum(),
+            nullable: false,
+            auto_increment: false,
+            generated: None,
+            collation: None,
+        };
+        assert_eq!(
+            PostgresDialect::new().create_table(
+                "users",
+                &[column("status"), column("previous_status")],
+                &[],
+                &TableOptions::default()
+            ),
+            [
+                CREATE_STATUS_TYPE,
+                "CREATE TABLE users (status user_status NOT NULL, \
+                 previous_status user_status NOT NULL);"
+            ]
+        );
+    }
+
+    #[test]
+    fn creates_missing_enum_type_before_changing_column_type() {
+        assert_eq!(
+            PostgresDialect::new()
+                .change_column_type(
+                    "users",
+                    "status",
+                    &status_enum(),
+                    &ColumnOptions::default(),
+                    None
+                )
+                .unwrap(),
+            vec![
+                CREATE_STATUS_TYPE,
+                "ALTER TABLE users ALTER COLUMN status TYPE user_status;",
+            ]
+        );
+    }
+
+    #[test]
+    fn guards_index_creation_on_row_estimate() {
+        let index = CreateIndex {
+            name: None,
+            columns: vec!["created_at".into()],
+            unique: false,
+            conditional_on_rows: Some(1_000_000),
+            concurrently: false,
+            if_not_exists: false,
+            where_clause: None,
+        };
+        assert_eq!(
+            PostgresDialect::new().create_index("events", &index),
+            "DO $$\n\
+             BEGIN\n    \
+             IF (SELECT reltuples FROM pg_class WHERE oid = 'events'::regclass) > 1000000 THEN\n        \
+             CREATE INDEX idx_events_created_at ON events (created_at);\n    \
+             END IF;\n\
+             END\n\
+             $$;"
+        );
+    }
+
+    #[test]
+    fn creates_index_unconditionally_without_threshold() {
+        let index = CreateIndex {
+            name: None,
+            columns: vec!["created_at".into()],
+            unique: false,
+            conditional_on_rows: None,
+            concurrently: false,
+            if_not_exists: false,
+            where_clause: None,
+        };
+        assert_eq!(
+            PostgresDialect::new().create_index("events", &index),
+            "CREATE INDEX idx_events_created_at ON events (created_at);"
+        );
+    }
+
+    #[test]
+    fn drops_table_with_every_flag_combination() {
+        let dialect = PostgresDialect::new();
+        assert_eq!(
+            dialect.drop_table("users", false, false),
+            "DROP TABLE users;"
+        );
+        assert_eq!(
+            dialect.drop_table("users", true, false),
+            "DROP TABLE IF EXISTS users;"
+        );
+        assert_eq!(
+            dialect.drop_table("users", false, true),
+            "DROP TABLE users CASCADE;"
+        );
+        assert_eq!(
+            dialect.drop_table("users", true, true),
+            "DROP TABLE IF EXISTS users CASCADE;"
+        );
+    }
+
+    #[test]
+    fn quotes_identifiers_in_external_process_select() {
+        assert_eq!(
+            PostgresDialect::new().select_column_data("orders", "id", "order"),
+            "SELECT \"id\", \"order\" FROM \"orders\";"
+        );
+        assert_eq!(
+            PostgresDialect::new().select_column_data("analytics.users", "id", "order"),
+            "SELECT \"id\", \"order\" FROM \"analytics\".\"users\";"
+        );
+        assert_eq!(
+            PostgresDialect::new().quote_identifier("we\"ird"),
+            "\"we\"\"ird\""
+        );
+    }
+
+    #[test]
+    fn batches_updates_with_values_list() {
+        let rows = vec![
+            ("1".to_string(), "'a'".to_string()),
+            ("2".to_string(), "'b'".to_string()),
+        ];
+        assert_eq!(
+            PostgresDialect::new().update_column_data_batch("posts", "id", "slug", &rows),
+            "UPDATE posts SET slug = batch.value FROM (VALUES (1, 'a'), (2, 'b')) AS batch(id, value) \
+             WHERE posts.id = batch.id;"
+        );
+    }
+    #[test]
+    fn passes_custom_types_through() {
+        let column = Column {
+            name: "embedding".to_string(),
+            data_type: DataType::Custom("vector(3)".to_string()),
+            nullable: true,
+            auto_increment: false,
+            generated: None,
+            collation: None,
+        };
+        assert_eq!(
+            PostgresDialect::new().add_column("documents", &column, None, false),
+            ["ALTER TABLE documents ADD COLUMN embedding vector(3);"]
+        );
+    }
+    #[test]
+    fn renders_binary_as_bytea_ignoring_length() {
+        assert_eq!(
+            PostgresDialect::new().render_type(&DataType::Binary(Some(32))),
+            "BYTEA"
+        );
+        assert_eq!(
+            PostgresDialect::new().render_type(&DataType::Binary(None)),
+            "BYTEA"
+        );
+    }
+    #[test]
+    fn renders_timestamps_with_timezone_and_precision() {
+        let timestamp = |with_timezone, precision| DataType::Timestamp {
+            with_timezone,
+            precision,
+        };
+        let dialect = PostgresDialect::new();
+        assert_eq!(dialect.render_type(&timestamp(false, None)), "TIMESTAMP");
+        assert_eq!(
+            dialect.render_type(&timestamp(false, Some(6))),
+            "TIMESTAMP(6)"
+        );
+        assert_eq!(dialect.render_type(&timestamp(true, None)), "TIMESTAMPTZ");
+        assert_eq!(
+            dialect.render_type(&timestamp(true, Some(6))),
+            "TIMESTAMPTZ(6)"
+        );
+        assert_eq!(dialect.render_type(&DataType::Date), "DATE");
+        assert_eq!(dialect.render_type(&DataType::Time(Some(3))), "TIME(3)");
+    }
+    #[test]
+    fn resolves_registered_type_aliases() {
+        let mut postgres = PostgresDialect::new();
+        postgres.register_type_alias("email", "citext");
+        let mut mysql = MySqlDialect::new();
+        mysql.register_type_alias("email", "VARCHAR(320)");
+
+        let email = DataType::Custom("email".to_string());
+        assert_eq!(postgres.render_type(&email), "citext");
+        assert_eq!(mysql.render_type(&email), "VARCHAR(320)");
+        assert_eq!(
+            PostgresDialect::new().render_type(&email),
+            "email",
+            "unregistered aliases render verbatim"
+        );
+    }
+    #[test]
+    fn quotes_reserved_and_mixed_case_identifiers_when_needed() {
+        let dialect = PostgresDialect::new();
+        assert_eq!(
+            dialect.rename_column("order", "group", "userId", None),
+            "ALTER TABLE \"order\" RENAME COLUMN \"group\" TO \"userId\";"
+        );
+        assert_eq!(
+            dialect.drop_column("sales.order", "legacy_flag", false, false),
+            "ALTER TABLE sales.\"order\" DROP COLUMN legacy_flag;"
+        );
+        assert_eq!(
+            dialect.update_column_data(
+                "users",
+                "Status",
+                &UpdateValue::Fixed("'active'".to_string()),
+                &[WhereCondition {
+                    column: "select".to_string(),
+                    operator: Operator::Eq,
+                    value: UpdateValue::Fixed("1".to_string()),
+                }],
+            ),
+            "UPDATE users SET \"Status\" = 'active' WHERE \"select\" = 1;"
+        );
+    }
+
+    #[test]
+    fn quotes_enum_type_names() {
+        let dialect = PostgresDialect::new();
+        assert_eq!(
+            dialect
+                .create_enum_type("user", &["guest".to_string()])
+                .unwrap(),
+            "CREATE TYPE \"user\" AS ENUM ('guest');"
+        );
+        assert_eq!(
+            dialect.render_type(&DataType::Enum {
+                name: "app.Mood".to_string(),
+                variants: vec!["happy".to_string()],
+            }),
+            "app.\"Mood\""
+        );
+        let add = AddEnumValue {
+            type_name: "app.Mood".to_string(),
+            value: "sad".to_string(),
+            position: None,
+            column: None,
+        };
+        assert_eq!(
+            dialect.add_enum_value("users", &add).unwrap(),
+            "ALTER TYPE app.\"Mood\" ADD VALUE 'sad';"
+        );
+        assert_eq!(
+            dialect
+                .rename_enum_value("user", "guest", "visitor")
+                .unwrap(),
+            "ALTER TYPE \"user\" RENAME VALUE 'guest' TO 'visitor';"
+        );
+        assert_eq!(
+            PostgresDialect::new()
+                .with_keyword_case(KeywordCase::Lower)
+                .create_enum_type("MOOD", &["HAPPY".to_string()])
+                .unwrap(),
+            "create type \"MOOD\" as enum ('HAPPY');"
+        );
+    }
+
+    #[test]
+    fn quotes_reserved_names_in_constraints_identities_and_grants() {
+        let dialect = PostgresDialect::new();
+        assert_eq!(
+            dialect.add_unique_constraint("order", "order_group_key", &["Group".to_string()]),
+            "ALTER TABLE \"order\" ADD CONSTRAINT order_group_key UNIQUE (\"Group\");"
+        );
+        assert_eq!(
+            dialect
+                .add_foreign_key(
+                    "order",
+                    "fk_order_user",
+                    &AddForeignKey {
+                        name: None,
+                        columns: vec!["user".to_string()],
+                        referenced_table: "user".to_string(),
+                        referenced_columns: vec!["id".to_string()],
+                        on_delete: None,
+                        on_update: None,
+                        validate_separately: false,
+                    },
+                )
+                .unwrap(),
+            "ALTER TABLE \"order\" ADD CONSTRAINT fk_order_user FOREIGN KEY (\"user\") REFERENCES \"user\" (id);"
+        );
+        assert_eq!(
+            dialect
+                .add_identity("order", "Group", false, None, None)
+                .unwrap(),
+            ["ALTER TABLE \"order\" ALTER COLUMN \"Group\" ADD GENERATED BY DEFAULT AS IDENTITY;"]
+        );
+        assert_eq!(
+            dialect
+                .create_sequence(
+                    "order",
+                    &CreateSequence {
+                        name: "order_seq".to_string(),
+                        start: None,
+                        increment: None,
+                        owned_by: Some("Group".to_string()),
+                    },
+                )
+                .unwrap(),
+            "CREATE SEQUENCE order_seq OWNED BY \"order\".\"Group\";"
+        );
+        assert_eq!(
+            dialect.concat_columns(&["Group".to_string(), "last".to_string()], " "),
+            "COALESCE(\"Group\", '') || ' ' || COALESCE(last, '')"
+        );
+        assert_eq!(
+            dialect.grant_privileges(
+                "order",
+                &[Privilege::Select],
+                &dialect.grantee("Reporting", None)
+            ),
+            "GRANT SELECT ON \"order\" TO \"Reporting\";"
+        );
+        assert_eq!(
+            dialect
+                .refresh_materialized_view("analytics.Totals", false)
+                .unwrap(),
+            "REFRESH MATERIALIZED VIEW analytics.\"Totals\";"
+        );
+        assert_eq!(
+            dialect.create_schema("User", false).unwrap(),
+            "CREATE SCHEMA \"User\";"
+        );
+    }
+
+    #[test]
+    fn quotes_every_identifier_in_always_mode() {
+        let dialect = PostgresDialect::new().with_quote_mode(QuoteMode::Always);
+        assert_eq!(
+            dialect.drop_column("analytics.users", "email", false, false),
+            "ALTER TABLE \"analytics\".\"users\" DROP COLUMN \"email\";"
+        );
+        assert_eq!(
+            dialect.rename_column("users", "we\"ird", "email", None),
+            "ALTER TABLE \"users\" RENAME COLUMN \"we\"\"ird\" TO \"email\";"
+        );
+        let index = CreateIndex {
+            name: None,
+            columns: vec!["email".into()],
+            unique: false,
+            conditional_on_rows: None,
+            concurrently: false,
+            if_not_exists: false,
+            where_clause: None,
+        };
+        assert_eq!(
+            dialect.create_index("users", &index),
+            "CREATE INDEX \"idx_users_email\" ON \"users\" (\"email\");"
+        );
+    }
+}
@@ -0,0 +1,453 @@
+use std::sync::Arc;
+
+use super::keyword::{sql, Verbatim};
+use super::{
+    create_table, generated_clause, identifier_list, index_filter, quote_literal, quoted_table,
+    values_list, DefaultNaming, Dialect, Feature, KeywordCase, NamingStrategy, QuoteMode,
+    TypeAliases,
+};
+use crate::data_type::{Column, ColumnOptions, DataType, DefaultValue};
+use crate::error::MigrationError;
+use crate::migration::{AddForeignKey, ColumnPosition, CreateIndex, Operator, TableOptions};
+
+/// SQLite 3.35 or later, the first release with `DROP COLUMN`.
+///
+/// SQLite cannot alter a column's type, default or nullability, nor add or
+/// drop table constraints, without rebuilding the table. Those operations
+/// return [`MigrationError::Unsupported`].
+#[derive(Debug, Clone, Default)]
+pub struct SqliteDialect {
+    type_aliases: TypeAliases,
+    quote_mode: QuoteMode,
+    keyword_case: KeywordCase,
+    naming: Option<Arc<dyn NamingStrategy>>,
+}
+
+impl SqliteDialect {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_quote_mode(mut self, quote_mode: QuoteMode) -> Self {
+        self.quote_mode = quote_mode;
+        self
+    }
+
+    pub fn with_keyword_case(mut self, keyword_case: KeywordCase) -> Self {
+        self.keyword_case = keyword_case;
+        self
+    }
+
+    /// Names the indexes and constraints migrations leave unnamed.
+    pub fn with_naming_strategy(mut self, strategy: impl NamingStrategy + 'static) -> Self {
+        self.naming = Some(Arc::new(strategy));
+        self
+    }
+
+    fn unsupported(&self, operation: &str) -> MigrationError {
+        MigrationError::unsupported(self.name(), operation)
+    }
+}
+
+impl Dialect for SqliteDialect {
+    fn name(&self) -> &str {
+        "sqlite"
+    }
+
+    fn type_aliases(&self) -> &TypeAliases {
+        &self.type_aliases
+    }
+
+    fn type_aliases_mut(&mut self) -> &mut TypeAliases {
+        &mut self.type_aliases
+    }
+
+    fn quote_mode(&self) -> QuoteMode {
+        self.quote_mode
+    }
+
+    fn keyword_case(&self) -> KeywordCase {
+        self.keyword_case
+    }
+
+    fn naming_strategy(&self) -> &dyn NamingStrategy {
+        self.naming.as_deref().unwrap_or(&DefaultNaming)
+    }
+
+    fn render_type(&self, data_type: &DataType) -> String {
+        // Only the storage class matters to SQLite, so use its own names.
+        match data_type {
+            DataType::SmallInt
+            | DataType::Integer
+            | DataType::BigInt
+            | DataType::UnsignedInteger
+            | DataType::UnsignedBigInt
+            | DataType::Boolean => "INTEGER".to_string(),
+            DataType::Decimal(..) => "NUMERIC".to_string(),
+            DataType::Float => "REAL".to_string(),
+            DataType::Binary(_) => "BLOB".to_string(),
+            DataType::Varchar(_)
+            | DataType::Text
+            | DataType::Date
+            | DataType::Time(_)
+            | DataType::Timestamp { .. }
+            | DataType::Json
+            | DataType::Array(_)
+            | DataType::Enum { .. } => "TEXT".to_string(),
+            DataType::Custom(name) => self.type_aliases.resolve(name),
+        }
+    }
+
+    fn supports(&self, feature: Feature) -> bool {
+        matches!(
+            feature,
+            Feature::VirtualColumns
+                | Feature::InsertIgnore
+                | Feature::TransactionalDdl
+                | Feature::CreateIndexIfNotExists
+                | Feature::DropIndexIfExists
+                | Feature::PartialIndex
+        )
+    }
+
+    fn column_definition(&self, column: &Column) -> String {
+        // AUTOINCREMENT only exists on the rowid alias, which must be declared
+        // exactly like this and so can only be created with its table.
+        if column.auto_increment {
+            return sql!(
+                self,
+                "{} INTEGER PRIMARY KEY AUTOINCREMENT",
+                self.identifier(&column.name)
+            );
+        }
+        let generated = match &column.generated {
+            Some(generated) => generated_clause(self, generated),
+            None => String::new(),
+        };
+        sql!(
+            self,
+            "{} {}{}{}{}",
+            self.identifier(&column.name),
+            self.render_type(&column.data_type),
+            Verbatim(self.collate(column.collation.as_deref())),
+            Verbatim(generated),
+            if column.nullable { "" } else { " NOT NULL" }
+        )
+    }
+
+    fn add_column(
+        &self,
+        table: &str,
+        column: &Column,
+        _position: Option<&ColumnPosition>,
+        _if_not_exists: bool,
+    ) -> Vec<String> {
+        vec![sql!(
+            self,
+            "ALTER TABLE {} ADD COLUMN {};",
+            self.table_identifier(table),
+            Verbatim(self.column_definition(column))
+        )]
+    }
+
+    fn drop_column(&self, table: &str, column: &str, _if_exists: bool, _cascade: bool) -> String {
+        sql!(
+            self,
+            "ALTER TABLE {} DROP COLUMN {};",
+            self.table_identifier(table),
+            self.identifier(column)
+        )
+    }
+
+    fn rename_column(
+        &self,
+        table: &str,
+        old_name: &str,
+        new_name: &str,
+        _definition: Option<&Column>,
+    ) -> String {
+        sql!(
+            self,
+            "ALTER TABLE {} RENAME COLUMN {} TO {};",
+            self.table_identifier(table),
+            self.identifier(old_name),
+            self.identifier(new_name)
+        )
+    }
+
+    fn change_column_type(
+        &self,
+        _table: &str,
+        _column: &str,
+        _data_type: &DataType,
+        _options: &ColumnOptions,
+        _position: Option<&ColumnPosition>,
+    ) -> Result<Vec<String>, MigrationError> {
+        Err(self.unsupported("ALTER COLUMN TYPE"))
+    }
+
+    fn set_not_null(
+        &self,
+        _table: &str,
+        _column: &str,
+        _current_type: Option<&DataType>,
+    ) -> Result<String, MigrationError> {
+        Err(self.unsupported("SET NOT NULL"))
+    }
+
+    fn drop_not_null(
+        &self,
+        _table: &str,
+        _column: &str,
+        _current_type: Option<&DataType>,
+    ) -> Result<String, MigrationError> {
+        Err(self.unsupported("DROP NOT NULL"))
+    }
+
+    fn set_column_default(
+        &self,
+        _table: &str,
+        _column: &str,
+        _default: &DefaultValue,
+    ) -> Result<String, MigrationError> {
+        Err(self.unsupported("SET DEFAULT"))
+    }
+
+    fn drop_column_default(&self, _table: &str, _column: &str) -> Result<String, MigrationError> {
+        Err(self.unsupported("DROP DEFAULT"))
+    }
+
+    fn render_condition(&self, column: &str, operator: Operator, value: &str) -> String {
+        let column = self.identifier(column);
+        match operator {
+            Operator::NullSafeEq => sql!(self, "{} IS {}", column, Verbatim(value)),
+            // LIKE already ignores ASCII case.
+            Operator::ILike => sql!(self, "{} LIKE {}", column, Verbatim(value)),
+            other => sql!(self, "{} {} {}", column, other.as_str(), Verbatim(value)),
+        }
+    }
+
+    fn insert_rows(
+        &self,
+        table: &str,
+        columns: &[String],
+        rows: &[Vec<String>],
+        on_conflict_do_nothing: bool,
+    ) -> String {
+        sql!(
+            self,
+            "INSERT{} INTO {} ({}) VALUES {};",
+            if on_conflict_do_nothing {
+                " OR IGNORE"
+            } else {
+                ""
+            },
+            self.table_identifier(table),
+            identifier_list(self, columns),
+            Verbatim(values_list(rows))
+        )
+    }
+
+    fn select_column_data(&self, table: &str, id_column: &str, column: &str) -> String {
+        sql!(
+            self,
+            "SELECT {}, {} FROM {};",
+            self.quote_identifier(id_column),
+            self.quote_identifier(column),
+            quoted_table(self, table)
+        )
+    }
+
+    fn create_index(&self, table: &str, index: &CreateIndex) -> String {
+        let columns: Vec<String> = index
+            .columns
+            .iter()
+            .map(|c| self.identifier(&c.name))
+            .collect();
+        sql!(
+            self,
+            "CREATE {}INDEX {}{} ON {} ({}){};",
+            if index.unique { "UNIQUE " } else { "" },
+            if index.if_not_exists {
+                "IF NOT EXISTS "
+            } else {
+                ""
+            },
+            self.identifier(&index.index_name(table, self)),
+            self.table_identifier(table),
+            columns.join(", "),
+            Verbatim(index_filter(self, index))
+        )
+    }
+
+    fn create_table(
+        &self,
+        table: &str,
+        columns: &[Column],
+        primary_key: &[String],
+        options: &TableOptions,
+    ) -> Vec<String> {
+        let definitions = columns
+            .iter()
+            .map(|column| self.column_definition(column))
+            .collect();
+        // An AUTOINCREMENT column already declares the key.
+        let declares_key = columns.iter().any(|column| column.auto_increment);
+        vec![create_table(
+            self,
+            table,
+            definitions,
+            if declares_key { &[] } else { primary_key },
+            options,
+        )]
+    }
+
+    fn drop_table(&self, table: &str, if_exists: bool, _cascade: bool) -> String {
+        sql!(
+            self,
+            "DROP TABLE {}{};",
+            if if_exists { "IF EXISTS " } else { "" },
+            self.table_identifier(table)
+        )
+    }
+
+    fn restart_sequence(&self, table: &str, _sequence: &str, value: Option<u64>) -> String {
+        // AUTOINCREMENT counters live in sqlite_sequence and hold the last
+        // value handed out.
+        match value {
+            Some(value) => sql!(
+                self,
+                "UPDATE sqlite_sequence SET seq = {} WHERE name = {};",
+                value.saturating_sub(1),
+                quote_literal(table)
+            ),
+            None => sql!(
+                self,
+                "DELETE FROM sqlite_sequence WHERE name = {};",
+                quote_literal(table)
+            ),
+        }
+    }
+
+    fn create_view(&self, name: &str, query: &str, or_replace: bool) -> Vec<String> {
+        let create_view = sql!(
+            self,
+            "CREATE VIEW {} AS {};",
+            self.table_identifier(name),
+            Verbatim(query)
+        );
+        if or_replace {
+            vec![self.drop_view(name, true), create_view]
+        } else {
+            vec![create_view]
+        }
+    }
+
+    fn add_check_constraint(
+        &self,
+        _table: &str,
+        _name: &str,
+        _expression: &str,
+    ) -> Result<String, MigrationError> {
+        Err(self.unsupported("ADD CONSTRAINT CHECK"))
+    }
+
+    fn drop_check_constraint(&self, _table: &str, _name: &str) -> Result<String, MigrationError> {
+        Err(self.unsupported("DROP CONSTRAINT"))
+    }
+
+    fn add_unique_constraint(&self, table: &str, name: &str, columns: &[String]) -> String {
+        // A unique index enforces the same rule and can be added in place.
+        sql!(
+            self,
+            "CREATE UNIQUE INDEX {} ON {} ({});",
+            self.identifier(name),
+            self.table_identifier(table),
+            identifier_list(self, columns)
+        )
+    }
+
+    fn drop_unique_constraint(&self, _table: &str, name: &str) -> String {
+        sql!(self, "DROP INDEX {};", self.identifier(name))
+    }
+
+    fn add_primary_key(
+        &self,
+        _table: &str,
+        _name: &str,
+        _columns: &[String],
+    ) -> Result<String, MigrationError> {
+        Err(self.unsupported("ADD PRIMARY KEY"))
+    }
+
+    fn drop_primary_key(
+        &self,
+        _table: &str,
+        _name: &str,
+        _auto_increment_column: Option<&Column>,
+    ) -> Result<String, MigrationError> {
+        Err(self.unsupported("DROP PRIMARY KEY"))
+    }
+
+    fn add_foreign_key(
+        &self,
+        _table: &str,
+        _name: &str,
+        _foreign_key: &AddForeignKey,
+    ) -> Result<String, MigrationError> {
+        Err(self.unsupported("ADD FOREIGN KEY"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_storage_classes() {
+        let dialect = SqliteDialect::new();
+        assert_eq!(dialect.render_type(&DataType::Boolean), "INTEGER");
+        assert_eq!(dialect.render_type(&DataType::Varchar(255)), "TEXT");
+        assert_eq!(dialect.render_type(&DataType::Binary(Some(16))), "BLOB");
+    }
+
+    #[test]
+    fn cannot_change_column_types() {
+        assert_eq!(
+            SqliteDialect::new().change_column_type(
+                "users",
+                "age",
+                &DataType::BigInt,
+                &ColumnOptions::default(),
+                None
+            ),
+            Err(MigrationError::unsupported("sqlite", "ALTER COLUMN TYPE"))
+        );
+    }
+
+    #[test]
+    fn adds_unique_constraints_as_indexes() {
+        let dialect = SqliteDialect::new();
+        assert_eq!(
+            dialect.add_unique_constraint("users", "uq_users_email", &["email".to_string()]),
+            "CREATE UNIQUE INDEX uq_users_email ON users (email);"
+        );
+        assert_eq!(
+            dialect.drop_unique_constraint("users", "uq_users_email"),
+            "DROP INDEX uq_users_email;"
+        );
+    }
+
+    #[test]
+    fn ignores_conflicting_inserts() {
+        assert_eq!(
+            SqliteDialect::new().insert_rows(
+                "roles",
+                &["id".to_string()],
+                &[vec!["1".to_string()]],
+                true
+            ),
+            "INSERT OR IGNORE INTO roles (id) VALUES (1);"
+        );
+    }
+}
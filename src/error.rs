@@ -0,0 +1,161 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::dialect::DialectKind;
+
+/// Errors raised while generating SQL for a migration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationError {
+    /// The dialect has no way to express the operation.
+    Unsupported { dialect: String, operation: String },
+    /// A `${token}` placeholder had no matching override.
+    UndefinedOverride { token: String },
+    /// An external process printed a line that is not an `id:value` pair.
+    /// `line` is 1-based.
+    MalformedOutput { line: usize, content: String },
+    /// An external process could not be started.
+    ProcessSpawn { program: String, message: String },
+    /// An external process exited unsuccessfully. `code` is `None` when it
+    /// was killed by a signal.
+    ProcessExit {
+        program: String,
+        code: Option<i32>,
+        stderr: String,
+    },
+    /// An external process printed output that is not valid UTF-8.
+    NonUtf8Output { program: String },
+    /// The dialect has to restate the column's type, but none was given.
+    MissingColumnType { dialect: String, column: String },
+    /// An inserted row refers to a column, which a `VALUES` list cannot do.
+    ColumnReferenceInValues { column: String },
+    /// An inserted row has a different number of values than there are
+    /// columns. `row` is 1-based.
+    RowLengthMismatch {
+        row: usize,
+        columns: usize,
+        values: usize,
+    },
+    /// A delete without conditions was not explicitly allowed.
+    UnboundedDelete { table: String },
+    /// A down migration was requested, but the operation cannot be undone.
+    Irreversible { operation: String },
+    /// Writing generated SQL or a migration file to disk failed.
+    Io { path: PathBuf, message: String },
+    /// A migration file could not be read or parsed.
+    InvalidFile { path: PathBuf, message: String },
+    /// The operation has no migration file representation, e.g. because it
+    /// is defined outside this crate.
+    Unserializable { operation: String },
+    /// A dialect name that is not one of [`DialectKind::ALL`].
+    ///
+    /// [`DialectKind::ALL`]: crate::dialect::DialectKind::ALL
+    UnknownDialect { name: String },
+}
+
+impl MigrationError {
+    pub fn unsupported(dialect: &str, operation: &str) -> Self {
+        MigrationError::Unsupported {
+            dialect: dialect.to_string(),
+            operation: operation.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MigrationError::Unsupported { dialect, operation } => {
+                write!(
+                    f,
+                    "{} is not supported by the {} dialect",
+                    operation, dialect
+                )
+            }
+            MigrationError::UndefinedOverride { token } => {
+                write!(f, "no override given for ${{{}}}", token)
+            }
+            MigrationError::MalformedOutput { line, content } => {
+                write!(
+                    f,
+                    "external process output line {} is not an id:value pair: {:?}",
+                    line, content
+                )
+            }
+            MigrationError::ProcessSpawn { program, message } => {
+                write!(f, "failed to run {}: {}", program, message)
+            }
+            MigrationError::ProcessExit {
+                program,
+                code,
+                stderr,
+            } => {
+                match code {
+                    Some(code) => write!(f, "{} exited with status {}", program, code)?,
+                    None => write!(f, "{} was terminated by a signal", program)?,
+                }
+                if !stderr.trim().is_empty() {
+                    write!(f, ": {}", stderr.trim())?;
+                }
+                Ok(())
+            }
+            MigrationError::NonUtf8Output { program } => {
+                write!(f, "{} printed output that is not valid UTF-8", program)
+            }
+            MigrationError::MissingColumnType { dialect, column } => {
+                write!(
+                    f,
+                    "the {} dialect needs the current type of {} to change its nullability",
+                    dialect, column
+                )
+            }
+            MigrationError::ColumnReferenceInValues { column } => {
+                write!(
+                    f,
+                    "cannot insert a reference to column {} as a value",
+                    column
+                )
+            }
+            MigrationError::RowLengthMismatch {
+                row,
+                columns,
+                values,
+            } => {
+                write!(
+                    f,
+                    "row {} has {} values for {} columns",
+                    row, values, columns
+                )
+            }
+            MigrationError::UnboundedDelete { table } => {
+                write!(
+                    f,
+                    "refusing to delete every row of {} without allow_full_table_delete",
+                    table
+                )
+            }
+            MigrationError::Irreversible { operation } => {
+                write!(f, "{} cannot be rolled back", operation)
+            }
+            MigrationError::Io { path, message } => {
+                write!(f, "failed to write {}: {}", path.display(), message)
+            }
+            MigrationError::InvalidFile { path, message } => {
+                write!(f, "failed to read {}: {}", path.display(), message)
+            }
+            MigrationError::Unserializable { operation } => {
+                write!(f, "{} cannot be saved to a migration file", operation)
+            }
+            MigrationError::UnknownDialect { name } => {
+                let known: Vec<&str> = DialectKind::ALL.iter().map(|kind| kind.name()).collect();
+                write!(
+                    f,
+                    "unknown dialect {:?}, expected one of {}",
+                    name,
+                    known.join(", ")
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
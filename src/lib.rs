@@ -0,0 +1,28 @@
+//! Generates dialect-specific SQL for schema and data migrations.
+//!
+//! Build a [`Migration`] for one table and a [`Dialect`], add operations to
+//! it and call [`Migration::generate_sql`]. Every operation implements
+//! [`MigrationStep`], so a single one can also be rendered with [`to_sql`].
+//! With the `serde` feature, migrations can also be read from a definition
+//! file as a `MigrationFile`, and saved to one with `Migration::save`.
+//!
+//! Everything needed to do that is re-exported here: the column types, the
+//! dialects, the operations and their option enums, and [`MigrationError`].
+//! The modules stay public for callers that prefer full paths; helpers
+//! shared between dialects and operations are crate-private.
+
+pub mod data_type;
+pub mod dialect;
+pub mod error;
+pub mod migration;
+
+pub use data_type::{
+    Column, ColumnBuilder, ColumnOptions, DataType, DefaultValue, GeneratedColumn,
+};
+pub use dialect::{
+    Algorithm, CockroachDialect, DefaultNaming, Dialect, DialectKind, Feature, KeywordCase, Lock,
+    MariaDbDialect, MssqlDialect, MySqlDialect, NameKind, NamingStrategy, OnlineDdl,
+    PostgresDialect, QuoteMode, SqliteDialect, TypeAliases,
+};
+pub use error::MigrationError;
+pub use migration::*;
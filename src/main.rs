@@ -0,0 +1,86 @@
+//! Prints the SQL for a migration definition file.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::Parser;
+use drift::{DialectKind, MigrationFile};
+
+/// Generate dialect-specific SQL from a migration definition.
+#[derive(Parser)]
+#[command(version, about)]
+struct Args {
+    /// Migration definition; `.yaml`/`.yml` files are read as YAML,
+    /// anything else as JSON.
+    file: PathBuf,
+
+    /// One of postgres, cockroach, mysql, mariadb, mssql or sqlite.
+    #[arg(long, default_value_t = DialectKind::Postgres)]
+    dialect: DialectKind,
+
+    /// Print the down migration instead.
+    #[arg(long, conflicts_with = "check")]
+    down: bool,
+
+    /// Only validate the migration.
+    #[arg(long)]
+    check: bool,
+}
+
+fn read_migration_file(path: &Path) -> Result<MigrationFile, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+    let is_yaml = matches!(
+        path.extension().and_then(|extension| extension.to_str()),
+        Some("yaml" | "yml")
+    );
+    let parsed = if is_yaml {
+        serde_yaml::from_str(&contents).map_err(|err| err.to_string())
+    } else {
+        serde_json::from_str(&contents).map_err(|err| err.to_string())
+    };
+    parsed.map_err(|err| format!("failed to parse {}: {}", path.display(), err))
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let migration = read_migration_file(&args.file)?.into_migration(args.dialect.dialect());
+
+    if args.check {
+        return match migration.validate() {
+            Ok(()) => {
+                println!("{} is valid", args.file.display());
+                Ok(())
+            }
+            Err(errors) => {
+                let errors: Vec<String> = errors.iter().map(|err| err.to_string()).collect();
+                Err(errors.join("\n"))
+            }
+        };
+    }
+
+    let statements = if args.down {
+        migration.generate_rollback_sql()
+    } else {
+        migration.generate_sql().map(|statements| {
+            statements
+                .into_iter()
+                .map(|statement| statement.sql)
+                .collect()
+        })
+    };
+    for sql in statements.map_err(|err| err.to_string())? {
+        println!("{}", sql);
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
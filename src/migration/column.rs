@@ -0,0 +1,1495 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::overrides::substitute;
+use super::statement::{joined, of_step};
+use super::{ColumnEffect, MigrationStep, RiskProfile, Statement, UpdateValue};
+use crate::data_type::{Column, ColumnOptions, DataType, DefaultValue};
+use crate::dialect::keyword::sql;
+use crate::dialect::{Dialect, Feature};
+use crate::error::MigrationError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AddColumn {
+    pub column: Column,
+    /// Where to place the column. Only MySQL and MariaDB support column
+    /// order; other dialects ignore it.
+    pub position: Option<ColumnPosition>,
+    /// Skip the column if it already exists, where the dialect supports it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub if_not_exists: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ColumnPosition {
+    First,
+    After(String),
+}
+
+impl MigrationStep for AddColumn {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(joined(&self.statements(table, dialect)?))
+    }
+
+    fn statements(
+        &self,
+        table: &str,
+        dialect: &dyn Dialect,
+    ) -> Result<Vec<Statement>, MigrationError> {
+        let sql = dialect.add_column(
+            table,
+            &self.column,
+            self.position.as_ref(),
+            self.if_not_exists,
+        );
+        Ok(of_step(self, table, sql))
+    }
+
+    fn combinable(&self) -> bool {
+        true
+    }
+
+    fn description(&self, table: &str) -> String {
+        format!("add column {} to {}", self.column.name, table)
+    }
+
+    fn rollback_sql(&self, table: &str, dialect: &dyn Dialect) -> Option<String> {
+        Some(dialect.drop_column(table, &self.column.name, false, false))
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        let mut effects = vec![ColumnEffect::Add(self.column.name.clone())];
+        if let Some(ColumnPosition::After(anchor)) = &self.position {
+            effects.push(ColumnEffect::Reference(anchor.clone()));
+        }
+        effects
+    }
+
+    fn ignored_options(&self, dialect: &dyn Dialect) -> Vec<&'static str> {
+        let mut ignored = Vec::new();
+        if self.position.is_some() && !dialect.supports(Feature::ColumnPosition) {
+            ignored.push("column position");
+        }
+        let is_virtual = matches!(&self.column.generated, Some(generated) if !generated.stored);
+        if is_virtual && !dialect.supports(Feature::VirtualColumns) {
+            ignored.push("virtual generation");
+        }
+        if self.if_not_exists && !dialect.supports(Feature::AddColumnIfNotExists) {
+            ignored.push("IF NOT EXISTS");
+        }
+        ignored
+    }
+}
+
+/// Adds a column that existing rows cannot leave empty without rewriting or
+/// locking the table for long: the column is added as nullable, filled with
+/// `backfill`, and only then made `NOT NULL` if `column` says so.
+///
+/// `default` is set last, because MySQL's `MODIFY COLUMN` would reset it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AddColumnSafe {
+    pub column: Column,
+    pub backfill: UpdateValue,
+    pub default: Option<DefaultValue>,
+}
+
+impl MigrationStep for AddColumnSafe {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(joined(&self.statements(table, dialect)?))
+    }
+
+    fn statements(
+        &self,
+        table: &str,
+        dialect: &dyn Dialect,
+    ) -> Result<Vec<Statement>, MigrationError> {
+        let nullable = Column {
+            nullable: true,
+            ..self.column.clone()
+        };
+        let mut statements = dialect.add_column(table, &nullable, None, false);
+        statements.push(dialect.update_column_data(table, &self.column.name, &self.backfill, &[]));
+        if !self.column.nullable {
+            statements.push(dialect.set_not_null(
+                table,
+                &self.column.name,
+                Some(&self.column.data_type),
+            )?);
+        }
+        if let Some(default) = &self.default {
+            statements.push(dialect.set_column_default(table, &self.column.name, default)?);
+        }
+        Ok(of_step(self, table, statements))
+    }
+
+    fn rollback_sql(&self, table: &str, dialect: &dyn Dialect) -> Option<String> {
+        Some(dialect.drop_column(table, &self.column.name, false, false))
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        let mut effects = vec![ColumnEffect::Add(self.column.name.clone())];
+        if let UpdateValue::Column(source) = &self.backfill {
+            effects.push(ColumnEffect::Reference(source.clone()));
+        }
+        effects
+    }
+}
+
+/// Moves an existing column. Only MySQL and MariaDB order columns, and they
+/// restate the whole definition, so `column` must describe it in full.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ReorderColumn {
+    pub column: Column,
+    pub position: ColumnPosition,
+}
+
+impl MigrationStep for ReorderColumn {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        dialect.reorder_column(table, &self.column, &self.position)
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        let mut effects = vec![ColumnEffect::Reference(self.column.name.clone())];
+        if let ColumnPosition::After(anchor) = &self.position {
+            effects.push(ColumnEffect::Reference(anchor.clone()));
+        }
+        effects
+    }
+}
+
+/// Collapses several columns into a new one, e.g. `first_name` and
+/// `last_name` into `full_name`: the target is added, filled by joining the
+/// sources with `separator`, and the sources are dropped if asked.
+///
+/// `NULL` sources are treated as empty, so they do not make the whole value
+/// `NULL`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MergeColumns {
+    pub sources: Vec<String>,
+    pub separator: String,
+    pub target: Column,
+    pub drop_sources: bool,
+}
+
+impl MigrationStep for MergeColumns {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(joined(&self.statements(table, dialect)?))
+    }
+
+    fn statements(
+        &self,
+        table: &str,
+        dialect: &dyn Dialect,
+    ) -> Result<Vec<Statement>, MigrationError> {
+        let nullable = Column {
+            nullable: true,
+            ..self.target.clone()
+        };
+        let merged = UpdateValue::Fixed(dialect.concat_columns(&self.sources, &self.separator));
+        let mut sql = dialect.add_column(table, &nullable, None, false);
+        sql.push(dialect.update_column_data(table, &self.target.name, &merged, &[]));
+        if !self.target.nullable {
+            sql.push(dialect.set_not_null(
+                table,
+                &self.target.name,
+                Some(&self.target.data_type),
+            )?);
+        }
+        // Only dropping the sources loses data.
+        let mut statements: Vec<Statement> = sql
+            .into_iter()
+            .map(|sql| Statement {
+                destructive: false,
+                ..Statement::new(self, table, sql)
+            })
+            .collect();
+        if self.drop_sources {
+            for source in &self.sources {
+                let sql = dialect.drop_column(table, source, false, false);
+                statements.push(Statement::new(self, table, sql));
+            }
+        }
+        Ok(statements)
+    }
+
+    fn rollback_sql(&self, table: &str, dialect: &dyn Dialect) -> Option<String> {
+        // Dropped sources cannot be split back out of the target.
+        if self.drop_sources {
+            return None;
+        }
+        Some(dialect.drop_column(table, &self.target.name, false, false))
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        let mut effects: Vec<ColumnEffect> = self
+            .sources
+            .iter()
+            .map(|source| ColumnEffect::Reference(source.clone()))
+            .collect();
+        effects.push(ColumnEffect::Add(self.target.name.clone()));
+        if self.drop_sources {
+            effects.extend(
+                self.sources
+                    .iter()
+                    .map(|source| ColumnEffect::Drop(source.clone())),
+            );
+        }
+        effects
+    }
+
+    fn risk_profile(&self) -> RiskProfile {
+        RiskProfile {
+            destructive: self.drop_sources,
+            ..RiskProfile::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DropColumn {
+    pub column: String,
+    /// Do nothing if the column is already gone, where the dialect supports
+    /// it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub if_exists: bool,
+    /// Also drop views, indexes and constraints that depend on the column.
+    /// Only Postgres acts on this.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cascade: bool,
+}
+
+impl MigrationStep for DropColumn {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(dialect.drop_column(table, &self.column, self.if_exists, self.cascade))
+    }
+
+    fn combinable(&self) -> bool {
+        true
+    }
+
+    fn description(&self, table: &str) -> String {
+        format!("drop column {} from {}", self.column, table)
+    }
+
+    fn ignored_options(&self, dialect: &dyn Dialect) -> Vec<&'static str> {
+        let mut ignored = Vec::new();
+        if self.if_exists && !dialect.supports(Feature::DropColumnIfExists) {
+            ignored.push("IF EXISTS");
+        }
+        if self.cascade && !dialect.supports(Feature::DropCascade) {
+            ignored.push("cascade");
+        }
+        ignored
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        vec![ColumnEffect::Drop(self.column.clone())]
+    }
+
+    fn risk_profile(&self) -> RiskProfile {
+        RiskProfile {
+            destructive: true,
+            ..RiskProfile::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RenameColumn {
+    pub old_name: String,
+    pub new_name: String,
+    /// The column's full definition, whatever its name. MySQL before 8.0
+    /// has no `RENAME COLUMN`; with this set MySQL and MariaDB restate the
+    /// column with `CHANGE COLUMN` instead, which every version accepts.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub definition: Option<Column>,
+}
+
+impl MigrationStep for RenameColumn {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(dialect.rename_column(
+            table,
+            &self.old_name,
+            &self.new_name,
+            self.definition.as_ref(),
+        ))
+    }
+
+    fn combinable(&self) -> bool {
+        true
+    }
+
+    fn rollback_sql(&self, table: &str, dialect: &dyn Dialect) -> Option<String> {
+        Some(dialect.rename_column(
+            table,
+            &self.new_name,
+            &self.old_name,
+            self.definition.as_ref(),
+        ))
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        vec![ColumnEffect::Rename {
+            from: self.old_name.clone(),
+            to: self.new_name.clone(),
+        }]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ChangeColumnType {
+    pub column: String,
+    pub data_type: DataType,
+    pub options: ColumnOptions,
+    /// The definition before the change. Without it the change cannot be
+    /// rolled back.
+    pub previous: Option<(DataType, ColumnOptions)>,
+    /// Where to move the column. Only MySQL and MariaDB support it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub position: Option<ColumnPosition>,
+}
+
+impl ChangeColumnType {
+    /// `options` with the dialect's conversion from `from` to `to` filled in
+    /// when no `using` expression is given.
+    fn converting(
+        &self,
+        dialect: &dyn Dialect,
+        from: Option<&DataType>,
+        to: &DataType,
+        options: &ColumnOptions,
+    ) -> ColumnOptions {
+        let using = options
+            .using
+            .clone()
+            .or_else(|| from.and_then(|from| dialect.type_conversion(&self.column, from, to)));
+        ColumnOptions {
+            using,
+            ..options.clone()
+        }
+    }
+}
+
+impl MigrationStep for ChangeColumnType {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(joined(&self.statements(table, dialect)?))
+    }
+
+    fn combinable(&self) -> bool {
+        true
+    }
+
+    fn transactional(&self) -> bool {
+        !(self.options.unique == Some(true) && self.options.concurrently)
+    }
+
+    fn statements(
+        &self,
+        table: &str,
+        dialect: &dyn Dialect,
+    ) -> Result<Vec<Statement>, MigrationError> {
+        let from = self.previous.as_ref().map(|(data_type, _)| data_type);
+        let sql = dialect.change_column_type(
+            table,
+            &self.column,
+            &self.data_type,
+            &self.converting(dialect, from, &self.data_type, &self.options),
+            self.position.as_ref(),
+        )?;
+        // Only the unique index built concurrently has to run outside a
+        // transaction; the type change itself does not.
+        let concurrent_index = sql!(dialect, "CREATE UNIQUE INDEX CONCURRENTLY");
+        Ok(sql
+            .into_iter()
+            .map(|sql| Statement {
+                transactional: !sql.starts_with(&concurrent_index),
+                ..Statement::new(self, table, sql)
+            })
+            .collect())
+    }
+
+    fn rollback_sql(&self, table: &str, dialect: &dyn Dialect) -> Option<String> {
+        let (data_type, options) = self.previous.as_ref()?;
+        let options = self.converting(dialect, Some(&self.data_type), data_type, options);
+        dialect
+            .change_column_type(table, &self.column, data_type, &options, None)
+            .map(|statements| statements.join("\n"))
+            .ok()
+    }
+
+    fn ignored_options(&self, dialect: &dyn Dialect) -> Vec<&'static str> {
+        let mut ignored = Vec::new();
+        if self.position.is_some() && !dialect.supports(Feature::ColumnPosition) {
+            ignored.push("column position");
+        }
+        if self.options.using.is_some() && !dialect.supports(Feature::TypeConversion) {
+            ignored.push("USING conversion");
+        }
+        if self.options.concurrently && !dialect.supports(Feature::ConcurrentIndex) {
+            ignored.push("concurrently");
+        }
+        ignored
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        vec![ColumnEffect::Reference(self.column.clone())]
+    }
+
+    fn risk_profile(&self) -> RiskProfile {
+        // Widening a VARCHAR is cheap in some databases, but most type
+        // changes rewrite every row.
+        RiskProfile {
+            requires_rewrite: true,
+            ..RiskProfile::default()
+        }
+    }
+
+    fn apply_overrides(
+        &mut self,
+        overrides: &HashMap<String, String>,
+    ) -> Result<(), MigrationError> {
+        if let Some(default) = &mut self.options.default {
+            *default = substitute(default, overrides)?;
+        }
+        if let Some(using) = &mut self.options.using {
+            *using = substitute(using, overrides)?;
+        }
+        Ok(())
+    }
+}
+
+/// Makes a column `NOT NULL` without changing its type.
+///
+/// MySQL and SQL Server can only do this by restating the column's type, so
+/// they need `current_type`. MySQL's `MODIFY COLUMN` also resets the
+/// column's default and other attributes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SetNotNull {
+    pub column: String,
+    pub current_type: Option<DataType>,
+    /// Written into existing `NULL`s first, as-is, so the constraint holds.
+    pub backfill: Option<String>,
+}
+
+impl MigrationStep for SetNotNull {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(joined(&self.statements(table, dialect)?))
+    }
+
+    fn statements(
+        &self,
+        table: &str,
+        dialect: &dyn Dialect,
+    ) -> Result<Vec<Statement>, MigrationError> {
+        let mut statements = Vec::new();
+        if let Some(value) = &self.backfill {
+            statements.push(dialect.backfill_nulls(table, &self.column, value));
+        }
+        statements.push(dialect.set_not_null(table, &self.column, self.current_type.as_ref())?);
+        Ok(of_step(self, table, statements))
+    }
+
+    fn rollback_sql(&self, table: &str, dialect: &dyn Dialect) -> Option<String> {
+        dialect
+            .drop_not_null(table, &self.column, self.current_type.as_ref())
+            .ok()
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        vec![ColumnEffect::Reference(self.column.clone())]
+    }
+}
+
+/// Allows `NULL` in a column without changing its type. See [`SetNotNull`]
+/// for when `current_type` is needed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DropNotNull {
+    pub column: String,
+    pub current_type: Option<DataType>,
+}
+
+impl MigrationStep for DropNotNull {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        dialect.drop_not_null(table, &self.column, self.current_type.as_ref())
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        vec![ColumnEffect::Reference(self.column.clone())]
+    }
+}
+
+/// Changes the collation of a text column. Both Postgres and MySQL restate
+/// the column's type to do so, so `current_type` is required; see
+/// [`SetNotNull`] for what MySQL's `MODIFY COLUMN` resets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AlterColumnCollation {
+    pub column: String,
+    pub collation: String,
+    pub current_type: Option<DataType>,
+}
+
+impl MigrationStep for AlterColumnCollation {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(joined(&self.statements(table, dialect)?))
+    }
+
+    fn statements(
+        &self,
+        table: &str,
+        dialect: &dyn Dialect,
+    ) -> Result<Vec<Statement>, MigrationError> {
+        let data_type =
+            self.current_type
+                .as_ref()
+                .ok_or_else(|| MigrationError::MissingColumnType {
+                    dialect: dialect.name().to_string(),
+                    column: self.column.clone(),
+                })?;
+        let options = ColumnOptions {
+            collation: Some(self.collation.clone()),
+            ..ColumnOptions::default()
+        };
+        let sql = dialect.change_column_type(table, &self.column, data_type, &options, None)?;
+        Ok(of_step(self, table, sql))
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        vec![ColumnEffect::Reference(self.column.clone())]
+    }
+}
+
+/// Changes only a column's default, leaving its definition alone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SetColumnDefault {
+    pub column: String,
+    pub default: DefaultValue,
+}
+
+impl MigrationStep for SetColumnDefault {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        dialect.set_column_default(table, &self.column, &self.default)
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        vec![ColumnEffect::Reference(self.column.clone())]
+    }
+
+    fn apply_overrides(
+        &mut self,
+        overrides: &HashMap<String, String>,
+    ) -> Result<(), MigrationError> {
+        match &mut self.default {
+            DefaultValue::Literal(value) | DefaultValue::Expression(value) => {
+                *value = substitute(value, overrides)?;
+            }
+            DefaultValue::Integer(_) => {}
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DropColumnDefault {
+    pub column: String,
+}
+
+impl MigrationStep for DropColumnDefault {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        dialect.drop_column_default(table, &self.column)
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        vec![ColumnEffect::Reference(self.column.clone())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_type::GeneratedColumn;
+    use crate::dialect::{MariaDbDialect, MssqlDialect, MySqlDialect, PostgresDialect};
+    use crate::migration::Migration;
+
+    fn widen_age(previous: Option<(DataType, ColumnOptions)>) -> ChangeColumnType {
+        ChangeColumnType {
+            column: "age".to_string(),
+            data_type: DataType::BigInt,
+            options: ColumnOptions::default(),
+            previous,
+            position: None,
+        }
+    }
+
+    #[test]
+    fn type_change_without_previous_definition_is_irreversible() {
+        assert_eq!(
+            widen_age(None).rollback_sql("users", &PostgresDialect::new()),
+            None
+        );
+    }
+
+    #[test]
+    fn type_change_rolls_back_to_previous_definition() {
+        let change = widen_age(Some((
+            DataType::Integer,
+            ColumnOptions {
+                nullable: Some(false),
+                default: Some("0".to_string()),
+                unique: None,
+                collation: None,
+                using: None,
+                concurrently: false,
+            },
+        )));
+
+        assert_eq!(
+            change
+                .rollback_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE users ALTER COLUMN age TYPE INTEGER;\n\
+             ALTER TABLE users ALTER COLUMN age SET NOT NULL;\n\
+             ALTER TABLE users ALTER COLUMN age SET DEFAULT 0;"
+        );
+        assert_eq!(
+            change.rollback_sql("users", &MySqlDialect::new()).unwrap(),
+            "ALTER TABLE users MODIFY COLUMN age INTEGER NOT NULL DEFAULT 0;"
+        );
+    }
+    #[test]
+    fn positions_column_on_mysql_only() {
+        let add = AddColumn {
+            column: Column {
+                name: "updated_at".to_string(),
+                data_type: DataType::Timestamp {
+                    with_timezone: false,
+                    precision: None,
+                },
+                nullable: true,
+                auto_increment: false,
+                generated: None,
+                collation: None,
+            },
+            position: Some(ColumnPosition::After("created_at".to_string())),
+            if_not_exists: false,
+        };
+        assert_eq!(
+            add.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "ALTER TABLE users ADD COLUMN updated_at DATETIME AFTER created_at;"
+        );
+        assert_eq!(
+            add.generate_sql("users", &PostgresDialect::new()).unwrap(),
+            "ALTER TABLE users ADD COLUMN updated_at TIMESTAMP;"
+        );
+
+        let first = AddColumn {
+            position: Some(ColumnPosition::First),
+            ..add
+        };
+        assert_eq!(
+            first.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "ALTER TABLE users ADD COLUMN updated_at DATETIME FIRST;"
+        );
+    }
+
+    #[test]
+    fn guards_column_existence_where_supported() {
+        let add = AddColumn {
+            column: Column {
+                name: "nickname".to_string(),
+                data_type: DataType::Text,
+                nullable: true,
+                auto_increment: false,
+                generated: None,
+                collation: None,
+            },
+            position: None,
+            if_not_exists: true,
+        };
+        assert_eq!(
+            add.generate_sql("users", &PostgresDialect::new()).unwrap(),
+            "ALTER TABLE users ADD COLUMN IF NOT EXISTS nickname TEXT;"
+        );
+        assert_eq!(
+            add.generate_sql("users", &MariaDbDialect::new()).unwrap(),
+            "ALTER TABLE users ADD COLUMN IF NOT EXISTS nickname TEXT;"
+        );
+        assert_eq!(
+            add.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "ALTER TABLE users ADD COLUMN nickname TEXT;"
+        );
+        assert_eq!(
+            add.ignored_options(&MySqlDialect::new()),
+            vec!["IF NOT EXISTS"]
+        );
+
+        let drop = DropColumn {
+            column: "nickname".to_string(),
+            if_exists: true,
+            cascade: false,
+        };
+        assert_eq!(
+            drop.generate_sql("users", &MssqlDialect::new()).unwrap(),
+            "ALTER TABLE users DROP COLUMN IF EXISTS nickname;"
+        );
+        assert_eq!(
+            drop.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "ALTER TABLE users DROP COLUMN nickname;"
+        );
+        assert_eq!(
+            drop.ignored_options(&MySqlDialect::new()),
+            vec!["IF EXISTS"]
+        );
+        assert!(drop.ignored_options(&PostgresDialect::new()).is_empty());
+    }
+    fn set_default(default: DefaultValue) -> SetColumnDefault {
+        SetColumnDefault {
+            column: "status".to_string(),
+            default,
+        }
+    }
+
+    #[test]
+    fn sets_literal_default_quoted() {
+        let set = set_default(DefaultValue::Literal("it's new".to_string()));
+        let sql = "ALTER TABLE users ALTER COLUMN status SET DEFAULT 'it''s new';";
+        assert_eq!(
+            set.generate_sql("users", &PostgresDialect::new()).unwrap(),
+            sql
+        );
+        assert_eq!(
+            set.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            sql
+        );
+    }
+
+    #[test]
+    fn sets_numeric_default() {
+        let set = set_default(DefaultValue::Integer(-1));
+        let sql = "ALTER TABLE users ALTER COLUMN status SET DEFAULT -1;";
+        assert_eq!(
+            set.generate_sql("users", &PostgresDialect::new()).unwrap(),
+            sql
+        );
+        assert_eq!(
+            set.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            sql
+        );
+    }
+
+    #[test]
+    fn sets_expression_default_unquoted() {
+        let set = set_default(DefaultValue::Expression("now()".to_string()));
+        assert_eq!(
+            set.generate_sql("users", &PostgresDialect::new()).unwrap(),
+            "ALTER TABLE users ALTER COLUMN status SET DEFAULT now();"
+        );
+        assert_eq!(
+            set.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "ALTER TABLE users ALTER COLUMN status SET DEFAULT (now());"
+        );
+    }
+
+    #[test]
+    fn drops_default() {
+        let drop = DropColumnDefault {
+            column: "status".to_string(),
+        };
+        let sql = "ALTER TABLE users ALTER COLUMN status DROP DEFAULT;";
+        assert_eq!(
+            drop.generate_sql("users", &PostgresDialect::new()).unwrap(),
+            sql
+        );
+        assert_eq!(
+            drop.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            sql
+        );
+    }
+    fn set_not_null(current_type: Option<DataType>, backfill: Option<&str>) -> SetNotNull {
+        SetNotNull {
+            column: "email".to_string(),
+            current_type,
+            backfill: backfill.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn sets_not_null_without_type_on_postgres() {
+        assert_eq!(
+            set_not_null(None, None)
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE users ALTER COLUMN email SET NOT NULL;"
+        );
+        let drop = DropNotNull {
+            column: "email".to_string(),
+            current_type: None,
+        };
+        assert_eq!(
+            drop.generate_sql("users", &PostgresDialect::new()).unwrap(),
+            "ALTER TABLE users ALTER COLUMN email DROP NOT NULL;"
+        );
+    }
+
+    #[test]
+    fn restates_type_to_change_nullability_on_mysql() {
+        assert_eq!(
+            set_not_null(Some(DataType::Varchar(255)), None)
+                .generate_sql("users", &MySqlDialect::new())
+                .unwrap(),
+            "ALTER TABLE users MODIFY COLUMN email VARCHAR(255) NOT NULL;"
+        );
+        let drop = DropNotNull {
+            column: "email".to_string(),
+            current_type: Some(DataType::Varchar(255)),
+        };
+        assert_eq!(
+            drop.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "ALTER TABLE users MODIFY COLUMN email VARCHAR(255) NULL;"
+        );
+    }
+
+    #[test]
+    fn mysql_needs_current_type() {
+        assert_eq!(
+            set_not_null(None, None).generate_sql("users", &MySqlDialect::new()),
+            Err(MigrationError::MissingColumnType {
+                dialect: "mysql".to_string(),
+                column: "email".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn backfills_nulls_before_setting_not_null() {
+        let set = set_not_null(Some(DataType::Varchar(255)), Some("''"));
+        assert_eq!(
+            set.statements("users", &PostgresDialect::new()).unwrap(),
+            [
+                "UPDATE users SET email = '' WHERE email IS NULL;",
+                "ALTER TABLE users ALTER COLUMN email SET NOT NULL;"
+            ]
+        );
+        assert_eq!(
+            set.statements("users", &MySqlDialect::new()).unwrap(),
+            [
+                "UPDATE users SET email = '' WHERE email IS NULL;",
+                "ALTER TABLE users MODIFY COLUMN email VARCHAR(255) NOT NULL;"
+            ]
+        );
+    }
+    fn full_name(stored: bool) -> AddColumn {
+        AddColumn {
+            column: Column {
+                name: "full_name".to_string(),
+                data_type: DataType::Text,
+                nullable: true,
+                auto_increment: false,
+                generated: Some(GeneratedColumn {
+                    expression: "first_name || ' ' || last_name".to_string(),
+                    stored,
+                }),
+                collation: None,
+            },
+            position: None,
+            if_not_exists: false,
+        }
+    }
+
+    #[test]
+    fn adds_generated_columns() {
+        assert_eq!(
+            full_name(true)
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE users ADD COLUMN full_name TEXT GENERATED ALWAYS AS (first_name || ' ' || last_name) STORED;"
+        );
+        assert_eq!(
+            full_name(false)
+                .generate_sql("users", &MySqlDialect::new())
+                .unwrap(),
+            "ALTER TABLE users ADD COLUMN full_name TEXT GENERATED ALWAYS AS (first_name || ' ' || last_name) VIRTUAL;"
+        );
+    }
+
+    #[test]
+    fn postgres_stores_virtual_columns() {
+        let virtual_column = full_name(false);
+        assert_eq!(
+            virtual_column
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE users ADD COLUMN full_name TEXT GENERATED ALWAYS AS (first_name || ' ' || last_name) STORED;"
+        );
+        assert_eq!(
+            virtual_column.ignored_options(&PostgresDialect::new()),
+            vec!["virtual generation"]
+        );
+        assert!(virtual_column
+            .ignored_options(&MySqlDialect::new())
+            .is_empty());
+    }
+    fn add_country() -> AddColumnSafe {
+        AddColumnSafe {
+            column: Column {
+                name: "country".to_string(),
+                data_type: DataType::Varchar(2),
+                nullable: false,
+                auto_increment: false,
+                generated: None,
+                collation: None,
+            },
+            backfill: UpdateValue::Fixed("'US'".to_string()),
+            default: None,
+        }
+    }
+
+    #[test]
+    fn adds_not_null_column_in_three_phases() {
+        let add = add_country();
+        assert_eq!(
+            add.statements("users", &PostgresDialect::new()).unwrap(),
+            vec![
+                "ALTER TABLE users ADD COLUMN country VARCHAR(2);",
+                "UPDATE users SET country = 'US';",
+                "ALTER TABLE users ALTER COLUMN country SET NOT NULL;",
+            ]
+        );
+        assert_eq!(
+            add.statements("users", &MySqlDialect::new()).unwrap(),
+            vec![
+                "ALTER TABLE users ADD COLUMN country VARCHAR(2);",
+                "UPDATE users SET country = 'US';",
+                "ALTER TABLE users MODIFY COLUMN country VARCHAR(2) NOT NULL;",
+            ]
+        );
+    }
+
+    #[test]
+    fn sets_default_after_not_null() {
+        let add = AddColumnSafe {
+            default: Some(DefaultValue::Literal("US".to_string())),
+            ..add_country()
+        };
+        assert_eq!(
+            add.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "ALTER TABLE users ADD COLUMN country VARCHAR(2);\n\
+             UPDATE users SET country = 'US';\n\
+             ALTER TABLE users MODIFY COLUMN country VARCHAR(2) NOT NULL;\n\
+             ALTER TABLE users ALTER COLUMN country SET DEFAULT 'US';"
+        );
+        assert_eq!(
+            add.rollback_sql("users", &PostgresDialect::new()).unwrap(),
+            "ALTER TABLE users DROP COLUMN country;"
+        );
+    }
+    #[test]
+    fn alters_column_collation() {
+        let alter = AlterColumnCollation {
+            column: "email".to_string(),
+            collation: "en_US".to_string(),
+            current_type: Some(DataType::Text),
+        };
+        assert_eq!(
+            alter
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE users ALTER COLUMN email TYPE TEXT COLLATE \"en_US\";"
+        );
+
+        let alter = AlterColumnCollation {
+            collation: "utf8mb4_general_ci".to_string(),
+            current_type: Some(DataType::Varchar(255)),
+            ..alter
+        };
+        assert_eq!(
+            alter.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "ALTER TABLE users MODIFY COLUMN email VARCHAR(255) COLLATE utf8mb4_general_ci;"
+        );
+    }
+
+    #[test]
+    fn collation_needs_current_type() {
+        let alter = AlterColumnCollation {
+            column: "email".to_string(),
+            collation: "en_US".to_string(),
+            current_type: None,
+        };
+        assert_eq!(
+            alter.generate_sql("users", &PostgresDialect::new()),
+            Err(MigrationError::MissingColumnType {
+                dialect: "postgres".to_string(),
+                column: "email".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn changes_type_with_collation() {
+        let change = ChangeColumnType {
+            column: "email".to_string(),
+            data_type: DataType::Varchar(320),
+            options: ColumnOptions {
+                nullable: Some(false),
+                collation: Some("utf8mb4_bin".to_string()),
+                ..ColumnOptions::default()
+            },
+            previous: None,
+            position: None,
+        };
+        assert_eq!(
+            change.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "ALTER TABLE users MODIFY COLUMN email VARCHAR(320) COLLATE utf8mb4_bin NOT NULL;"
+        );
+    }
+    #[test]
+    fn adds_column_with_collation() {
+        let add = AddColumn {
+            column: Column {
+                name: "email".to_string(),
+                data_type: DataType::Text,
+                nullable: false,
+                auto_increment: false,
+                generated: None,
+                collation: Some("en_US".to_string()),
+            },
+            position: None,
+            if_not_exists: false,
+        };
+        assert_eq!(
+            add.generate_sql("users", &PostgresDialect::new()).unwrap(),
+            "ALTER TABLE users ADD COLUMN email TEXT COLLATE \"en_US\" NOT NULL;"
+        );
+
+        let add = AddColumn {
+            column: Column {
+                data_type: DataType::Varchar(255),
+                collation: Some("utf8mb4_unicode_ci".to_string()),
+                ..add.column
+            },
+            ..add
+        };
+        assert_eq!(
+            add.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "ALTER TABLE users ADD COLUMN email VARCHAR(255) COLLATE utf8mb4_unicode_ci NOT NULL;"
+        );
+        let change = ChangeColumnType {
+            column: "email".to_string(),
+            data_type: DataType::Text,
+            options: ColumnOptions {
+                collation: Some("en_US".to_string()),
+                ..ColumnOptions::default()
+            },
+            previous: None,
+            position: None,
+        };
+        assert_eq!(
+            change
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE users ALTER COLUMN email TYPE TEXT COLLATE \"en_US\";"
+        );
+    }
+    #[test]
+    fn builds_unique_index_concurrently_outside_transactions() {
+        let unique_email = |concurrently| ChangeColumnType {
+            column: "email".to_string(),
+            data_type: DataType::Varchar(255),
+            options: ColumnOptions {
+                unique: Some(true),
+                concurrently,
+                ..ColumnOptions::default()
+            },
+            previous: None,
+            position: None,
+        };
+
+        let blocking = unique_email(false);
+        assert_eq!(
+            blocking
+                .statements("users", &PostgresDialect::new())
+                .unwrap()[1],
+            "CREATE UNIQUE INDEX users_email_unique ON users (email);"
+        );
+        assert!(blocking.transactional());
+
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration.add_operation(unique_email(true));
+        let statements = migration.generate_sql().unwrap();
+        assert_eq!(
+            statements[1],
+            "CREATE UNIQUE INDEX CONCURRENTLY users_email_unique ON users (email);"
+        );
+        assert!(statements[0].transactional);
+        assert!(!statements[1].transactional);
+
+        assert_eq!(
+            unique_email(true)
+                .generate_sql("users", &MySqlDialect::new())
+                .unwrap(),
+            "ALTER TABLE users MODIFY COLUMN email VARCHAR(255) UNIQUE;"
+        );
+        assert_eq!(
+            unique_email(true).ignored_options(&MySqlDialect::new()),
+            vec!["concurrently"]
+        );
+    }
+
+    fn age_to_integer(using: Option<&str>) -> ChangeColumnType {
+        ChangeColumnType {
+            column: "age".to_string(),
+            data_type: DataType::Integer,
+            options: ColumnOptions {
+                using: using.map(str::to_string),
+                ..ColumnOptions::default()
+            },
+            previous: Some((DataType::Text, ColumnOptions::default())),
+            position: None,
+        }
+    }
+
+    #[test]
+    fn converts_with_the_given_expression() {
+        let change = age_to_integer(Some("NULLIF(age, '')::integer"));
+        assert_eq!(
+            change
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE users ALTER COLUMN age TYPE INTEGER USING NULLIF(age, '')::integer;"
+        );
+        assert_eq!(
+            change.ignored_options(&MySqlDialect::new()),
+            vec!["USING conversion"]
+        );
+    }
+
+    #[test]
+    fn casts_between_incompatible_types_by_default() {
+        let change = age_to_integer(None);
+        assert_eq!(
+            change
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE users ALTER COLUMN age TYPE INTEGER USING age::INTEGER;"
+        );
+        // Integers convert to text implicitly.
+        assert_eq!(
+            change
+                .rollback_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE users ALTER COLUMN age TYPE TEXT;"
+        );
+
+        let widen = ChangeColumnType {
+            data_type: DataType::BigInt,
+            previous: Some((DataType::Integer, ColumnOptions::default())),
+            ..change
+        };
+        assert_eq!(
+            widen
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE users ALTER COLUMN age TYPE BIGINT;"
+        );
+    }
+
+    #[test]
+    fn mysql_ignores_conversions() {
+        for using in [None, Some("age::integer")] {
+            assert_eq!(
+                age_to_integer(using)
+                    .generate_sql("users", &MySqlDialect::new())
+                    .unwrap(),
+                "ALTER TABLE users MODIFY COLUMN age INTEGER;"
+            );
+        }
+    }
+
+    #[test]
+    fn migration_lists_each_type_change_statement_separately() {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration.add_operation(ChangeColumnType {
+            column: "age".to_string(),
+            data_type: DataType::BigInt,
+            options: ColumnOptions {
+                nullable: Some(false),
+                default: Some("0".to_string()),
+                unique: Some(true),
+                ..ColumnOptions::default()
+            },
+            previous: None,
+            position: None,
+        });
+        assert_eq!(
+            migration.generate_sql().unwrap(),
+            vec![
+                "ALTER TABLE users ALTER COLUMN age TYPE BIGINT;",
+                "ALTER TABLE users ALTER COLUMN age SET NOT NULL;",
+                "ALTER TABLE users ALTER COLUMN age SET DEFAULT 0;",
+                "CREATE UNIQUE INDEX users_age_unique ON users (age);",
+            ]
+        );
+    }
+
+    #[test]
+    fn drops_column_with_cascade_on_postgres() {
+        let drop = DropColumn {
+            column: "email".to_string(),
+            if_exists: false,
+            cascade: true,
+        };
+        assert_eq!(
+            drop.generate_sql("users", &PostgresDialect::new()).unwrap(),
+            "ALTER TABLE users DROP COLUMN email CASCADE;"
+        );
+        assert_eq!(
+            drop.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "ALTER TABLE users DROP COLUMN email;"
+        );
+        assert_eq!(drop.ignored_options(&MySqlDialect::new()), vec!["cascade"]);
+    }
+    fn merge_names(sources: &[&str], drop_sources: bool) -> MergeColumns {
+        MergeColumns {
+            sources: sources.iter().map(|s| s.to_string()).collect(),
+            separator: " ".to_string(),
+            target: Column {
+                name: "full_name".to_string(),
+                data_type: DataType::Varchar(255),
+                nullable: true,
+                auto_increment: false,
+                generated: None,
+                collation: None,
+            },
+            drop_sources,
+        }
+    }
+
+    #[test]
+    fn merges_two_columns() {
+        let merge = merge_names(&["first_name", "last_name"], false);
+        assert_eq!(
+            merge.statements("users", &PostgresDialect::new()).unwrap(),
+            vec![
+                "ALTER TABLE users ADD COLUMN full_name VARCHAR(255);",
+                "UPDATE users SET full_name = COALESCE(first_name, '') || ' ' || COALESCE(last_name, '');",
+            ]
+        );
+        assert_eq!(
+            merge.statements("users", &MySqlDialect::new()).unwrap(),
+            vec![
+                "ALTER TABLE users ADD COLUMN full_name VARCHAR(255);",
+                "UPDATE users SET full_name = CONCAT_WS(' ', first_name, last_name);",
+            ]
+        );
+        assert_eq!(
+            merge.rollback_sql("users", &MySqlDialect::new()).unwrap(),
+            "ALTER TABLE users DROP COLUMN full_name;"
+        );
+    }
+
+    #[test]
+    fn merges_three_columns_and_drops_them() {
+        let merge = merge_names(&["first_name", "middle_name", "last_name"], true);
+        assert_eq!(
+            merge.statements("users", &PostgresDialect::new()).unwrap(),
+            vec![
+                "ALTER TABLE users ADD COLUMN full_name VARCHAR(255);",
+                "UPDATE users SET full_name = COALESCE(first_name, '') || ' ' || COALESCE(middle_name, '') || ' ' || COALESCE(last_name, '');",
+                "ALTER TABLE users DROP COLUMN first_name;",
+                "ALTER TABLE users DROP COLUMN middle_name;",
+                "ALTER TABLE users DROP COLUMN last_name;",
+            ]
+        );
+        assert_eq!(
+            merge.statements("users", &MySqlDialect::new()).unwrap(),
+            vec![
+                "ALTER TABLE users ADD COLUMN full_name VARCHAR(255);",
+                "UPDATE users SET full_name = CONCAT_WS(' ', first_name, middle_name, last_name);",
+                "ALTER TABLE users DROP COLUMN first_name;",
+                "ALTER TABLE users DROP COLUMN middle_name;",
+                "ALTER TABLE users DROP COLUMN last_name;",
+            ]
+        );
+        assert!(merge.risk_profile().destructive);
+        assert_eq!(merge.rollback_sql("users", &MySqlDialect::new()), None);
+    }
+    #[test]
+    fn positions_changed_column_on_mysql_only() {
+        let change = ChangeColumnType {
+            position: Some(ColumnPosition::After("id".to_string())),
+            ..widen_age(None)
+        };
+        assert_eq!(
+            change.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "ALTER TABLE users MODIFY COLUMN age BIGINT AFTER id;"
+        );
+        assert_eq!(
+            change
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE users ALTER COLUMN age TYPE BIGINT;"
+        );
+        assert_eq!(
+            change.ignored_options(&PostgresDialect::new()),
+            vec!["column position"]
+        );
+    }
+
+    #[test]
+    fn reorders_mysql_column() {
+        let reorder = ReorderColumn {
+            column: Column {
+                name: "email".to_string(),
+                data_type: DataType::Varchar(255),
+                nullable: false,
+                auto_increment: false,
+                generated: None,
+                collation: None,
+            },
+            position: ColumnPosition::First,
+        };
+        assert_eq!(
+            reorder.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "ALTER TABLE users MODIFY COLUMN email VARCHAR(255) NOT NULL FIRST;"
+        );
+        let reorder = ReorderColumn {
+            position: ColumnPosition::After("id".to_string()),
+            ..reorder
+        };
+        assert_eq!(
+            reorder
+                .generate_sql("users", &MariaDbDialect::new())
+                .unwrap(),
+            "ALTER TABLE users MODIFY COLUMN email VARCHAR(255) NOT NULL AFTER id;"
+        );
+        assert_eq!(
+            reorder.generate_sql("users", &PostgresDialect::new()),
+            Err(MigrationError::unsupported("postgres", "column reordering"))
+        );
+    }
+    #[test]
+    fn renames_on_mysql_with_or_without_the_definition() {
+        let mut rename = RenameColumn {
+            old_name: "phone".to_string(),
+            new_name: "contact_number".to_string(),
+            definition: None,
+        };
+        let mysql = MySqlDialect::new();
+        assert_eq!(
+            rename.generate_sql("users", &mysql).unwrap(),
+            "ALTER TABLE users RENAME COLUMN phone TO contact_number;"
+        );
+
+        // MySQL 5.7 only has CHANGE COLUMN.
+        rename.definition = Some(Column {
+            name: "phone".to_string(),
+            data_type: DataType::Varchar(30),
+            nullable: false,
+            auto_increment: false,
+            generated: None,
+            collation: None,
+        });
+        assert_eq!(
+            rename.generate_sql("users", &mysql).unwrap(),
+            "ALTER TABLE users CHANGE COLUMN phone contact_number VARCHAR(30) NOT NULL;"
+        );
+        assert_eq!(
+            rename.rollback_sql("users", &mysql).unwrap(),
+            "ALTER TABLE users CHANGE COLUMN contact_number phone VARCHAR(30) NOT NULL;"
+        );
+        assert_eq!(
+            rename
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE users RENAME COLUMN phone TO contact_number;"
+        );
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use super::*;
+    use serde::de::DeserializeOwned;
+    use std::fmt::Debug;
+
+    fn assert_round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: T) {
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(serde_json::from_str::<T>(&json).unwrap(), value);
+    }
+
+    #[test]
+    fn add_column_round_trips() {
+        assert_round_trip(AddColumn {
+            column: Column {
+                name: "email".to_string(),
+                data_type: DataType::Varchar(255),
+                nullable: true,
+                auto_increment: false,
+                generated: None,
+                collation: None,
+            },
+            position: Some(ColumnPosition::After("username".to_string())),
+            if_not_exists: false,
+        });
+    }
+
+    #[test]
+    fn drop_column_round_trips() {
+        assert_round_trip(DropColumn {
+            column: "legacy_flag".to_string(),
+            if_exists: false,
+            cascade: false,
+        });
+    }
+
+    #[test]
+    fn rename_column_round_trips() {
+        assert_round_trip(RenameColumn {
+            old_name: "phone".to_string(),
+            new_name: "contact_number".to_string(),
+            definition: None,
+        });
+    }
+
+    #[test]
+    fn change_column_type_round_trips() {
+        assert_round_trip(ChangeColumnType {
+            column: "age".to_string(),
+            data_type: DataType::SmallInt,
+            options: ColumnOptions {
+                nullable: Some(false),
+                default: Some("0".to_string()),
+                unique: None,
+                collation: None,
+                using: None,
+                concurrently: false,
+            },
+            previous: Some((DataType::Integer, ColumnOptions::default())),
+            position: None,
+        });
+    }
+    #[test]
+    fn column_defaults_round_trip() {
+        assert_round_trip(SetColumnDefault {
+            column: "created_at".to_string(),
+            default: DefaultValue::Expression("CURRENT_TIMESTAMP".to_string()),
+        });
+        assert_round_trip(DropColumnDefault {
+            column: "created_at".to_string(),
+        });
+    }
+}
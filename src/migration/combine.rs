@@ -0,0 +1,193 @@
+use super::Migration;
+use crate::dialect::keyword::sql;
+use crate::dialect::Feature;
+use crate::error::MigrationError;
+
+impl Migration {
+    /// Like [`Migration::generate_sql`], but on dialects supporting
+    /// [`Feature::CombinedAlter`] each run of consecutive
+    /// [`combinable`](super::MigrationStep::combinable) operations becomes a
+    /// single `ALTER TABLE` with their actions separated by commas, so MySQL
+    /// rebuilds the table once instead of once per operation.
+    ///
+    /// Every other operation keeps its place between the runs, so a data
+    /// update between two column changes still sees the first and not the
+    /// second.
+    pub fn generate_sql_combined(&self) -> Result<Vec<String>, MigrationError> {
+        if !self.dialect.supports(Feature::CombinedAlter) {
+            return Ok(self
+                .generate_sql()?
+                .into_iter()
+                .map(|statement| statement.sql)
+                .collect());
+        }
+        let prefix = sql!(
+            self.dialect,
+            "ALTER TABLE {} ",
+            self.dialect.table_identifier(&self.table.to_string())
+        );
+        // Given once for the whole statement rather than per action.
+        let alter_options = self.dialect.alter_options();
+        let mut statements = Vec::new();
+        let mut actions: Vec<String> = Vec::new();
+        for operation in &self.operations {
+            let sql: Vec<String> = self
+                .generate_operation(operation.as_ref())?
+                .into_iter()
+                .map(|statement| statement.sql)
+                .collect();
+            let operation_actions: Option<Vec<&str>> = if operation.combinable() {
+                sql.iter()
+                    .map(|sql| {
+                        let action = sql.strip_prefix(&prefix)?.strip_suffix(';')?;
+                        Some(action.strip_suffix(&alter_options).unwrap_or(action))
+                    })
+                    .collect()
+            } else {
+                None
+            };
+            match operation_actions {
+                Some(operation_actions) => {
+                    actions.extend(operation_actions.into_iter().map(str::to_string))
+                }
+                None => {
+                    flush(&prefix, &alter_options, &mut actions, &mut statements);
+                    statements.extend(sql);
+                }
+            }
+        }
+        flush(&prefix, &alter_options, &mut actions, &mut statements);
+        Ok(statements)
+    }
+}
+
+/// Ends the current run of actions with one `ALTER TABLE` holding them all.
+fn flush(
+    prefix: &str,
+    alter_options: &str,
+    actions: &mut Vec<String>,
+    statements: &mut Vec<String>,
+) {
+    if !actions.is_empty() {
+        statements.push(format!(
+            "{}{}{};",
+            prefix,
+            actions.join(", "),
+            alter_options
+        ));
+        actions.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_type::{Column, ColumnOptions, DataType};
+    use crate::dialect::{Algorithm, Dialect, Lock, MySqlDialect, OnlineDdl, PostgresDialect};
+    use crate::migration::{
+        AddColumn, ChangeColumnType, CreateIndex, DropColumn, UpdateColumnData, UpdateValue,
+    };
+
+    fn add(name: &str) -> AddColumn {
+        AddColumn {
+            column: Column {
+                name: name.to_string(),
+                data_type: DataType::Text,
+                nullable: true,
+                auto_increment: false,
+                generated: None,
+                collation: None,
+            },
+            position: None,
+            if_not_exists: false,
+        }
+    }
+
+    fn drop(name: &str) -> DropColumn {
+        DropColumn {
+            column: name.to_string(),
+            if_exists: false,
+            cascade: false,
+        }
+    }
+
+    fn migration(dialect: Box<dyn Dialect>) -> Migration {
+        let mut migration = Migration::new("users", dialect);
+        migration
+            .add_operation(add("nickname"))
+            .add_operation(ChangeColumnType {
+                column: "bio".to_string(),
+                data_type: DataType::Varchar(500),
+                options: ColumnOptions::default(),
+                previous: None,
+                position: None,
+            })
+            .add_operation(CreateIndex {
+                name: None,
+                columns: vec!["nickname".into()],
+                unique: false,
+                conditional_on_rows: None,
+                concurrently: false,
+                if_not_exists: false,
+                where_clause: None,
+            })
+            .add_operation(drop("legacy_flag"))
+            .add_operation(add("display_name"));
+        migration
+    }
+
+    #[test]
+    fn combines_consecutive_column_changes_on_mysql() {
+        assert_eq!(
+            migration(Box::new(MySqlDialect::new()))
+                .generate_sql_combined()
+                .unwrap(),
+            vec![
+                "ALTER TABLE users ADD COLUMN nickname TEXT, MODIFY COLUMN bio VARCHAR(500);",
+                "CREATE INDEX idx_users_nickname ON users (nickname);",
+                "ALTER TABLE users DROP COLUMN legacy_flag, ADD COLUMN display_name TEXT;",
+            ]
+        );
+    }
+
+    #[test]
+    fn gives_online_ddl_clauses_once_per_combined_statement() {
+        let dialect = MySqlDialect::new().with_online_ddl(OnlineDdl {
+            algorithm: Some(Algorithm::Inplace),
+            lock: Some(Lock::None),
+        });
+        assert_eq!(
+            migration(Box::new(dialect))
+                .generate_sql_combined()
+                .unwrap()[0],
+            "ALTER TABLE users ADD COLUMN nickname TEXT, MODIFY COLUMN bio VARCHAR(500), \
+             ALGORITHM=INPLACE, LOCK=NONE;"
+        );
+    }
+
+    #[test]
+    fn data_updates_split_the_runs() {
+        let mut migration = Migration::new("users", Box::new(MySqlDialect::new()));
+        migration
+            .add_operation(add("display_name"))
+            .add_operation(UpdateColumnData {
+                column: "display_name".to_string(),
+                value: UpdateValue::Column("username".to_string()),
+                conditions: Vec::new(),
+            })
+            .add_operation(drop("username"));
+        assert_eq!(
+            migration.generate_sql_combined().unwrap(),
+            migration.generate_sql().unwrap()
+        );
+    }
+
+    #[test]
+    fn leaves_other_dialects_alone() {
+        let migration = migration(Box::new(PostgresDialect::new()));
+        assert_eq!(
+            migration.generate_sql_combined().unwrap(),
+            migration.generate_sql().unwrap()
+        );
+    }
+}
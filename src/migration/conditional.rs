@@ -0,0 +1,151 @@
+use super::statement::{joined, of_step};
+use super::{ColumnEffect, MigrationStep, RiskProfile, Statement};
+use crate::dialect::Dialect;
+use crate::error::MigrationError;
+
+/// A check on the live schema, made when the migration runs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Precondition {
+    ColumnExists(String),
+    ColumnMissing(String),
+}
+
+/// Runs an operation only when a precondition holds, for migrations that
+/// must apply cleanly to databases whose schemas have drifted.
+///
+/// The check reads `information_schema.columns`: Postgres wraps the
+/// statement in a `DO` block and MySQL in a prepared statement, so the inner
+/// operation must generate a single statement. Whether it ran is only known
+/// at run time, so the step has no rollback and reports no column effects.
+pub struct ConditionalStep {
+    pub precondition: Precondition,
+    pub step: Box<dyn MigrationStep>,
+}
+
+impl ConditionalStep {
+    pub fn new(precondition: Precondition, step: impl MigrationStep + 'static) -> Self {
+        ConditionalStep {
+            precondition,
+            step: Box::new(step),
+        }
+    }
+}
+
+impl MigrationStep for ConditionalStep {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(joined(&self.statements(table, dialect)?))
+    }
+
+    fn statements(
+        &self,
+        table: &str,
+        dialect: &dyn Dialect,
+    ) -> Result<Vec<Statement>, MigrationError> {
+        let sql = self.step.generate_sql(table, dialect)?;
+        let sql = dialect.conditional(table, &self.precondition, &sql)?;
+        Ok(of_step(self, table, sql))
+    }
+
+    fn transactional(&self) -> bool {
+        self.step.transactional()
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        Vec::new()
+    }
+
+    fn risk_profile(&self) -> RiskProfile {
+        self.step.risk_profile()
+    }
+
+    fn ignored_options(&self, dialect: &dyn Dialect) -> Vec<&'static str> {
+        self.step.ignored_options(dialect)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_type::{Column, DataType};
+    use crate::dialect::{MariaDbDialect, MySqlDialect, PostgresDialect, SqliteDialect};
+    use crate::migration::{AddColumn, DropColumn};
+
+    fn drop_legacy_flag() -> ConditionalStep {
+        ConditionalStep::new(
+            Precondition::ColumnExists("legacy_flag".to_string()),
+            DropColumn {
+                column: "legacy_flag".to_string(),
+                if_exists: false,
+                cascade: false,
+            },
+        )
+    }
+
+    #[test]
+    fn wraps_postgres_statement_in_do_block() {
+        assert_eq!(
+            drop_legacy_flag()
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "DO $$\nBEGIN\n  IF EXISTS (SELECT 1 FROM information_schema.columns WHERE table_schema = current_schema() AND table_name = 'users' AND column_name = 'legacy_flag') THEN\n    ALTER TABLE users DROP COLUMN legacy_flag;\n  END IF;\nEND\n$$;"
+        );
+    }
+
+    #[test]
+    fn prepares_mysql_statement_when_column_is_missing() {
+        let add = ConditionalStep::new(
+            Precondition::ColumnMissing("nickname".to_string()),
+            AddColumn {
+                column: Column {
+                    name: "nickname".to_string(),
+                    data_type: DataType::Varchar(50),
+                    nullable: true,
+                    auto_increment: false,
+                    generated: None,
+                    collation: None,
+                },
+                position: None,
+                if_not_exists: false,
+            },
+        );
+        let sql = "SET @drift_sql = IF((SELECT COUNT(*) FROM information_schema.columns WHERE table_schema = DATABASE() AND table_name = 'users' AND column_name = 'nickname') = 0, 'ALTER TABLE users ADD COLUMN nickname VARCHAR(50)', 'DO 0');\nPREPARE drift_stmt FROM @drift_sql;\nEXECUTE drift_stmt;\nDEALLOCATE PREPARE drift_stmt;";
+        assert_eq!(
+            add.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            sql
+        );
+        assert_eq!(
+            add.generate_sql("users", &MariaDbDialect::new()).unwrap(),
+            sql
+        );
+    }
+
+    #[test]
+    fn escapes_quotes_in_mysql_statement() {
+        let sql = MySqlDialect::new()
+            .conditional(
+                "users",
+                &Precondition::ColumnExists("status".to_string()),
+                "UPDATE users SET status = 'active';",
+            )
+            .unwrap();
+        assert!(sql[0].contains("'UPDATE users SET status = ''active'''"));
+    }
+
+    #[test]
+    fn sqlite_has_no_information_schema() {
+        assert_eq!(
+            drop_legacy_flag().generate_sql("users", &SqliteDialect::new()),
+            Err(MigrationError::unsupported(
+                "sqlite",
+                "conditional operations"
+            ))
+        );
+    }
+    #[test]
+    fn checks_the_table_schema() {
+        let sql = drop_legacy_flag()
+            .generate_sql("analytics.users", &PostgresDialect::new())
+            .unwrap();
+        assert!(sql.contains("table_schema = 'analytics' AND table_name = 'users'"));
+    }
+}
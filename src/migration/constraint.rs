@@ -0,0 +1,761 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::header::checksum;
+use super::statement::{joined, of_step};
+use super::{ColumnEffect, MigrationStep, Statement};
+use crate::data_type::Column;
+use crate::dialect::keyword::{sql, Verbatim};
+use crate::dialect::{Dialect, Feature, NameKind};
+use crate::error::MigrationError;
+
+/// Adds a `CHECK` constraint. `expression` is raw SQL and is not escaped.
+///
+/// MySQL only enforces checks from 8.0.16; older servers parse and silently
+/// ignore them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AddCheckConstraint {
+    /// Defaults to `chk_{table}_{hash}`, where the hash is derived from the
+    /// expression so the name is stable across runs. Generated names follow
+    /// the dialect's [`NamingStrategy`](crate::dialect::NamingStrategy).
+    pub name: Option<String>,
+    pub expression: String,
+    /// See [`AddForeignKey::validate_separately`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub validate_separately: bool,
+}
+
+impl AddCheckConstraint {
+    pub fn constraint_name(&self, table: &str, dialect: &dyn Dialect) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => {
+                let hash = checksum(std::slice::from_ref(&self.expression)) as u32;
+                dialect.generated_name(NameKind::CheckConstraint, table, &[format!("{:08x}", hash)])
+            }
+        }
+    }
+}
+
+impl MigrationStep for AddCheckConstraint {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(joined(&self.statements(table, dialect)?))
+    }
+
+    fn statements(
+        &self,
+        table: &str,
+        dialect: &dyn Dialect,
+    ) -> Result<Vec<Statement>, MigrationError> {
+        let name = self.constraint_name(table, dialect);
+        let sql = dialect.add_check_constraint(table, &name, &self.expression)?;
+        let sql = validated_separately(sql, table, &name, self.validate_separately, dialect);
+        Ok(of_step(self, table, sql))
+    }
+
+    fn ignored_options(&self, dialect: &dyn Dialect) -> Vec<&'static str> {
+        ignored_not_valid(self.validate_separately, dialect)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DropCheckConstraint {
+    pub name: String,
+}
+
+impl MigrationStep for DropCheckConstraint {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        dialect.drop_check_constraint(table, &self.name)
+    }
+}
+
+/// Adds a `UNIQUE` constraint over `columns`. Unlike the unique index created
+/// by a type change, a constraint can be the target of a foreign key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AddUniqueConstraint {
+    pub columns: Vec<String>,
+    /// Defaults to `uq_{table}_{columns}`; see
+    /// [`NamingStrategy`](crate::dialect::NamingStrategy).
+    pub name: Option<String>,
+}
+
+impl AddUniqueConstraint {
+    pub fn constraint_name(&self, table: &str, dialect: &dyn Dialect) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => dialect.generated_name(NameKind::UniqueConstraint, table, &self.columns),
+        }
+    }
+}
+
+impl MigrationStep for AddUniqueConstraint {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(dialect.add_unique_constraint(
+            table,
+            &self.constraint_name(table, dialect),
+            &self.columns,
+        ))
+    }
+
+    fn rollback_sql(&self, table: &str, dialect: &dyn Dialect) -> Option<String> {
+        Some(dialect.drop_unique_constraint(table, &self.constraint_name(table, dialect)))
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        self.columns
+            .iter()
+            .map(|column| ColumnEffect::Reference(column.clone()))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DropUniqueConstraint {
+    pub name: String,
+}
+
+impl MigrationStep for DropUniqueConstraint {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(dialect.drop_unique_constraint(table, &self.name))
+    }
+}
+
+/// Makes `columns` the table's primary key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AddPrimaryKey {
+    pub columns: Vec<String>,
+    /// Defaults to `{table}_pkey`, the name Postgres picks itself. MySQL
+    /// always names the primary key `PRIMARY` and ignores this.
+    pub name: Option<String>,
+}
+
+impl MigrationStep for AddPrimaryKey {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        let name = primary_key_name(table, self.name.as_deref(), dialect);
+        dialect.add_primary_key(table, &name, &self.columns)
+    }
+
+    fn rollback_sql(&self, table: &str, dialect: &dyn Dialect) -> Option<String> {
+        let name = primary_key_name(table, self.name.as_deref(), dialect);
+        dialect.drop_primary_key(table, &name, None).ok()
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        self.columns
+            .iter()
+            .map(|column| ColumnEffect::Reference(column.clone()))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DropPrimaryKey {
+    /// Defaults to `{table}_pkey`, see [`AddPrimaryKey::name`].
+    pub name: Option<String>,
+    /// The key's `AUTO_INCREMENT` column, if any. MySQL refuses to drop the
+    /// key while the column is still auto-incremented, so it is redefined
+    /// with this definition in the same statement.
+    pub auto_increment_column: Option<Column>,
+}
+
+impl MigrationStep for DropPrimaryKey {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        let name = primary_key_name(table, self.name.as_deref(), dialect);
+        dialect.drop_primary_key(table, &name, self.auto_increment_column.as_ref())
+    }
+}
+
+fn primary_key_name(table: &str, name: Option<&str>, dialect: &dyn Dialect) -> String {
+    match name {
+        Some(name) => name.to_string(),
+        None => dialect.generated_name(NameKind::PrimaryKey, table, &[]),
+    }
+}
+
+/// What happens to referencing rows when the referenced row is deleted or
+/// its key is updated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ReferentialAction {
+    Cascade,
+    SetNull,
+    Restrict,
+    NoAction,
+}
+
+impl ReferentialAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReferentialAction::Cascade => "CASCADE",
+            ReferentialAction::SetNull => "SET NULL",
+            ReferentialAction::Restrict => "RESTRICT",
+            ReferentialAction::NoAction => "NO ACTION",
+        }
+    }
+}
+
+/// Adds a `FOREIGN KEY` constraint. `columns` and `referenced_columns` pair up
+/// by position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AddForeignKey {
+    /// Defaults to `fk_{table}_{columns}`; see
+    /// [`NamingStrategy`](crate::dialect::NamingStrategy).
+    pub name: Option<String>,
+    pub columns: Vec<String>,
+    pub referenced_table: String,
+    pub referenced_columns: Vec<String>,
+    pub on_delete: Option<ReferentialAction>,
+    pub on_update: Option<ReferentialAction>,
+    /// Add the constraint `NOT VALID`, taking only a brief lock, then check
+    /// existing rows with `VALIDATE CONSTRAINT`, which does not block writes.
+    /// Honoured by Postgres.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub validate_separately: bool,
+}
+
+impl AddForeignKey {
+    pub fn constraint_name(&self, table: &str, dialect: &dyn Dialect) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => dialect.generated_name(NameKind::ForeignKey, table, &self.columns),
+        }
+    }
+}
+
+impl MigrationStep for AddForeignKey {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(joined(&self.statements(table, dialect)?))
+    }
+
+    fn statements(
+        &self,
+        table: &str,
+        dialect: &dyn Dialect,
+    ) -> Result<Vec<Statement>, MigrationError> {
+        let name = self.constraint_name(table, dialect);
+        let sql = dialect.add_foreign_key(table, &name, self)?;
+        let sql = validated_separately(sql, table, &name, self.validate_separately, dialect);
+        Ok(of_step(self, table, sql))
+    }
+
+    fn ignored_options(&self, dialect: &dyn Dialect) -> Vec<&'static str> {
+        ignored_not_valid(self.validate_separately, dialect)
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        self.columns
+            .iter()
+            .map(|column| ColumnEffect::Reference(column.clone()))
+            .collect()
+    }
+}
+
+/// Splits adding a constraint into `NOT VALID` and `VALIDATE CONSTRAINT`
+/// where the dialect supports it.
+fn validated_separately(
+    sql: String,
+    table: &str,
+    name: &str,
+    validate_separately: bool,
+    dialect: &dyn Dialect,
+) -> Vec<String> {
+    if !validate_separately || !dialect.supports(Feature::NotValidConstraints) {
+        return vec![sql];
+    }
+    vec![
+        sql!(
+            dialect,
+            "{} NOT VALID;",
+            Verbatim(sql.trim_end_matches(';'))
+        ),
+        dialect.validate_constraint(table, name),
+    ]
+}
+
+fn ignored_not_valid(validate_separately: bool, dialect: &dyn Dialect) -> Vec<&'static str> {
+    if validate_separately && !dialect.supports(Feature::NotValidConstraints) {
+        vec!["validate_separately"]
+    } else {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_type::DataType;
+    use crate::dialect::{MySqlDialect, PostgresDialect};
+
+    fn foreign_key(
+        on_delete: Option<ReferentialAction>,
+        on_update: Option<ReferentialAction>,
+    ) -> AddForeignKey {
+        AddForeignKey {
+            name: None,
+            columns: vec!["customer_id".to_string()],
+            referenced_table: "customers".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete,
+            on_update,
+            validate_separately: false,
+        }
+    }
+
+    #[test]
+    fn uses_explicit_constraint_name() {
+        let check = AddCheckConstraint {
+            name: Some("price_positive".to_string()),
+            expression: "price > 0".to_string(),
+            validate_separately: false,
+        };
+        assert_eq!(
+            check
+                .generate_sql("products", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE products ADD CONSTRAINT price_positive CHECK (price > 0);"
+        );
+        assert_eq!(
+            check
+                .generate_sql("products", &MySqlDialect::new())
+                .unwrap(),
+            "ALTER TABLE products ADD CONSTRAINT price_positive CHECK (price > 0);"
+        );
+    }
+
+    #[test]
+    fn derives_deterministic_name_from_expression() {
+        let check = AddCheckConstraint {
+            name: None,
+            expression: "price > 0".to_string(),
+            validate_separately: false,
+        };
+        let name = check.constraint_name("products", &PostgresDialect::new());
+
+        assert!(name.starts_with("chk_products_"));
+        assert_eq!(name.len(), "chk_products_".len() + 8);
+        assert_eq!(
+            name,
+            check
+                .clone()
+                .constraint_name("products", &PostgresDialect::new())
+        );
+        assert_ne!(
+            name,
+            AddCheckConstraint {
+                name: None,
+                expression: "price >= 0".to_string(),
+                validate_separately: false,
+            }
+            .constraint_name("products", &PostgresDialect::new())
+        );
+        assert_eq!(
+            check
+                .generate_sql("products", &PostgresDialect::new())
+                .unwrap(),
+            format!(
+                "ALTER TABLE products ADD CONSTRAINT {} CHECK (price > 0);",
+                name
+            )
+        );
+    }
+
+    #[test]
+    fn drops_check_constraint() {
+        let drop = DropCheckConstraint {
+            name: "price_positive".to_string(),
+        };
+        assert_eq!(
+            drop.generate_sql("products", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE products DROP CONSTRAINT price_positive;"
+        );
+        assert_eq!(
+            drop.generate_sql("products", &MySqlDialect::new()).unwrap(),
+            "ALTER TABLE products DROP CHECK price_positive;"
+        );
+    }
+
+    fn unique(columns: &[&str]) -> AddUniqueConstraint {
+        AddUniqueConstraint {
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            name: None,
+        }
+    }
+
+    #[test]
+    fn adds_unique_constraints_over_one_to_three_columns() {
+        let cases = [
+            (
+                unique(&["email"]),
+                "ALTER TABLE users ADD CONSTRAINT uq_users_email UNIQUE (email);",
+            ),
+            (
+                unique(&["tenant_id", "email"]),
+                "ALTER TABLE users ADD CONSTRAINT uq_users_tenant_id_email \
+                 UNIQUE (tenant_id, email);",
+            ),
+            (
+                unique(&["tenant_id", "region", "email"]),
+                "ALTER TABLE users ADD CONSTRAINT uq_users_tenant_id_region_email \
+                 UNIQUE (tenant_id, region, email);",
+            ),
+        ];
+        for (constraint, sql) in cases {
+            assert_eq!(
+                constraint
+                    .generate_sql("users", &PostgresDialect::new())
+                    .unwrap(),
+                sql
+            );
+            assert_eq!(
+                constraint
+                    .generate_sql("users", &MySqlDialect::new())
+                    .unwrap(),
+                sql
+            );
+        }
+    }
+
+    #[test]
+    fn uses_explicit_unique_constraint_name() {
+        let constraint = AddUniqueConstraint {
+            name: Some("users_email_key".to_string()),
+            ..unique(&["email"])
+        };
+        assert_eq!(
+            constraint.constraint_name("users", &PostgresDialect::new()),
+            "users_email_key"
+        );
+        assert_eq!(
+            constraint
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE users ADD CONSTRAINT users_email_key UNIQUE (email);"
+        );
+    }
+
+    #[test]
+    fn rolls_back_unique_constraint_by_dropping_it() {
+        let constraint = unique(&["tenant_id", "email"]);
+        assert_eq!(
+            constraint
+                .rollback_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE users DROP CONSTRAINT uq_users_tenant_id_email;"
+        );
+        assert_eq!(
+            constraint
+                .rollback_sql("users", &MySqlDialect::new())
+                .unwrap(),
+            "ALTER TABLE users DROP INDEX uq_users_tenant_id_email;"
+        );
+    }
+
+    #[test]
+    fn drops_unique_constraint() {
+        let drop = DropUniqueConstraint {
+            name: "uq_users_email".to_string(),
+        };
+        assert_eq!(
+            drop.generate_sql("users", &PostgresDialect::new()).unwrap(),
+            "ALTER TABLE users DROP CONSTRAINT uq_users_email;"
+        );
+        assert_eq!(
+            drop.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "ALTER TABLE users DROP INDEX uq_users_email;"
+        );
+    }
+
+    #[test]
+    fn adds_single_column_primary_key() {
+        let primary_key = AddPrimaryKey {
+            columns: vec!["id".to_string()],
+            name: None,
+        };
+        assert_eq!(
+            primary_key
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE users ADD CONSTRAINT users_pkey PRIMARY KEY (id);"
+        );
+        assert_eq!(
+            primary_key
+                .generate_sql("users", &MySqlDialect::new())
+                .unwrap(),
+            "ALTER TABLE users ADD PRIMARY KEY (id);"
+        );
+    }
+
+    #[test]
+    fn adds_composite_primary_key() {
+        let primary_key = AddPrimaryKey {
+            columns: vec!["user_id".to_string(), "role_id".to_string()],
+            name: Some("user_roles_pk".to_string()),
+        };
+        assert_eq!(
+            primary_key
+                .generate_sql("user_roles", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE user_roles ADD CONSTRAINT user_roles_pk PRIMARY KEY (user_id, role_id);"
+        );
+        assert_eq!(
+            primary_key
+                .generate_sql("user_roles", &MySqlDialect::new())
+                .unwrap(),
+            "ALTER TABLE user_roles ADD PRIMARY KEY (user_id, role_id);"
+        );
+    }
+
+    #[test]
+    fn rolls_back_primary_key_by_dropping_it() {
+        let primary_key = AddPrimaryKey {
+            columns: vec!["user_id".to_string(), "role_id".to_string()],
+            name: None,
+        };
+        assert_eq!(
+            primary_key
+                .rollback_sql("user_roles", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE user_roles DROP CONSTRAINT user_roles_pkey;"
+        );
+        assert_eq!(
+            primary_key
+                .rollback_sql("user_roles", &MySqlDialect::new())
+                .unwrap(),
+            "ALTER TABLE user_roles DROP PRIMARY KEY;"
+        );
+    }
+
+    #[test]
+    fn drops_primary_key() {
+        let drop = DropPrimaryKey {
+            name: None,
+            auto_increment_column: None,
+        };
+        assert_eq!(
+            drop.generate_sql("user_roles", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE user_roles DROP CONSTRAINT user_roles_pkey;"
+        );
+        assert_eq!(
+            drop.generate_sql("user_roles", &MySqlDialect::new())
+                .unwrap(),
+            "ALTER TABLE user_roles DROP PRIMARY KEY;"
+        );
+    }
+
+    #[test]
+    fn removes_auto_increment_before_dropping_mysql_primary_key() {
+        let drop = DropPrimaryKey {
+            name: None,
+            auto_increment_column: Some(Column {
+                name: "id".to_string(),
+                data_type: DataType::BigInt,
+                nullable: false,
+                auto_increment: false,
+                generated: None,
+                collation: None,
+            }),
+        };
+        assert_eq!(
+            drop.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "ALTER TABLE users MODIFY COLUMN id BIGINT NOT NULL, DROP PRIMARY KEY;"
+        );
+        assert_eq!(
+            drop.generate_sql("users", &PostgresDialect::new()).unwrap(),
+            "ALTER TABLE users DROP CONSTRAINT users_pkey;"
+        );
+    }
+
+    #[test]
+    fn adds_foreign_key_with_generated_name() {
+        let sql = "ALTER TABLE orders ADD CONSTRAINT fk_orders_customer_id \
+                   FOREIGN KEY (customer_id) REFERENCES customers (id);";
+        let foreign_key = foreign_key(None, None);
+        assert_eq!(
+            foreign_key
+                .generate_sql("orders", &PostgresDialect::new())
+                .unwrap(),
+            sql
+        );
+        assert_eq!(
+            foreign_key
+                .generate_sql("orders", &MySqlDialect::new())
+                .unwrap(),
+            sql
+        );
+    }
+
+    #[test]
+    fn adds_composite_foreign_key_with_explicit_name() {
+        let foreign_key = AddForeignKey {
+            name: Some("order_lines_order_fk".to_string()),
+            columns: vec!["order_id".to_string(), "order_version".to_string()],
+            referenced_table: "orders".to_string(),
+            referenced_columns: vec!["id".to_string(), "version".to_string()],
+            on_delete: Some(ReferentialAction::Cascade),
+            on_update: None,
+            validate_separately: false,
+        };
+        let sql = "ALTER TABLE order_lines ADD CONSTRAINT order_lines_order_fk \
+                   FOREIGN KEY (order_id, order_version) REFERENCES orders (id, version) \
+                   ON DELETE CASCADE;";
+        assert_eq!(
+            foreign_key
+                .generate_sql("order_lines", &PostgresDialect::new())
+                .unwrap(),
+            sql
+        );
+        assert_eq!(
+            foreign_key
+                .generate_sql("order_lines", &MySqlDialect::new())
+                .unwrap(),
+            sql
+        );
+    }
+
+    #[test]
+    fn emits_every_referential_action() {
+        let actions = [
+            ReferentialAction::Cascade,
+            ReferentialAction::SetNull,
+            ReferentialAction::Restrict,
+            ReferentialAction::NoAction,
+        ];
+        for on_delete in actions {
+            for on_update in actions {
+                let sql = format!(
+                    "ALTER TABLE orders ADD CONSTRAINT fk_orders_customer_id \
+                     FOREIGN KEY (customer_id) REFERENCES customers (id) \
+                     ON DELETE {} ON UPDATE {};",
+                    on_delete.as_str(),
+                    on_update.as_str()
+                );
+                let foreign_key = foreign_key(Some(on_delete), Some(on_update));
+                assert_eq!(
+                    foreign_key
+                        .generate_sql("orders", &PostgresDialect::new())
+                        .unwrap(),
+                    sql
+                );
+                assert_eq!(
+                    foreign_key
+                        .generate_sql("orders", &MySqlDialect::new())
+                        .unwrap(),
+                    sql
+                );
+            }
+        }
+    }
+    #[test]
+    fn validates_postgres_foreign_key_separately() {
+        let foreign_key = AddForeignKey {
+            validate_separately: true,
+            ..foreign_key(None, None)
+        };
+        assert_eq!(
+            foreign_key
+                .statements("orders", &PostgresDialect::new())
+                .unwrap(),
+            [
+                "ALTER TABLE orders ADD CONSTRAINT fk_orders_customer_id FOREIGN KEY (customer_id) REFERENCES customers (id) NOT VALID;",
+                "ALTER TABLE orders VALIDATE CONSTRAINT fk_orders_customer_id;"
+            ]
+        );
+        assert_eq!(
+            foreign_key
+                .generate_sql("orders", &MySqlDialect::new())
+                .unwrap(),
+            "ALTER TABLE orders ADD CONSTRAINT fk_orders_customer_id FOREIGN KEY (customer_id) REFERENCES customers (id);"
+        );
+        assert_eq!(
+            foreign_key.ignored_options(&MySqlDialect::new()),
+            vec!["validate_separately"]
+        );
+    }
+
+    #[test]
+    fn validates_postgres_check_constraint_separately() {
+        let check = AddCheckConstraint {
+            name: Some("chk_orders_total".to_string()),
+            expression: "total >= 0".to_string(),
+            validate_separately: true,
+        };
+        assert_eq!(
+            check.statements("orders", &PostgresDialect::new()).unwrap(),
+            [
+                "ALTER TABLE orders ADD CONSTRAINT chk_orders_total CHECK (total >= 0) NOT VALID;",
+                "ALTER TABLE orders VALIDATE CONSTRAINT chk_orders_total;"
+            ]
+        );
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use super::*;
+
+    #[test]
+    fn check_constraints_round_trip() {
+        let add = AddCheckConstraint {
+            name: None,
+            expression: "price > 0".to_string(),
+            validate_separately: false,
+        };
+        let json = serde_json::to_string(&add).unwrap();
+        assert_eq!(
+            serde_json::from_str::<AddCheckConstraint>(&json).unwrap(),
+            add
+        );
+
+        let drop = DropCheckConstraint {
+            name: "price_positive".to_string(),
+        };
+        let json = serde_json::to_string(&drop).unwrap();
+        assert_eq!(
+            serde_json::from_str::<DropCheckConstraint>(&json).unwrap(),
+            drop
+        );
+    }
+
+    #[test]
+    fn foreign_key_round_trips() {
+        let foreign_key = AddForeignKey {
+            name: None,
+            columns: vec!["customer_id".to_string()],
+            referenced_table: "customers".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: Some(ReferentialAction::SetNull),
+            on_update: Some(ReferentialAction::NoAction),
+            validate_separately: false,
+        };
+        let json = serde_json::to_string(&foreign_key).unwrap();
+        assert_eq!(
+            serde_json::from_str::<AddForeignKey>(&json).unwrap(),
+            foreign_key
+        );
+    }
+    #[test]
+    fn primary_keys_round_trip() {
+        let add = AddPrimaryKey {
+            columns: vec!["user_id".to_string(), "role_id".to_string()],
+            name: None,
+        };
+        let json = serde_json::to_string(&add).unwrap();
+        assert_eq!(serde_json::from_str::<AddPrimaryKey>(&json).unwrap(), add);
+
+        let drop = DropPrimaryKey {
+            name: Some("user_roles_pk".to_string()),
+            auto_increment_column: None,
+        };
+        let json = serde_json::to_string(&drop).unwrap();
+        assert_eq!(serde_json::from_str::<DropPrimaryKey>(&json).unwrap(), drop);
+    }
+}
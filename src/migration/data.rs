@@ -0,0 +1,1060 @@
+use std::collections::HashMap;
+use std::process::Command;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::overrides::substitute;
+use super::statement::{joined, of_step};
+use super::{ColumnEffect, LockLevel, MigrationStep, Param, RiskProfile, Statement};
+use crate::dialect::{Dialect, Feature};
+use crate::error::MigrationError;
+
+/// The new value written by an update.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum UpdateValue {
+    /// SQL written into the statement as-is, e.g. `42` or `now()`.
+    Fixed(String),
+    /// A string, quoted and escaped by the dialect.
+    Literal(String),
+    /// The value of another column in the same row.
+    Column(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Operator {
+    Eq,
+    NotEq,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Like,
+    /// Equality that treats two `NULL`s as equal.
+    NullSafeEq,
+    /// Case-insensitive `LIKE`.
+    ILike,
+}
+
+impl Operator {
+    /// The standard SQL spelling. Dialects that spell an operator differently
+    /// override [`Dialect::render_condition`].
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Operator::Eq => "=",
+            Operator::NotEq => "<>",
+            Operator::Gt => ">",
+            Operator::Gte => ">=",
+            Operator::Lt => "<",
+            Operator::Lte => "<=",
+            Operator::Like => "LIKE",
+            Operator::NullSafeEq => "IS NOT DISTINCT FROM",
+            Operator::ILike => "ILIKE",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WhereCondition {
+    pub column: String,
+    pub operator: Operator,
+    pub value: UpdateValue,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UpdateColumnData {
+    pub column: String,
+    pub value: UpdateValue,
+    pub conditions: Vec<WhereCondition>,
+}
+
+impl MigrationStep for UpdateColumnData {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(dialect.update_column_data(table, &self.column, &self.value, &self.conditions))
+    }
+
+    fn generate_parameterized(
+        &self,
+        table: &str,
+        dialect: &dyn Dialect,
+    ) -> Result<Option<(String, Vec<Param>)>, MigrationError> {
+        Ok(Some(dialect.update_column_data_parameterized(
+            table,
+            &self.column,
+            &self.value,
+            &self.conditions,
+        )))
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        let mut columns = vec![self.column.clone()];
+        if let UpdateValue::Column(source) = &self.value {
+            columns.push(source.clone());
+        }
+        for condition in &self.conditions {
+            columns.push(condition.column.clone());
+            if let UpdateValue::Column(other) = &condition.value {
+                columns.push(other.clone());
+            }
+        }
+
+        let mut effects = Vec::new();
+        for column in columns {
+            let effect = ColumnEffect::Reference(column);
+            if !effects.contains(&effect) {
+                effects.push(effect);
+            }
+        }
+        effects
+    }
+
+    fn risk_profile(&self) -> RiskProfile {
+        RiskProfile {
+            destructive: self.conditions.is_empty(),
+            requires_rewrite: false,
+            lock_level: LockLevel::Row,
+        }
+    }
+
+    fn apply_overrides(
+        &mut self,
+        overrides: &HashMap<String, String>,
+    ) -> Result<(), MigrationError> {
+        substitute_value(&mut self.value, overrides)?;
+        for condition in &mut self.conditions {
+            substitute_value(&mut condition.value, overrides)?;
+        }
+        Ok(())
+    }
+}
+
+/// Replaces the `${token}`s of a fixed or literal value; column references
+/// are left alone.
+fn substitute_value(
+    value: &mut UpdateValue,
+    overrides: &HashMap<String, String>,
+) -> Result<(), MigrationError> {
+    if let UpdateValue::Fixed(value) | UpdateValue::Literal(value) = value {
+        *value = substitute(value, overrides)?;
+    }
+    Ok(())
+}
+
+/// Deletes the rows matching every condition. Without conditions the whole
+/// table is emptied, which has to be allowed explicitly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DeleteRows {
+    pub conditions: Vec<WhereCondition>,
+    pub allow_full_table_delete: bool,
+}
+
+impl MigrationStep for DeleteRows {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        if self.conditions.is_empty() && !self.allow_full_table_delete {
+            return Err(MigrationError::UnboundedDelete {
+                table: table.to_string(),
+            });
+        }
+        Ok(dialect.delete_rows(table, &self.conditions))
+    }
+
+    fn generate_parameterized(
+        &self,
+        table: &str,
+        dialect: &dyn Dialect,
+    ) -> Result<Option<(String, Vec<Param>)>, MigrationError> {
+        self.generate_sql(table, dialect)?;
+        Ok(Some(
+            dialect.delete_rows_parameterized(table, &self.conditions),
+        ))
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        let mut effects = Vec::new();
+        for condition in &self.conditions {
+            let effect = ColumnEffect::Reference(condition.column.clone());
+            if !effects.contains(&effect) {
+                effects.push(effect);
+            }
+        }
+        effects
+    }
+
+    fn risk_profile(&self) -> RiskProfile {
+        RiskProfile {
+            destructive: true,
+            requires_rewrite: false,
+            lock_level: LockLevel::Row,
+        }
+    }
+}
+
+/// Copies `source_column` into `target_column` on every row, typically to
+/// backfill a freshly added column. `transform` replaces the plain copy with
+/// a SQL expression over the source column, written as-is, e.g.
+/// `LOWER(email)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CopyColumnData {
+    pub source_column: String,
+    pub target_column: String,
+    pub transform: Option<String>,
+}
+
+impl CopyColumnData {
+    fn value(&self) -> UpdateValue {
+        match &self.transform {
+            Some(expression) => UpdateValue::Fixed(expression.clone()),
+            None => UpdateValue::Column(self.source_column.clone()),
+        }
+    }
+}
+
+impl MigrationStep for CopyColumnData {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(dialect.update_column_data(table, &self.target_column, &self.value(), &[]))
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        vec![
+            ColumnEffect::Reference(self.target_column.clone()),
+            ColumnEffect::Reference(self.source_column.clone()),
+        ]
+    }
+
+    fn risk_profile(&self) -> RiskProfile {
+        RiskProfile {
+            destructive: true,
+            requires_rewrite: false,
+            lock_level: LockLevel::Row,
+        }
+    }
+}
+
+/// Inserts literal rows, e.g. seed data. Values are given in the order of
+/// `columns`; column references cannot be inserted.
+///
+/// With `key_columns` set the insert can be rolled back by deleting exactly
+/// the inserted keys. `on_conflict_do_nothing` skips rows that would violate
+/// a unique constraint, so the insert can be re-run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InsertRows {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<UpdateValue>>,
+    pub key_columns: Vec<String>,
+    pub on_conflict_do_nothing: bool,
+}
+
+impl InsertRows {
+    /// The rows as rendered values, rejecting column references and rows
+    /// that do not have one value per column.
+    fn literal_rows(&self, dialect: &dyn Dialect) -> Result<Vec<Vec<String>>, MigrationError> {
+        self.rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                if row.len() != self.columns.len() {
+                    return Err(MigrationError::RowLengthMismatch {
+                        row: i + 1,
+                        columns: self.columns.len(),
+                        values: row.len(),
+                    });
+                }
+                row.iter()
+                    .map(|value| match value {
+                        UpdateValue::Fixed(_) | UpdateValue::Literal(_) => {
+                            Ok(dialect.render_value(value))
+                        }
+                        UpdateValue::Column(column) => {
+                            Err(MigrationError::ColumnReferenceInValues {
+                                column: column.clone(),
+                            })
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl MigrationStep for InsertRows {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(dialect.insert_rows(
+            table,
+            &self.columns,
+            &self.literal_rows(dialect)?,
+            self.on_conflict_do_nothing,
+        ))
+    }
+
+    fn rollback_sql(&self, table: &str, dialect: &dyn Dialect) -> Option<String> {
+        if self.key_columns.is_empty() {
+            return None;
+        }
+        let positions = self
+            .key_columns
+            .iter()
+            .map(|key| self.columns.iter().position(|column| column == key))
+            .collect::<Option<Vec<usize>>>()?;
+        let rows = self.literal_rows(dialect).ok()?;
+        let keys: Vec<Vec<String>> = rows
+            .iter()
+            .map(|row| positions.iter().map(|&i| row[i].clone()).collect())
+            .collect();
+        Some(dialect.delete_rows_by_key(table, &self.key_columns, &keys))
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        self.columns
+            .iter()
+            .map(|column| ColumnEffect::Reference(column.clone()))
+            .collect()
+    }
+
+    fn ignored_options(&self, dialect: &dyn Dialect) -> Vec<&'static str> {
+        if self.on_conflict_do_nothing && !dialect.supports(Feature::InsertIgnore) {
+            vec!["on_conflict_do_nothing"]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn risk_profile(&self) -> RiskProfile {
+        RiskProfile {
+            lock_level: LockLevel::Row,
+            ..RiskProfile::default()
+        }
+    }
+}
+
+/// Computes new column values with an external script.
+///
+/// The script is invoked as `<interpreter> <script> <select query>`. It is
+/// expected to run the query, and print one `id:value` pair per line for each
+/// row that should be updated, with the value as SQL, e.g. `'quoted'`.
+///
+/// By default every pair becomes its own `UPDATE`. With `batch_size` set, the
+/// pairs are grouped into multi-row updates of at most that many rows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExternalProcessColumnData {
+    pub column: String,
+    pub id_column: String,
+    pub interpreter: String,
+    pub script: String,
+    pub batch_size: Option<usize>,
+}
+
+impl MigrationStep for ExternalProcessColumnData {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(joined(&self.statements(table, dialect)?))
+    }
+
+    fn statements(
+        &self,
+        table: &str,
+        dialect: &dyn Dialect,
+    ) -> Result<Vec<Statement>, MigrationError> {
+        let query = dialect.select_column_data(table, &self.id_column, &self.column);
+        #[cfg(feature = "logging")]
+        log::info!(
+            "running {} {} for {}.{}",
+            self.interpreter,
+            self.script,
+            table,
+            self.column
+        );
+        let output = Command::new(&self.interpreter)
+            .arg(&self.script)
+            .arg(&query)
+            .output()
+            .map_err(|error| MigrationError::ProcessSpawn {
+                program: self.interpreter.clone(),
+                message: error.to_string(),
+            })?;
+        if !output.status.success() {
+            return Err(MigrationError::ProcessExit {
+                program: self.interpreter.clone(),
+                code: output.status.code(),
+                stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+            });
+        }
+        let stdout =
+            String::from_utf8(output.stdout).map_err(|_| MigrationError::NonUtf8Output {
+                program: self.interpreter.clone(),
+            })?;
+
+        let rows = parse_output(&stdout)?;
+
+        let statements: Vec<String> = match self.batch_size {
+            Some(batch_size) => rows
+                .chunks(batch_size.max(1))
+                .map(|batch| {
+                    dialect.update_column_data_batch(table, &self.id_column, &self.column, batch)
+                })
+                .collect(),
+            None => rows
+                .iter()
+                .map(|(id, value)| {
+                    dialect.update_column_data_by_id(
+                        table,
+                        &self.id_column,
+                        id,
+                        &self.column,
+                        &UpdateValue::Fixed(value.clone()),
+                    )
+                })
+                .collect(),
+        };
+        Ok(of_step(self, table, statements))
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        vec![
+            ColumnEffect::Reference(self.id_column.clone()),
+            ColumnEffect::Reference(self.column.clone()),
+        ]
+    }
+
+    fn risk_profile(&self) -> RiskProfile {
+        RiskProfile {
+            lock_level: LockLevel::Row,
+            ..RiskProfile::default()
+        }
+    }
+}
+
+/// Parses `id:value` lines, skipping blank ones.
+fn parse_output(stdout: &str) -> Result<Vec<(String, String)>, MigrationError> {
+    stdout
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| match line.split_once(':') {
+            Some((id, value)) => Ok((id.trim().to_string(), value.trim().to_string())),
+            None => Err(MigrationError::MalformedOutput {
+                line: index + 1,
+                content: line.to_string(),
+            }),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_type::{Column, DataType};
+    use crate::dialect::{
+        MariaDbDialect, MssqlDialect, MySqlDialect, PostgresDialect, SqliteDialect,
+    };
+    use crate::migration::{AddColumn, DropColumn, Migration};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    /// Writes a shell script printing `output` and returns its path.
+    fn script_printing(name: &str, output: &str) -> PathBuf {
+        script_running(name, &format!("cat <<'EOF'\n{}EOF\n", output))
+    }
+
+    /// Writes a shell script with the given body and returns its path.
+    fn script_running(name: &str, body: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("drift-{}-{}.sh", name, std::process::id()));
+        fs::write(&path, body).unwrap();
+        path
+    }
+
+    fn external_process(script: &Path, batch_size: Option<usize>) -> ExternalProcessColumnData {
+        ExternalProcessColumnData {
+            column: "slug".to_string(),
+            id_column: "id".to_string(),
+            interpreter: "sh".to_string(),
+            script: script.to_string_lossy().into_owned(),
+            batch_size,
+        }
+    }
+
+    #[test]
+    fn updates_each_row_by_default() {
+        let script = script_printing("per-row", "1:'a'\n2:'b'\n");
+        let sql = external_process(&script, None)
+            .generate_sql("posts", &MySqlDialect::new())
+            .unwrap();
+        fs::remove_file(script).unwrap();
+
+        assert_eq!(
+            sql,
+            "UPDATE posts SET slug = 'a' WHERE id = 1;\n\
+             UPDATE posts SET slug = 'b' WHERE id = 2;"
+        );
+    }
+
+    #[test]
+    fn chunks_rows_into_batches() {
+        let script = script_printing("batched", "1:'a'\n2:'b'\n3:'c'\n");
+        let sql = external_process(&script, Some(2))
+            .generate_sql("posts", &MySqlDialect::new())
+            .unwrap();
+        fs::remove_file(script).unwrap();
+
+        assert_eq!(
+            sql,
+            "UPDATE posts SET slug = CASE id WHEN 1 THEN 'a' WHEN 2 THEN 'b' END WHERE id IN (1, 2);\n\
+             UPDATE posts SET slug = CASE id WHEN 3 THEN 'c' END WHERE id IN (3);"
+        );
+    }
+    #[test]
+    fn reports_the_malformed_output_line() {
+        let script = script_printing("malformed", "1:'a'\nnot a pair\n");
+        let result = external_process(&script, None).generate_sql("posts", &MySqlDialect::new());
+        fs::remove_file(script).unwrap();
+
+        assert_eq!(
+            result,
+            Err(MigrationError::MalformedOutput {
+                line: 2,
+                content: "not a pair".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn reports_an_interpreter_that_cannot_be_started() {
+        let mut process = external_process(Path::new("slugify.sh"), None);
+        process.interpreter = "drift-missing-interpreter".to_string();
+        let result = process.generate_sql("posts", &MySqlDialect::new());
+
+        assert!(matches!(
+            result,
+            Err(MigrationError::ProcessSpawn { program, .. })
+                if program == "drift-missing-interpreter"
+        ));
+    }
+
+    #[test]
+    fn reports_a_failing_script_instead_of_its_output() {
+        let script = script_running("failing", "echo 1:1\necho 'no such table' >&2\nexit 3\n");
+        let result = external_process(&script, None).generate_sql("posts", &MySqlDialect::new());
+        fs::remove_file(script).unwrap();
+
+        assert_eq!(
+            result,
+            Err(MigrationError::ProcessExit {
+                program: "sh".to_string(),
+                code: Some(3),
+                stderr: "no such table\n".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn runs_the_script_once_when_parameterized() {
+        let runs = std::env::temp_dir().join(format!("drift-runs-{}", std::process::id()));
+        let script = script_running(
+            "counted",
+            &format!("echo run >> '{}'\necho \"1:'a'\"\n", runs.display()),
+        );
+        let mut migration = Migration::new("posts", Box::new(MySqlDialect::new()));
+        migration.add_operation(external_process(&script, None));
+        let statements = migration.generate_parameterized().unwrap();
+        let log = fs::read_to_string(&runs).unwrap();
+        fs::remove_file(script).unwrap();
+        fs::remove_file(runs).unwrap();
+
+        assert_eq!(
+            statements,
+            [(
+                "UPDATE posts SET slug = 'a' WHERE id = 1;".to_string(),
+                Vec::new()
+            )]
+        );
+        assert_eq!(log, "run\n");
+    }
+
+    #[test]
+    fn reports_output_that_is_not_utf8() {
+        let script = script_running("binary", "printf '1:\\377\\n'\n");
+        let result = external_process(&script, None).generate_sql("posts", &MySqlDialect::new());
+        fs::remove_file(script).unwrap();
+
+        assert_eq!(
+            result,
+            Err(MigrationError::NonUtf8Output {
+                program: "sh".to_string(),
+            })
+        );
+    }
+    fn fixed(value: &str) -> UpdateValue {
+        UpdateValue::Fixed(value.to_string())
+    }
+
+    fn seed_roles() -> InsertRows {
+        InsertRows {
+            columns: vec!["id".to_string(), "name".to_string()],
+            rows: vec![
+                vec![fixed("1"), fixed("'admin'")],
+                vec![fixed("2"), fixed("'editor'")],
+            ],
+            key_columns: vec!["id".to_string()],
+            on_conflict_do_nothing: false,
+        }
+    }
+
+    #[test]
+    fn inserts_rows() {
+        assert_eq!(
+            seed_roles()
+                .generate_sql("roles", &MySqlDialect::new())
+                .unwrap(),
+            "INSERT INTO roles (id, name) VALUES (1, 'admin'), (2, 'editor');"
+        );
+    }
+
+    #[test]
+    fn keeps_escaped_literals_intact() {
+        let insert = InsertRows {
+            rows: vec![vec![fixed("3"), fixed("'O''Brien, Jr.'")]],
+            ..seed_roles()
+        };
+        assert_eq!(
+            insert
+                .generate_sql("roles", &PostgresDialect::new())
+                .unwrap(),
+            "INSERT INTO roles (id, name) VALUES (3, 'O''Brien, Jr.');"
+        );
+    }
+
+    #[test]
+    fn skips_conflicting_rows_per_dialect() {
+        let insert = InsertRows {
+            on_conflict_do_nothing: true,
+            ..seed_roles()
+        };
+        assert_eq!(
+            insert.generate_sql("roles", &PostgresDialect::new()).unwrap(),
+            "INSERT INTO roles (id, name) VALUES (1, 'admin'), (2, 'editor') ON CONFLICT DO NOTHING;"
+        );
+        assert_eq!(
+            insert.generate_sql("roles", &MySqlDialect::new()).unwrap(),
+            "INSERT IGNORE INTO roles (id, name) VALUES (1, 'admin'), (2, 'editor');"
+        );
+    }
+
+    #[test]
+    fn inserts_single_row_once_per_dialect() {
+        let insert = InsertRows {
+            rows: vec![vec![fixed("1"), fixed("'admin'")]],
+            on_conflict_do_nothing: true,
+            ..seed_roles()
+        };
+        assert_eq!(
+            insert
+                .generate_sql("roles", &PostgresDialect::new())
+                .unwrap(),
+            "INSERT INTO roles (id, name) VALUES (1, 'admin') ON CONFLICT DO NOTHING;"
+        );
+        assert_eq!(
+            insert
+                .generate_sql("roles", &MariaDbDialect::new())
+                .unwrap(),
+            "INSERT IGNORE INTO roles (id, name) VALUES (1, 'admin');"
+        );
+        assert_eq!(
+            insert.generate_sql("roles", &SqliteDialect::new()).unwrap(),
+            "INSERT OR IGNORE INTO roles (id, name) VALUES (1, 'admin');"
+        );
+        assert_eq!(
+            insert.ignored_options(&MssqlDialect::new()),
+            vec!["on_conflict_do_nothing"]
+        );
+    }
+
+    #[test]
+    fn rejects_column_references_in_values() {
+        let insert = InsertRows {
+            rows: vec![vec![fixed("3"), UpdateValue::Column("title".to_string())]],
+            ..seed_roles()
+        };
+        assert_eq!(
+            insert.generate_sql("roles", &MySqlDialect::new()),
+            Err(MigrationError::ColumnReferenceInValues {
+                column: "title".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_rows_without_a_value_per_column() {
+        let insert = InsertRows {
+            rows: vec![vec![fixed("1"), fixed("'admin'")], vec![fixed("2")]],
+            key_columns: vec!["name".to_string()],
+            ..seed_roles()
+        };
+        assert_eq!(
+            insert.generate_sql("roles", &MySqlDialect::new()),
+            Err(MigrationError::RowLengthMismatch {
+                row: 2,
+                columns: 2,
+                values: 1,
+            })
+        );
+        assert_eq!(insert.rollback_sql("roles", &MySqlDialect::new()), None);
+    }
+
+    #[test]
+    fn rolls_back_insert_by_deleting_inserted_keys() {
+        assert_eq!(
+            seed_roles()
+                .rollback_sql("roles", &MySqlDialect::new())
+                .unwrap(),
+            "DELETE FROM roles WHERE id IN (1, 2);"
+        );
+
+        let composite = InsertRows {
+            key_columns: vec!["id".to_string(), "name".to_string()],
+            ..seed_roles()
+        };
+        assert_eq!(
+            composite
+                .rollback_sql("roles", &MySqlDialect::new())
+                .unwrap(),
+            "DELETE FROM roles WHERE (id, name) IN ((1, 'admin'), (2, 'editor'));"
+        );
+    }
+
+    #[test]
+    fn insert_without_keys_is_irreversible() {
+        let unkeyed = InsertRows {
+            key_columns: Vec::new(),
+            ..seed_roles()
+        };
+        assert_eq!(unkeyed.rollback_sql("roles", &MySqlDialect::new()), None);
+
+        let missing_key = InsertRows {
+            key_columns: vec!["slug".to_string()],
+            ..seed_roles()
+        };
+        assert_eq!(
+            missing_key.rollback_sql("roles", &MySqlDialect::new()),
+            None
+        );
+    }
+    fn update_where(operator: Operator) -> UpdateColumnData {
+        UpdateColumnData {
+            column: "status".to_string(),
+            value: UpdateValue::Fixed("'archived'".to_string()),
+            conditions: vec![WhereCondition {
+                column: "deleted_by".to_string(),
+                operator,
+                value: UpdateValue::Fixed("NULL".to_string()),
+            }],
+        }
+    }
+
+    #[test]
+    fn renders_null_safe_equality_per_dialect() {
+        let update = update_where(Operator::NullSafeEq);
+        assert_eq!(
+            update
+                .generate_sql("posts", &PostgresDialect::new())
+                .unwrap(),
+            "UPDATE posts SET status = 'archived' WHERE deleted_by IS NOT DISTINCT FROM NULL;"
+        );
+        assert_eq!(
+            update.generate_sql("posts", &MySqlDialect::new()).unwrap(),
+            "UPDATE posts SET status = 'archived' WHERE deleted_by <=> NULL;"
+        );
+    }
+
+    #[test]
+    fn renders_case_insensitive_like_per_dialect() {
+        let mut update = update_where(Operator::ILike);
+        update.conditions[0].value = UpdateValue::Fixed("'%bot%'".to_string());
+        assert_eq!(
+            update
+                .generate_sql("posts", &PostgresDialect::new())
+                .unwrap(),
+            "UPDATE posts SET status = 'archived' WHERE deleted_by ILIKE '%bot%';"
+        );
+        assert_eq!(
+            update.generate_sql("posts", &MySqlDialect::new()).unwrap(),
+            "UPDATE posts SET status = 'archived' WHERE LOWER(deleted_by) LIKE LOWER('%bot%');"
+        );
+    }
+    #[test]
+    fn copies_one_column_into_another() {
+        let copy = CopyColumnData {
+            source_column: "email".to_string(),
+            target_column: "login".to_string(),
+            transform: None,
+        };
+        assert_eq!(
+            copy.generate_sql("users", &PostgresDialect::new()).unwrap(),
+            "UPDATE users SET login = email;"
+        );
+    }
+
+    #[test]
+    fn applies_the_transform_while_copying() {
+        let copy = CopyColumnData {
+            source_column: "country".to_string(),
+            target_column: "country_code".to_string(),
+            transform: Some("UPPER(country)".to_string()),
+        };
+        assert_eq!(
+            copy.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "UPDATE users SET country_code = UPPER(country);"
+        );
+    }
+
+    #[test]
+    fn copies_between_adding_and_dropping_a_column() {
+        let dialects: [Box<dyn Dialect>; 2] = [
+            Box::new(PostgresDialect::new()),
+            Box::new(MySqlDialect::new()),
+        ];
+        for dialect in dialects {
+            let mut migration = Migration::new("users", dialect);
+            migration
+                .add_operation(AddColumn {
+                    column: Column {
+                        name: "email_normalized".to_string(),
+                        data_type: DataType::Varchar(255),
+                        nullable: true,
+                        auto_increment: false,
+                        generated: None,
+                        collation: None,
+                    },
+                    position: None,
+                    if_not_exists: false,
+                })
+                .add_operation(CopyColumnData {
+                    source_column: "email".to_string(),
+                    target_column: "email_normalized".to_string(),
+                    transform: Some("LOWER(email)".to_string()),
+                })
+                .add_operation(DropColumn {
+                    column: "email".to_string(),
+                    if_exists: false,
+                    cascade: false,
+                });
+
+            assert_eq!(migration.validate(), Ok(()));
+            assert_eq!(
+                migration.generate_sql().unwrap(),
+                vec![
+                    "ALTER TABLE users ADD COLUMN email_normalized VARCHAR(255);",
+                    "UPDATE users SET email_normalized = LOWER(email);",
+                    "ALTER TABLE users DROP COLUMN email;",
+                ]
+            );
+        }
+    }
+
+    fn condition(column: &str, operator: Operator, value: &str) -> WhereCondition {
+        WhereCondition {
+            column: column.to_string(),
+            operator,
+            value: UpdateValue::Fixed(value.to_string()),
+        }
+    }
+
+    #[test]
+    fn deletes_rows_matching_a_condition() {
+        let delete = DeleteRows {
+            conditions: vec![condition("status", Operator::Eq, "'deprecated'")],
+            allow_full_table_delete: false,
+        };
+        assert_eq!(
+            delete
+                .generate_sql("accounts", &PostgresDialect::new())
+                .unwrap(),
+            "DELETE FROM accounts WHERE status = 'deprecated';"
+        );
+    }
+
+    #[test]
+    fn ands_delete_conditions_together() {
+        let delete = DeleteRows {
+            conditions: vec![
+                condition("status", Operator::Eq, "'deprecated'"),
+                condition("deleted_by", Operator::NullSafeEq, "NULL"),
+            ],
+            allow_full_table_delete: false,
+        };
+        assert_eq!(
+            delete
+                .generate_sql("accounts", &MySqlDialect::new())
+                .unwrap(),
+            "DELETE FROM accounts WHERE status = 'deprecated' AND deleted_by <=> NULL;"
+        );
+    }
+
+    #[test]
+    fn full_table_delete_must_be_allowed() {
+        let mut delete = DeleteRows {
+            conditions: Vec::new(),
+            allow_full_table_delete: false,
+        };
+        assert_eq!(
+            delete.generate_sql("accounts", &PostgresDialect::new()),
+            Err(MigrationError::UnboundedDelete {
+                table: "accounts".to_string()
+            })
+        );
+
+        delete.allow_full_table_delete = true;
+        assert_eq!(
+            delete
+                .generate_sql("accounts", &PostgresDialect::new())
+                .unwrap(),
+            "DELETE FROM accounts;"
+        );
+    }
+    #[test]
+    fn quotes_and_escapes_literal_values() {
+        let update = |value: &str| UpdateColumnData {
+            column: "status".to_string(),
+            value: UpdateValue::Literal(value.to_string()),
+            conditions: vec![WhereCondition {
+                column: "note".to_string(),
+                operator: Operator::Eq,
+                value: UpdateValue::Literal(r"it's \done".to_string()),
+            }],
+        };
+        let postgres = PostgresDialect::new();
+        let mysql = MySqlDialect::new();
+
+        assert_eq!(
+            update("active").generate_sql("orders", &postgres).unwrap(),
+            r"UPDATE orders SET status = 'active' WHERE note = 'it''s \done';"
+        );
+        assert_eq!(
+            update("active").generate_sql("orders", &mysql).unwrap(),
+            r"UPDATE orders SET status = 'active' WHERE note = 'it''s \\done';"
+        );
+        for dialect in [&postgres as &dyn Dialect, &mysql] {
+            for (value, quoted) in [
+                ("'; DROP TABLE orders; --", "'''; DROP TABLE orders; --'"),
+                ("größe ✓", "'größe ✓'"),
+                ("", "''"),
+            ] {
+                assert_eq!(
+                    dialect.render_value(&UpdateValue::Literal(value.to_string())),
+                    quoted
+                );
+            }
+        }
+        assert_eq!(
+            mysql.update_column_data_by_id(
+                "orders",
+                "id",
+                "7",
+                "status",
+                &UpdateValue::Literal("on hold".to_string()),
+            ),
+            "UPDATE orders SET status = 'on hold' WHERE id = 7;"
+        );
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use super::*;
+    use serde::de::DeserializeOwned;
+    use std::fmt::Debug;
+
+    fn assert_round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: T) {
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(serde_json::from_str::<T>(&json).unwrap(), value);
+    }
+
+    #[test]
+    fn update_column_data_round_trips() {
+        assert_round_trip(UpdateColumnData {
+            column: "status".to_string(),
+            value: UpdateValue::Fixed("'active'".to_string()),
+            conditions: vec![
+                WhereCondition {
+                    column: "status".to_string(),
+                    operator: Operator::Eq,
+                    value: UpdateValue::Fixed("'pending'".to_string()),
+                },
+                WhereCondition {
+                    column: "created_at".to_string(),
+                    operator: Operator::Lt,
+                    value: UpdateValue::Fixed("'2024-01-01'".to_string()),
+                },
+            ],
+        });
+        assert_round_trip(UpdateColumnData {
+            column: "display_name".to_string(),
+            value: UpdateValue::Column("username".to_string()),
+            conditions: Vec::new(),
+        });
+    }
+
+    #[test]
+    fn external_process_column_data_round_trips() {
+        assert_round_trip(ExternalProcessColumnData {
+            column: "slug".to_string(),
+            id_column: "id".to_string(),
+            interpreter: "python3".to_string(),
+            script: "scripts/slugify.py".to_string(),
+            batch_size: Some(500),
+        });
+    }
+
+    #[test]
+    fn operators_round_trip() {
+        assert_round_trip(vec![
+            Operator::Eq,
+            Operator::NotEq,
+            Operator::Gt,
+            Operator::Gte,
+            Operator::Lt,
+            Operator::Lte,
+            Operator::Like,
+            Operator::NullSafeEq,
+            Operator::ILike,
+        ]);
+    }
+    #[test]
+    fn insert_rows_round_trips() {
+        assert_round_trip(InsertRows {
+            columns: vec!["id".to_string(), "name".to_string()],
+            rows: vec![vec![
+                UpdateValue::Fixed("1".to_string()),
+                UpdateValue::Fixed("'admin'".to_string()),
+            ]],
+            key_columns: vec!["id".to_string()],
+            on_conflict_do_nothing: true,
+        });
+    }
+
+    #[test]
+    fn copy_column_data_round_trips() {
+        assert_round_trip(CopyColumnData {
+            source_column: "country".to_string(),
+            target_column: "country_code".to_string(),
+            transform: Some("UPPER(country)".to_string()),
+        });
+    }
+
+    #[test]
+    fn delete_rows_round_trips() {
+        assert_round_trip(DeleteRows {
+            conditions: vec![WhereCondition {
+                column: "status".to_string(),
+                operator: Operator::Eq,
+                value: UpdateValue::Fixed("'deprecated'".to_string()),
+            }],
+            allow_full_table_delete: false,
+        });
+    }
+}
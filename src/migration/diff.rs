@@ -0,0 +1,156 @@
+use super::{AddColumn, ChangeColumnType, DropColumn, MigrationStep};
+use crate::data_type::{Column, ColumnOptions};
+
+/// The steps turning the `current` columns of a table into the `desired`
+/// ones: new columns are added, changed ones get a [`ChangeColumnType`] and
+/// missing ones are dropped last, in that order.
+///
+/// Columns are matched by name and compared by type and nullability only.
+/// A renamed column cannot be told apart from a dropped one, so it is
+/// dropped and added again, losing its data.
+pub fn diff_columns(current: &[Column], desired: &[Column]) -> Vec<Box<dyn MigrationStep>> {
+    let mut steps: Vec<Box<dyn MigrationStep>> = Vec::new();
+    for column in desired {
+        if find(current, &column.name).is_none() {
+            steps.push(Box::new(AddColumn {
+                column: column.clone(),
+                position: None,
+                if_not_exists: false,
+            }));
+        }
+    }
+    for column in desired {
+        let Some(existing) = find(current, &column.name) else {
+            continue;
+        };
+        if existing.data_type != column.data_type || existing.nullable != column.nullable {
+            // MySQL restates the whole column, so nullability is always
+            // given rather than only when it changes.
+            steps.push(Box::new(ChangeColumnType {
+                column: column.name.clone(),
+                data_type: column.data_type.clone(),
+                options: options(column),
+                previous: Some((existing.data_type.clone(), options(existing))),
+                position: None,
+            }));
+        }
+    }
+    for column in current {
+        if find(desired, &column.name).is_none() {
+            steps.push(Box::new(DropColumn {
+                column: column.name.clone(),
+                if_exists: false,
+                cascade: false,
+            }));
+        }
+    }
+    steps
+}
+
+fn find<'a>(columns: &'a [Column], name: &str) -> Option<&'a Column> {
+    columns.iter().find(|column| column.name == name)
+}
+
+fn options(column: &Column) -> ColumnOptions {
+    ColumnOptions {
+        nullable: Some(column.nullable),
+        collation: column.collation.clone(),
+        ..ColumnOptions::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_type::DataType;
+    use crate::dialect::{MySqlDialect, PostgresDialect};
+    use crate::migration::Migration;
+
+    fn column(name: &str, data_type: DataType, nullable: bool) -> Column {
+        Column {
+            name: name.to_string(),
+            data_type,
+            nullable,
+            auto_increment: false,
+            generated: None,
+            collation: None,
+        }
+    }
+
+    fn users() -> Vec<Column> {
+        vec![
+            column("id", DataType::BigInt, false),
+            column("email", DataType::Varchar(255), false),
+        ]
+    }
+
+    fn sql(steps: Vec<Box<dyn MigrationStep>>) -> Vec<String> {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration.extend_operations(steps);
+        migration
+            .generate_sql()
+            .unwrap()
+            .into_iter()
+            .map(|statement| statement.sql)
+            .collect()
+    }
+
+    #[test]
+    fn matching_columns_need_nothing() {
+        assert!(diff_columns(&users(), &users()).is_empty());
+    }
+
+    #[test]
+    fn adds_missing_columns() {
+        let mut desired = users();
+        desired.push(column("bio", DataType::Text, true));
+        assert_eq!(
+            sql(diff_columns(&users(), &desired)),
+            vec!["ALTER TABLE users ADD COLUMN bio TEXT;"]
+        );
+    }
+
+    #[test]
+    fn drops_extra_columns() {
+        assert_eq!(
+            sql(diff_columns(&users(), &users()[..1])),
+            vec!["ALTER TABLE users DROP COLUMN email;"]
+        );
+    }
+
+    #[test]
+    fn changes_mismatched_types() {
+        let desired = vec![
+            column("id", DataType::BigInt, false),
+            column("email", DataType::Text, true),
+        ];
+        let steps = diff_columns(&users(), &desired);
+        assert_eq!(
+            steps[0]
+                .generate_sql("users", &MySqlDialect::new())
+                .unwrap(),
+            "ALTER TABLE users MODIFY COLUMN email TEXT NULL;"
+        );
+        assert_eq!(
+            steps[0]
+                .rollback_sql("users", &MySqlDialect::new())
+                .unwrap(),
+            "ALTER TABLE users MODIFY COLUMN email VARCHAR(255) NOT NULL;"
+        );
+    }
+
+    #[test]
+    fn treats_renames_as_drop_and_add() {
+        let desired = vec![
+            column("id", DataType::BigInt, false),
+            column("email_address", DataType::Varchar(255), false),
+        ];
+        assert_eq!(
+            sql(diff_columns(&users(), &desired)),
+            vec![
+                "ALTER TABLE users ADD COLUMN email_address VARCHAR(255) NOT NULL;",
+                "ALTER TABLE users DROP COLUMN email;",
+            ]
+        );
+    }
+}
@@ -0,0 +1,188 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::MigrationStep;
+use crate::data_type::Column;
+use crate::dialect::Dialect;
+use crate::error::MigrationError;
+
+/// Creates a named enum type. Only Postgres has standalone enum types; the
+/// migration's table is ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CreateEnumType {
+    pub name: String,
+    pub variants: Vec<String>,
+}
+
+impl MigrationStep for CreateEnumType {
+    fn generate_sql(&self, _table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        dialect.create_enum_type(&self.name, &self.variants)
+    }
+}
+
+/// Adds a value to an existing enum type.
+///
+/// MySQL enums belong to a column, so there the column is redefined with
+/// its full new variant list and `column` is required. Postgres before 12
+/// cannot add enum values inside a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AddEnumValue {
+    pub type_name: String,
+    pub value: String,
+    /// Where to place the value. Without it, it goes last.
+    pub position: Option<EnumValuePosition>,
+    /// The column using the type, with every variant including `value`.
+    /// Postgres ignores it.
+    pub column: Option<Column>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EnumValuePosition {
+    Before(String),
+    After(String),
+}
+
+impl MigrationStep for AddEnumValue {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        dialect.add_enum_value(table, self)
+    }
+
+    fn transactional(&self) -> bool {
+        false
+    }
+}
+
+/// Renames a value of an existing enum type. Only Postgres supports it; the
+/// migration's table is ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RenameEnumValue {
+    pub type_name: String,
+    pub old_value: String,
+    pub new_value: String,
+}
+
+impl MigrationStep for RenameEnumValue {
+    fn generate_sql(&self, _table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        dialect.rename_enum_value(&self.type_name, &self.old_value, &self.new_value)
+    }
+
+    fn rollback_sql(&self, _table: &str, dialect: &dyn Dialect) -> Option<String> {
+        dialect
+            .rename_enum_value(&self.type_name, &self.new_value, &self.old_value)
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_type::DataType;
+    use crate::dialect::{MariaDbDialect, MySqlDialect, PostgresDialect};
+
+    fn variants(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    fn add_archived(column: Option<Column>) -> AddEnumValue {
+        AddEnumValue {
+            type_name: "order_status".to_string(),
+            value: "archived".to_string(),
+            position: Some(EnumValuePosition::After("shipped".to_string())),
+            column,
+        }
+    }
+
+    #[test]
+    fn creates_postgres_enum_type() {
+        let create = CreateEnumType {
+            name: "order_status".to_string(),
+            variants: variants(&["pending", "shipped"]),
+        };
+        assert_eq!(
+            create
+                .generate_sql("orders", &PostgresDialect::new())
+                .unwrap(),
+            "CREATE TYPE order_status AS ENUM ('pending', 'shipped');"
+        );
+        assert_eq!(
+            create.generate_sql("orders", &MySqlDialect::new()),
+            Err(MigrationError::unsupported("mysql", "CREATE TYPE"))
+        );
+    }
+
+    #[test]
+    fn adds_postgres_enum_value() {
+        let add = add_archived(None);
+        assert_eq!(
+            add.generate_sql("orders", &PostgresDialect::new()).unwrap(),
+            "ALTER TYPE order_status ADD VALUE 'archived' AFTER 'shipped';"
+        );
+        assert!(!add.transactional());
+
+        let add = AddEnumValue {
+            position: Some(EnumValuePosition::Before("pending".to_string())),
+            ..add
+        };
+        assert_eq!(
+            add.generate_sql("orders", &PostgresDialect::new()).unwrap(),
+            "ALTER TYPE order_status ADD VALUE 'archived' BEFORE 'pending';"
+        );
+    }
+
+    #[test]
+    fn redefines_mysql_enum_column() {
+        let add = add_archived(Some(Column {
+            name: "status".to_string(),
+            data_type: DataType::Enum {
+                name: "order_status".to_string(),
+                variants: variants(&["pending", "shipped", "archived"]),
+            },
+            nullable: false,
+            auto_increment: false,
+            generated: None,
+            collation: None,
+        }));
+        let sql = "ALTER TABLE orders MODIFY COLUMN status ENUM('pending','shipped','archived') NOT NULL;";
+        assert_eq!(
+            add.generate_sql("orders", &MySqlDialect::new()).unwrap(),
+            sql
+        );
+        assert_eq!(
+            add.generate_sql("orders", &MariaDbDialect::new()).unwrap(),
+            sql
+        );
+    }
+
+    #[test]
+    fn mysql_needs_the_enum_column() {
+        assert_eq!(
+            add_archived(None).generate_sql("orders", &MySqlDialect::new()),
+            Err(MigrationError::unsupported(
+                "mysql",
+                "ALTER TYPE ADD VALUE without the column definition"
+            ))
+        );
+    }
+
+    #[test]
+    fn renames_postgres_enum_value() {
+        let rename = RenameEnumValue {
+            type_name: "order_status".to_string(),
+            old_value: "shipped".to_string(),
+            new_value: "dispatched".to_string(),
+        };
+        let dialect = PostgresDialect::new();
+        assert_eq!(
+            rename.generate_sql("orders", &dialect).unwrap(),
+            "ALTER TYPE order_status RENAME VALUE 'shipped' TO 'dispatched';"
+        );
+        assert_eq!(
+            rename.rollback_sql("orders", &dialect).unwrap(),
+            "ALTER TYPE order_status RENAME VALUE 'dispatched' TO 'shipped';"
+        );
+    }
+}
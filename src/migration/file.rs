@@ -0,0 +1,291 @@
+use std::any::Any;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    AddCheckConstraint, AddColumn, AddColumnSafe, AddEnumValue, AddForeignKey, AddIdentity,
+    AddPrimaryKey, AddUniqueConstraint, AlterColumnCollation, AttachPartition, ChangeColumnType,
+    CopyColumnData, CreateEnumType, CreateFunction, CreateIndex, CreateMaterializedView,
+    CreatePartition, CreateSchema, CreateSequence, CreateTable, CreateView, DeleteRows,
+    DetachPartition, DropCheckConstraint, DropColumn, DropColumnDefault, DropFunction,
+    DropIdentity, DropIndex, DropMaterializedView, DropNotNull, DropPartition, DropPrimaryKey,
+    DropSequence, DropTable, DropUniqueConstraint, DropView, ExternalProcessColumnData,
+    GrantPrivileges, InsertRows, MergeColumns, Migration, MigrationStep, RawSql,
+    RefreshMaterializedView, RenameColumn, RenameEnumValue, ReorderColumn, RestartIdentity,
+    RestartSequence, RevokePrivileges, SetColumnDefault, SetNotNull, TableRef, UpdateColumnData,
+};
+use crate::dialect::Dialect;
+use crate::error::MigrationError;
+
+/// A migration as written in a definition file, e.g.
+///
+/// ```yaml
+/// table: users
+/// schema: analytics
+/// version: "20240101"
+/// operations:
+///   - type: DropColumn
+///     column: legacy_flag
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationFile {
+    pub table: String,
+    #[serde(default)]
+    pub schema: Option<String>,
+    pub version: Option<String>,
+    pub name: Option<String>,
+    pub operations: Vec<Operation>,
+}
+
+impl MigrationFile {
+    /// Builds the migration for `dialect`, in file order.
+    pub fn into_migration(self, dialect: Box<dyn Dialect>) -> Migration {
+        let table = TableRef {
+            schema: self.schema,
+            name: self.table,
+        };
+        let mut migration = Migration::new(table, dialect);
+        migration.version = self.version;
+        migration.name = self.name;
+        migration.operations = self
+            .operations
+            .into_iter()
+            .map(Operation::into_step)
+            .collect();
+        migration
+    }
+
+    /// The file describing `migration`. Fails on the first operation that
+    /// has no [`Operation`] variant.
+    pub fn from_migration(migration: &Migration) -> Result<MigrationFile, MigrationError> {
+        let operations = migration
+            .operations
+            .iter()
+            .map(|step| {
+                Operation::from_step(step.as_ref()).ok_or_else(|| MigrationError::Unserializable {
+                    operation: step.operation_name().to_string(),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(MigrationFile {
+            table: migration.table.name.clone(),
+            schema: migration.table.schema.clone(),
+            version: migration.version.clone(),
+            name: migration.name.clone(),
+            operations,
+        })
+    }
+}
+
+impl Migration {
+    /// Writes the table, version, name and operations to `path` as JSON,
+    /// replacing any existing file. The dialect is not saved.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), MigrationError> {
+        let path = path.as_ref();
+        let file = MigrationFile::from_migration(self)?;
+        let mut json =
+            serde_json::to_string_pretty(&file).expect("migration files always serialize to JSON");
+        json.push('\n');
+        fs::write(path, json).map_err(|error| MigrationError::Io {
+            path: path.to_path_buf(),
+            message: error.to_string(),
+        })
+    }
+
+    /// Reads a migration written by [`Migration::save`], generating SQL for
+    /// `dialect`.
+    pub fn load(path: impl AsRef<Path>, dialect: Box<dyn Dialect>) -> Result<Self, MigrationError> {
+        let path = path.as_ref();
+        let invalid = |message: String| MigrationError::InvalidFile {
+            path: path.to_path_buf(),
+            message,
+        };
+        let json = fs::read_to_string(path).map_err(|error| invalid(error.to_string()))?;
+        let file: MigrationFile =
+            serde_json::from_str(&json).map_err(|error| invalid(error.to_string()))?;
+        Ok(file.into_migration(dialect))
+    }
+}
+
+// Lists every built-in operation once, for both the enum and the
+// conversions to and from boxed steps.
+macro_rules! operations {
+    ($($step:ident),* $(,)?) => {
+        /// Any operation, tagged by its type name under `type`.
+        #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+        #[serde(tag = "type")]
+        pub enum Operation {
+            $($step($step),)*
+        }
+
+        impl Operation {
+            pub fn into_step(self) -> Box<dyn MigrationStep> {
+                match self {
+                    $(Operation::$step(step) => Box::new(step),)*
+                }
+            }
+
+            /// The operation `step` was built from, or `None` for steps
+            /// defined outside this crate or added with
+            /// [`Migration::add_operation_on`].
+            pub fn from_step(step: &dyn MigrationStep) -> Option<Operation> {
+                let step = step as &dyn Any;
+                $(
+                    if let Some(step) = step.downcast_ref::<$step>() {
+                        return Some(Operation::$step(step.clone()));
+                    }
+                )*
+                None
+            }
+        }
+    };
+}
+
+operations! {
+    AddColumn,
+    AddColumnSafe,
+    DropColumn,
+    RenameColumn,
+    ChangeColumnType,
+    SetNotNull,
+    DropNotNull,
+    SetColumnDefault,
+    DropColumnDefault,
+    CreateIndex,
+    DropIndex,
+    CreateView,
+    DropView,
+    CreateMaterializedView,
+    RefreshMaterializedView,
+    DropMaterializedView,
+    CreateFunction,
+    DropFunction,
+    AddCheckConstraint,
+    DropCheckConstraint,
+    AddUniqueConstraint,
+    DropUniqueConstraint,
+    AddPrimaryKey,
+    DropPrimaryKey,
+    AddForeignKey,
+    AttachPartition,
+    DetachPartition,
+    CreatePartition,
+    DropPartition,
+    UpdateColumnData,
+    DeleteRows,
+    CopyColumnData,
+    InsertRows,
+    ExternalProcessColumnData,
+    RestartSequence,
+    CreateSequence,
+    DropSequence,
+    CreateEnumType,
+    AddEnumValue,
+    RenameEnumValue,
+    CreateTable,
+    DropTable,
+    GrantPrivileges,
+    RevokePrivileges,
+    AlterColumnCollation,
+    MergeColumns,
+    ReorderColumn,
+    CreateSchema,
+    AddIdentity,
+    DropIdentity,
+    RestartIdentity,
+    RawSql,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{MySqlDialect, PostgresDialect};
+    use crate::migration::TargetNode;
+
+    #[test]
+    fn builds_a_migration_from_json() {
+        let file: MigrationFile = serde_json::from_str(
+            r#"{
+                "table": "users",
+                "version": "20240101",
+                "name": null,
+                "operations": [
+                    {"type": "RenameColumn", "old_name": "phone", "new_name": "contact_number"},
+                    {"type": "DropColumn", "column": "legacy_flag"}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let migration = file.into_migration(Box::new(PostgresDialect::new()));
+        assert_eq!(
+            migration.generate_sql().unwrap(),
+            vec![
+                "ALTER TABLE users RENAME COLUMN phone TO contact_number;",
+                "ALTER TABLE users DROP COLUMN legacy_flag;",
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_operations() {
+        let result = serde_json::from_str::<MigrationFile>(
+            r#"{"table": "users", "version": null, "name": null,
+                "operations": [{"type": "TruncateTable"}]}"#,
+        );
+        assert!(result.is_err());
+    }
+    #[test]
+    fn saves_and_loads_migrations() {
+        let path =
+            std::env::temp_dir().join(format!("drift-migration-{}.json", std::process::id()));
+        let mut migration =
+            Migration::new(("analytics", "users"), Box::new(PostgresDialect::new()))
+                .with_version("20240101");
+        migration.add_operation(DropColumn {
+            column: "legacy_flag".to_string(),
+            if_exists: true,
+            cascade: false,
+        });
+        migration.save(&path).unwrap();
+        let loaded = Migration::load(&path, Box::new(MySqlDialect::new()));
+        fs::remove_file(&path).unwrap();
+
+        let loaded = loaded.unwrap();
+        assert_eq!(loaded.version.as_deref(), Some("20240101"));
+        assert_eq!(
+            loaded.generate_sql().unwrap(),
+            vec!["ALTER TABLE analytics.users DROP COLUMN legacy_flag;"]
+        );
+    }
+
+    #[test]
+    fn cannot_save_steps_without_an_operation() {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration.add_operation_on(
+            TargetNode::PrimaryOnly,
+            DropColumn {
+                column: "legacy_flag".to_string(),
+                if_exists: false,
+                cascade: false,
+            },
+        );
+        assert_eq!(
+            MigrationFile::from_migration(&migration),
+            Err(MigrationError::Unserializable {
+                operation: "DropColumn".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn reports_unreadable_files() {
+        let path = std::env::temp_dir().join("drift-missing-migration.json");
+        assert!(matches!(
+            Migration::load(&path, Box::new(PostgresDialect::new())),
+            Err(MigrationError::InvalidFile { .. })
+        ));
+    }
+}
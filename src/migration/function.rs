@@ -0,0 +1,194 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::statement::{joined, of_step};
+use super::{MigrationStep, Statement};
+use crate::dialect::Dialect;
+use crate::error::MigrationError;
+
+/// Creates a function, or a procedure when nothing is returned. The
+/// migration's table is ignored.
+///
+/// `args` and `body` are raw SQL and are not escaped. MySQL takes the body
+/// after `RETURNS`, so characteristics such as `DETERMINISTIC` go at its
+/// start. The statement never contains client-side `DELIMITER` commands;
+/// send it to the server as a single statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CreateFunction {
+    pub name: String,
+    /// The parameter list without parentheses, e.g. `a integer, b text`.
+    pub args: String,
+    /// The return type. Without it a procedure is created.
+    pub returns: Option<String>,
+    /// e.g. `plpgsql`. MySQL only accepts `sql`.
+    pub language: String,
+    pub body: String,
+    pub or_replace: bool,
+}
+
+impl MigrationStep for CreateFunction {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(joined(&self.statements(table, dialect)?))
+    }
+
+    fn statements(
+        &self,
+        table: &str,
+        dialect: &dyn Dialect,
+    ) -> Result<Vec<Statement>, MigrationError> {
+        Ok(of_step(self, table, dialect.create_function(self)?))
+    }
+
+    fn rollback_sql(&self, _table: &str, dialect: &dyn Dialect) -> Option<String> {
+        // A replaced definition is lost.
+        if self.or_replace {
+            return None;
+        }
+        dialect
+            .drop_function(&DropFunction {
+                name: self.name.clone(),
+                args: Some(self.args.clone()),
+                procedure: self.returns.is_none(),
+                if_exists: false,
+            })
+            .ok()
+    }
+}
+
+/// Drops a function or procedure. The migration's table is ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DropFunction {
+    pub name: String,
+    /// The parameter list, which Postgres needs to pick an overload. MySQL
+    /// ignores it.
+    pub args: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub procedure: bool,
+    pub if_exists: bool,
+}
+
+impl MigrationStep for DropFunction {
+    fn generate_sql(&self, _table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        dialect.drop_function(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{MariaDbDialect, MySqlDialect, PostgresDialect, SqliteDialect};
+
+    fn set_updated_at() -> CreateFunction {
+        CreateFunction {
+            name: "set_updated_at".to_string(),
+            args: String::new(),
+            returns: Some("trigger".to_string()),
+            language: "plpgsql".to_string(),
+            body: "BEGIN\n  NEW.updated_at := now();\n  RETURN NEW;\nEND;".to_string(),
+            or_replace: true,
+        }
+    }
+
+    #[test]
+    fn creates_postgres_function() {
+        assert_eq!(
+            set_updated_at()
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "CREATE OR REPLACE FUNCTION set_updated_at() RETURNS trigger LANGUAGE plpgsql AS $$\nBEGIN\n  NEW.updated_at := now();\n  RETURN NEW;\nEND;\n$$;"
+        );
+    }
+
+    #[test]
+    fn picks_a_dollar_quote_tag_missing_from_the_body() {
+        let function = CreateFunction {
+            name: "greeting".to_string(),
+            args: "name text".to_string(),
+            returns: Some("text".to_string()),
+            language: "plpgsql".to_string(),
+            body: "BEGIN\n  RETURN $$Hello, $$ || name;\nEND;".to_string(),
+            or_replace: false,
+        };
+        let dialect = PostgresDialect::new();
+        assert_eq!(
+            function.generate_sql("users", &dialect).unwrap(),
+            "CREATE FUNCTION greeting(name text) RETURNS text LANGUAGE plpgsql AS $body$\nBEGIN\n  RETURN $$Hello, $$ || name;\nEND;\n$body$;"
+        );
+        assert_eq!(
+            function.rollback_sql("users", &dialect).unwrap(),
+            "DROP FUNCTION greeting(name text);"
+        );
+    }
+
+    #[test]
+    fn creates_mysql_function() {
+        let function = CreateFunction {
+            name: "full_name".to_string(),
+            args: "first VARCHAR(100), last VARCHAR(100)".to_string(),
+            returns: Some("VARCHAR(201)".to_string()),
+            language: "sql".to_string(),
+            body: "DETERMINISTIC RETURN CONCAT(first, ' ', last)".to_string(),
+            or_replace: true,
+        };
+        assert_eq!(
+            function.statements("users", &MySqlDialect::new()).unwrap(),
+            [
+                "DROP FUNCTION IF EXISTS full_name;",
+                "CREATE FUNCTION full_name(first VARCHAR(100), last VARCHAR(100)) RETURNS VARCHAR(201) DETERMINISTIC RETURN CONCAT(first, ' ', last);"
+            ]
+        );
+        assert_eq!(
+            function.generate_sql("users", &MariaDbDialect::new()).unwrap(),
+            "CREATE OR REPLACE FUNCTION full_name(first VARCHAR(100), last VARCHAR(100)) RETURNS VARCHAR(201) DETERMINISTIC RETURN CONCAT(first, ' ', last);"
+        );
+    }
+
+    #[test]
+    fn creates_mysql_procedure() {
+        let procedure = CreateFunction {
+            name: "archive_users".to_string(),
+            args: String::new(),
+            returns: None,
+            language: "SQL".to_string(),
+            body: "BEGIN\n  DELETE FROM users WHERE archived;\nEND".to_string(),
+            or_replace: false,
+        };
+        let dialect = MySqlDialect::new();
+        assert_eq!(
+            procedure.generate_sql("users", &dialect).unwrap(),
+            "CREATE PROCEDURE archive_users() BEGIN\n  DELETE FROM users WHERE archived;\nEND;"
+        );
+        assert_eq!(
+            procedure.rollback_sql("users", &dialect).unwrap(),
+            "DROP PROCEDURE archive_users;"
+        );
+    }
+
+    #[test]
+    fn mysql_rejects_other_languages() {
+        assert_eq!(
+            set_updated_at().generate_sql("users", &MySqlDialect::new()),
+            Err(MigrationError::unsupported("mysql", "LANGUAGE plpgsql"))
+        );
+    }
+
+    #[test]
+    fn drops_function() {
+        let drop = DropFunction {
+            name: "set_updated_at".to_string(),
+            args: None,
+            procedure: false,
+            if_exists: true,
+        };
+        assert_eq!(
+            drop.generate_sql("users", &PostgresDialect::new()).unwrap(),
+            "DROP FUNCTION IF EXISTS set_updated_at;"
+        );
+        assert_eq!(
+            drop.generate_sql("users", &SqliteDialect::new()),
+            Err(MigrationError::unsupported("sqlite", "DROP FUNCTION"))
+        );
+    }
+}
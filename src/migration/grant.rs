@@ -0,0 +1,208 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::MigrationStep;
+use crate::dialect::{Dialect, Feature};
+use crate::error::MigrationError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Privilege {
+    Select,
+    Insert,
+    Update,
+    Delete,
+    All,
+}
+
+impl fmt::Display for Privilege {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Privilege::Select => write!(f, "SELECT"),
+            Privilege::Insert => write!(f, "INSERT"),
+            Privilege::Update => write!(f, "UPDATE"),
+            Privilege::Delete => write!(f, "DELETE"),
+            Privilege::All => write!(f, "ALL"),
+        }
+    }
+}
+
+/// Grants privileges on the migration's table to a role or user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GrantPrivileges {
+    pub privileges: Vec<Privilege>,
+    pub to: String,
+    /// The host part of a MySQL account, as in `'user'@'host'`. Other
+    /// dialects ignore it.
+    pub host: Option<String>,
+    /// Qualifies the table, e.g. a MySQL database or a Postgres schema.
+    pub database: Option<String>,
+}
+
+impl MigrationStep for GrantPrivileges {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(dialect.grant_privileges(
+            &qualified(table, self.database.as_deref()),
+            &self.privileges,
+            &dialect.grantee(&self.to, self.host.as_deref()),
+        ))
+    }
+
+    fn rollback_sql(&self, table: &str, dialect: &dyn Dialect) -> Option<String> {
+        Some(dialect.revoke_privileges(
+            &qualified(table, self.database.as_deref()),
+            &self.privileges,
+            &dialect.grantee(&self.to, self.host.as_deref()),
+        ))
+    }
+
+    fn ignored_options(&self, dialect: &dyn Dialect) -> Vec<&'static str> {
+        ignored_host(self.host.as_deref(), dialect)
+    }
+}
+
+/// Revokes privileges on the migration's table. See [`GrantPrivileges`] for
+/// the fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RevokePrivileges {
+    pub privileges: Vec<Privilege>,
+    pub from: String,
+    pub host: Option<String>,
+    pub database: Option<String>,
+}
+
+impl MigrationStep for RevokePrivileges {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(dialect.revoke_privileges(
+            &qualified(table, self.database.as_deref()),
+            &self.privileges,
+            &dialect.grantee(&self.from, self.host.as_deref()),
+        ))
+    }
+
+    fn rollback_sql(&self, table: &str, dialect: &dyn Dialect) -> Option<String> {
+        Some(dialect.grant_privileges(
+            &qualified(table, self.database.as_deref()),
+            &self.privileges,
+            &dialect.grantee(&self.from, self.host.as_deref()),
+        ))
+    }
+
+    fn ignored_options(&self, dialect: &dyn Dialect) -> Vec<&'static str> {
+        ignored_host(self.host.as_deref(), dialect)
+    }
+}
+
+fn qualified(table: &str, database: Option<&str>) -> String {
+    match database {
+        Some(database) => format!("{}.{}", database, table),
+        None => table.to_string(),
+    }
+}
+
+fn ignored_host(host: Option<&str>, dialect: &dyn Dialect) -> Vec<&'static str> {
+    if host.is_some() && !dialect.supports(Feature::GranteeHost) {
+        vec!["host"]
+    } else {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{MySqlDialect, PostgresDialect};
+
+    fn grant(privileges: Vec<Privilege>) -> GrantPrivileges {
+        GrantPrivileges {
+            privileges,
+            to: "reporting".to_string(),
+            host: None,
+            database: None,
+        }
+    }
+
+    #[test]
+    fn grants_single_and_multiple_privileges() {
+        let grant_select = grant(vec![Privilege::Select]);
+        assert_eq!(
+            grant_select
+                .generate_sql("orders", &PostgresDialect::new())
+                .unwrap(),
+            "GRANT SELECT ON orders TO reporting;"
+        );
+        assert_eq!(
+            grant_select
+                .generate_sql("orders", &MySqlDialect::new())
+                .unwrap(),
+            "GRANT SELECT ON orders TO 'reporting';"
+        );
+
+        let grant_writes = grant(vec![
+            Privilege::Insert,
+            Privilege::Update,
+            Privilege::Delete,
+        ]);
+        assert_eq!(
+            grant_writes
+                .generate_sql("orders", &PostgresDialect::new())
+                .unwrap(),
+            "GRANT INSERT, UPDATE, DELETE ON orders TO reporting;"
+        );
+    }
+
+    #[test]
+    fn qualifies_mysql_accounts_and_tables() {
+        let grant_all = GrantPrivileges {
+            host: Some("10.0.%".to_string()),
+            database: Some("shop".to_string()),
+            ..grant(vec![Privilege::All])
+        };
+        assert_eq!(
+            grant_all
+                .generate_sql("orders", &MySqlDialect::new())
+                .unwrap(),
+            "GRANT ALL ON shop.orders TO 'reporting'@'10.0.%';"
+        );
+        assert!(grant_all.ignored_options(&MySqlDialect::new()).is_empty());
+
+        assert_eq!(
+            grant_all
+                .generate_sql("orders", &PostgresDialect::new())
+                .unwrap(),
+            "GRANT ALL ON shop.orders TO reporting;"
+        );
+        assert_eq!(
+            grant_all.ignored_options(&PostgresDialect::new()),
+            vec!["host"]
+        );
+    }
+
+    #[test]
+    fn revokes_privileges() {
+        let revoke = RevokePrivileges {
+            privileges: vec![Privilege::Select, Privilege::Insert],
+            from: "reporting".to_string(),
+            host: Some("%".to_string()),
+            database: None,
+        };
+        assert_eq!(
+            revoke
+                .generate_sql("orders", &PostgresDialect::new())
+                .unwrap(),
+            "REVOKE SELECT, INSERT ON orders FROM reporting;"
+        );
+        assert_eq!(
+            revoke.generate_sql("orders", &MySqlDialect::new()).unwrap(),
+            "REVOKE SELECT, INSERT ON orders FROM 'reporting'@'%';"
+        );
+        assert_eq!(
+            revoke.rollback_sql("orders", &MySqlDialect::new()).unwrap(),
+            "GRANT SELECT, INSERT ON orders TO 'reporting'@'%';"
+        );
+    }
+}
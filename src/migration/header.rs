@@ -0,0 +1,141 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::Migration;
+use crate::error::MigrationError;
+
+impl Migration {
+    /// Generates the statements preceded by a `--` comment header carrying the
+    /// migration's version, name, generation time, dialect and checksum.
+    pub fn generate_with_header(&self) -> Result<String, MigrationError> {
+        self.generate_with_header_at(SystemTime::now())
+    }
+
+    /// Like [`Migration::generate_with_header`], with an explicit generation
+    /// time so the output is reproducible.
+    pub fn generate_with_header_at(
+        &self,
+        generated_at: SystemTime,
+    ) -> Result<String, MigrationError> {
+        let statements = self.generate_sql()?;
+
+        let mut lines = Vec::new();
+        if let Some(version) = &self.version {
+            lines.push(format!("-- version: {}", version));
+        }
+        if let Some(name) = &self.name {
+            lines.push(format!("-- name: {}", name));
+        }
+        lines.push(format!("-- generated-at: {}", format_rfc3339(generated_at)));
+        lines.push(format!("-- dialect: {}", self.dialect.name()));
+        lines.push(format!("-- checksum: {:016x}", checksum(&statements)));
+        lines.push(String::new());
+        lines.extend(statements.into_iter().map(|statement| statement.sql));
+
+        Ok(lines.join("\n"))
+    }
+}
+
+/// FNV-1a over the statements, so the value is stable across Rust releases.
+pub(crate) fn checksum<S: AsRef<str>>(statements: &[S]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for (index, statement) in statements.iter().enumerate() {
+        if index > 0 {
+            hash = fnv1a_byte(hash, b'\n');
+        }
+        for byte in statement.as_ref().bytes() {
+            hash = fnv1a_byte(hash, byte);
+        }
+    }
+    hash
+}
+
+fn fnv1a_byte(hash: u64, byte: u8) -> u64 {
+    (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+}
+
+/// Formats a time as a UTC RFC 3339 timestamp with second precision.
+fn format_rfc3339(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    let days = seconds / 86_400;
+    let time_of_day = seconds % 86_400;
+
+    // Civil-from-days conversion, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        time_of_day / 3_600,
+        time_of_day % 3_600 / 60,
+        time_of_day % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::PostgresDialect;
+    use crate::migration::DropColumn;
+    use std::time::Duration;
+
+    #[test]
+    fn formats_timestamps_as_rfc3339() {
+        assert_eq!(format_rfc3339(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        assert_eq!(
+            format_rfc3339(UNIX_EPOCH + Duration::from_secs(1_709_210_096)),
+            "2024-02-29T12:34:56Z"
+        );
+    }
+
+    #[test]
+    fn header_fields_appear_in_order() {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()))
+            .with_version("20240229123456")
+            .with_name("drop_legacy_flag");
+        migration.add_operation(DropColumn {
+            column: "legacy_flag".to_string(),
+            if_exists: false,
+            cascade: false,
+        });
+
+        let script = migration
+            .generate_with_header_at(UNIX_EPOCH + Duration::from_secs(1_709_210_096))
+            .unwrap();
+        let lines: Vec<&str> = script.lines().collect();
+
+        assert_eq!(lines[0], "-- version: 20240229123456");
+        assert_eq!(lines[1], "-- name: drop_legacy_flag");
+        assert_eq!(lines[2], "-- generated-at: 2024-02-29T12:34:56Z");
+        assert_eq!(lines[3], "-- dialect: postgres");
+        assert!(lines[4].starts_with("-- checksum: "));
+        assert_eq!(lines[5], "");
+        assert_eq!(lines[6], "ALTER TABLE users DROP COLUMN legacy_flag;");
+    }
+
+    #[test]
+    fn checksum_tracks_the_statements() {
+        let first = vec!["ALTER TABLE users DROP COLUMN a;".to_string()];
+        let second = vec!["ALTER TABLE users DROP COLUMN b;".to_string()];
+
+        assert_eq!(checksum(&first), checksum(&first.clone()));
+        assert_ne!(checksum(&first), checksum(&second));
+    }
+}
@@ -0,0 +1,193 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::statement::{joined, of_step};
+use super::{ColumnEffect, MigrationStep, Statement};
+use crate::data_type::DataType;
+use crate::dialect::{Dialect, Feature};
+use crate::error::MigrationError;
+
+/// Turns an existing column into an identity column.
+///
+/// MySQL sets `AUTO_INCREMENT` by restating the column, so it needs
+/// `current_type`, and cannot reject explicit values as `always` asks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AddIdentity {
+    pub column: String,
+    /// `GENERATED ALWAYS` rather than `GENERATED BY DEFAULT`.
+    pub always: bool,
+    pub start: Option<i64>,
+    pub current_type: Option<DataType>,
+}
+
+impl MigrationStep for AddIdentity {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(joined(&self.statements(table, dialect)?))
+    }
+
+    fn statements(
+        &self,
+        table: &str,
+        dialect: &dyn Dialect,
+    ) -> Result<Vec<Statement>, MigrationError> {
+        let sql = dialect.add_identity(
+            table,
+            &self.column,
+            self.always,
+            self.start,
+            self.current_type.as_ref(),
+        )?;
+        Ok(of_step(self, table, sql))
+    }
+
+    fn rollback_sql(&self, table: &str, dialect: &dyn Dialect) -> Option<String> {
+        dialect
+            .drop_identity(table, &self.column, self.current_type.as_ref())
+            .ok()
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        vec![ColumnEffect::Reference(self.column.clone())]
+    }
+
+    fn ignored_options(&self, dialect: &dyn Dialect) -> Vec<&'static str> {
+        if self.always && !dialect.supports(Feature::IdentityAlways) {
+            vec!["GENERATED ALWAYS"]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Stops a column generating values, keeping the values it has. See
+/// [`AddIdentity`] for when `current_type` is needed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DropIdentity {
+    pub column: String,
+    pub current_type: Option<DataType>,
+}
+
+impl MigrationStep for DropIdentity {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        dialect.drop_identity(table, &self.column, self.current_type.as_ref())
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        vec![ColumnEffect::Reference(self.column.clone())]
+    }
+}
+
+/// Sets the next value an existing identity column generates. Serial
+/// columns are restarted through their sequence with
+/// [`RestartSequence`](super::RestartSequence).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RestartIdentity {
+    pub column: String,
+    pub start: i64,
+}
+
+impl MigrationStep for RestartIdentity {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        dialect.restart_identity(table, &self.column, self.start)
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        vec![ColumnEffect::Reference(self.column.clone())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{MariaDbDialect, MySqlDialect, PostgresDialect};
+
+    fn add_identity(start: Option<i64>) -> AddIdentity {
+        AddIdentity {
+            column: "id".to_string(),
+            always: true,
+            start,
+            current_type: Some(DataType::BigInt),
+        }
+    }
+
+    #[test]
+    fn adds_postgres_identity() {
+        let dialect = PostgresDialect::new();
+        assert_eq!(
+            add_identity(None).generate_sql("orders", &dialect).unwrap(),
+            "ALTER TABLE orders ALTER COLUMN id ADD GENERATED ALWAYS AS IDENTITY;"
+        );
+        let add = AddIdentity {
+            always: false,
+            ..add_identity(Some(1000))
+        };
+        assert_eq!(
+            add.generate_sql("orders", &dialect).unwrap(),
+            "ALTER TABLE orders ALTER COLUMN id ADD GENERATED BY DEFAULT AS IDENTITY (START WITH 1000);"
+        );
+        assert_eq!(
+            add.rollback_sql("orders", &dialect).unwrap(),
+            "ALTER TABLE orders ALTER COLUMN id DROP IDENTITY;"
+        );
+    }
+
+    #[test]
+    fn sets_mysql_auto_increment() {
+        let add = add_identity(Some(1000));
+        let sql = [
+            "ALTER TABLE orders MODIFY COLUMN id BIGINT NOT NULL AUTO_INCREMENT;",
+            "ALTER TABLE orders AUTO_INCREMENT = 1000;",
+        ];
+        assert_eq!(add.statements("orders", &MySqlDialect::new()).unwrap(), sql);
+        assert_eq!(
+            add.statements("orders", &MariaDbDialect::new()).unwrap(),
+            sql
+        );
+        assert_eq!(
+            add.ignored_options(&MySqlDialect::new()),
+            vec!["GENERATED ALWAYS"]
+        );
+        assert_eq!(
+            add.rollback_sql("orders", &MySqlDialect::new()).unwrap(),
+            "ALTER TABLE orders MODIFY COLUMN id BIGINT NOT NULL;"
+        );
+    }
+
+    #[test]
+    fn mysql_needs_current_type() {
+        let add = AddIdentity {
+            current_type: None,
+            ..add_identity(None)
+        };
+        assert_eq!(
+            add.generate_sql("orders", &MySqlDialect::new()),
+            Err(MigrationError::MissingColumnType {
+                dialect: "mysql".to_string(),
+                column: "id".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn restarts_identity() {
+        let restart = RestartIdentity {
+            column: "id".to_string(),
+            start: 5000,
+        };
+        assert_eq!(
+            restart
+                .generate_sql("orders", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE orders ALTER COLUMN id RESTART WITH 5000;"
+        );
+        assert_eq!(
+            restart
+                .generate_sql("orders", &MySqlDialect::new())
+                .unwrap(),
+            "ALTER TABLE orders AUTO_INCREMENT = 5000;"
+        );
+    }
+}
@@ -0,0 +1,396 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{ColumnEffect, LockLevel, MigrationStep, RiskProfile};
+use crate::dialect::{Dialect, Feature, NameKind};
+use crate::error::MigrationError;
+
+/// A column in an index.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IndexColumn {
+    pub name: String,
+    /// Index only the first N characters. MySQL needs this for TEXT/BLOB
+    /// columns; Postgres has no prefix indexes and ignores it.
+    pub prefix_length: Option<u32>,
+}
+
+impl From<&str> for IndexColumn {
+    fn from(name: &str) -> Self {
+        IndexColumn {
+            name: name.to_string(),
+            prefix_length: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CreateIndex {
+    /// Defaults to `idx_{table}_{columns}`; see
+    /// [`NamingStrategy`](crate::dialect::NamingStrategy).
+    pub name: Option<String>,
+    pub columns: Vec<IndexColumn>,
+    pub unique: bool,
+    /// Only build the index once the table's estimated row count exceeds
+    /// this value. Honoured by Postgres; other dialects always create it.
+    pub conditional_on_rows: Option<u64>,
+    /// Build the index without blocking writes (Postgres). Cannot run inside
+    /// a transaction, nor together with `conditional_on_rows`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub concurrently: bool,
+    /// Skip the index if one with its name exists, where the dialect
+    /// supports it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub if_not_exists: bool,
+    /// Index only the rows matching this condition, e.g.
+    /// `deleted_at IS NULL`. Dialects without partial indexes reject it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub where_clause: Option<String>,
+}
+
+impl CreateIndex {
+    pub fn index_name(&self, table: &str, dialect: &dyn Dialect) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => {
+                let columns: Vec<String> = self.columns.iter().map(|c| c.name.clone()).collect();
+                dialect.generated_name(NameKind::Index, table, &columns)
+            }
+        }
+    }
+}
+
+impl MigrationStep for CreateIndex {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        // The row check runs in a DO block, which is a transaction.
+        if self.concurrently
+            && self.conditional_on_rows.is_some()
+            && dialect.supports(Feature::ConcurrentIndex)
+            && dialect.supports(Feature::ConditionalIndex)
+        {
+            return Err(MigrationError::unsupported(
+                dialect.name(),
+                "CREATE INDEX CONCURRENTLY with conditional_on_rows",
+            ));
+        }
+        // Indexing every row would change what a unique index enforces.
+        if self.where_clause.is_some() && !dialect.supports(Feature::PartialIndex) {
+            return Err(MigrationError::unsupported(
+                dialect.name(),
+                "partial indexes",
+            ));
+        }
+        Ok(dialect.create_index(table, self))
+    }
+
+    fn transactional(&self) -> bool {
+        !self.concurrently
+    }
+
+    fn description(&self, table: &str) -> String {
+        // The generated name depends on the dialect's naming strategy.
+        let columns: Vec<&str> = self.columns.iter().map(|c| c.name.as_str()).collect();
+        match &self.name {
+            Some(name) => format!("create index {} on {}", name, table),
+            None => format!("create index on {} ({})", table, columns.join(", ")),
+        }
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        self.columns
+            .iter()
+            .map(|column| ColumnEffect::Reference(column.name.clone()))
+            .collect()
+    }
+
+    fn ignored_options(&self, dialect: &dyn Dialect) -> Vec<&'static str> {
+        let mut ignored = Vec::new();
+        if self.columns.iter().any(|c| c.prefix_length.is_some())
+            && !dialect.supports(Feature::IndexPrefix)
+        {
+            ignored.push("prefix_length");
+        }
+        if self.conditional_on_rows.is_some() && !dialect.supports(Feature::ConditionalIndex) {
+            ignored.push("conditional_on_rows");
+        }
+        if self.concurrently && !dialect.supports(Feature::ConcurrentIndex) {
+            ignored.push("concurrently");
+        }
+        if self.if_not_exists && !dialect.supports(Feature::CreateIndexIfNotExists) {
+            ignored.push("IF NOT EXISTS");
+        }
+        ignored
+    }
+
+    fn risk_profile(&self) -> RiskProfile {
+        RiskProfile {
+            lock_level: if self.concurrently {
+                LockLevel::Row
+            } else {
+                LockLevel::Shared
+            },
+            ..RiskProfile::default()
+        }
+    }
+}
+
+/// Drops an index of the migration's table by name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DropIndex {
+    pub name: String,
+    /// Do nothing if the index is already gone, where the dialect supports
+    /// it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub if_exists: bool,
+}
+
+impl MigrationStep for DropIndex {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(dialect.drop_index(table, &self.name, self.if_exists))
+    }
+
+    fn ignored_options(&self, dialect: &dyn Dialect) -> Vec<&'static str> {
+        if self.if_exists && !dialect.supports(Feature::DropIndexIfExists) {
+            vec!["IF EXISTS"]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{MariaDbDialect, MssqlDialect, MySqlDialect, PostgresDialect};
+
+    fn index(name: Option<&str>, columns: &[&str], unique: bool) -> CreateIndex {
+        CreateIndex {
+            name: name.map(str::to_string),
+            columns: columns.iter().map(|&c| c.into()).collect(),
+            unique,
+            conditional_on_rows: None,
+            concurrently: false,
+            if_not_exists: false,
+            where_clause: None,
+        }
+    }
+
+    #[test]
+    fn creates_single_column_index() {
+        let index = index(None, &["email"], false);
+        assert_eq!(
+            index
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "CREATE INDEX idx_users_email ON users (email);"
+        );
+        assert_eq!(
+            index.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "CREATE INDEX idx_users_email ON users (email);"
+        );
+    }
+
+    #[test]
+    fn creates_composite_index_with_generated_name() {
+        let index = index(None, &["email", "created_at"], false);
+        assert_eq!(
+            index
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "CREATE INDEX idx_users_email_created_at ON users (email, created_at);"
+        );
+        assert_eq!(
+            index.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "CREATE INDEX idx_users_email_created_at ON users (email, created_at);"
+        );
+    }
+
+    #[test]
+    fn creates_unique_index_with_explicit_name() {
+        let index = index(Some("users_email_key"), &["email"], true);
+        assert_eq!(
+            index
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "CREATE UNIQUE INDEX users_email_key ON users (email);"
+        );
+        assert_eq!(
+            index.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "CREATE UNIQUE INDEX users_email_key ON users (email);"
+        );
+    }
+
+    #[test]
+    fn applies_prefix_length_on_mysql_only() {
+        let mut index = index(None, &["bio", "email"], false);
+        index.columns[0].prefix_length = Some(64);
+
+        assert_eq!(
+            index.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "CREATE INDEX idx_users_bio_email ON users (bio(64), email);"
+        );
+        assert_eq!(
+            index
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "CREATE INDEX idx_users_bio_email ON users (bio, email);"
+        );
+    }
+    #[test]
+    fn creates_postgres_index_concurrently() {
+        let index = CreateIndex {
+            concurrently: true,
+            ..index(None, &["email"], false)
+        };
+        assert_eq!(
+            index
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "CREATE INDEX CONCURRENTLY idx_users_email ON users (email);"
+        );
+        assert!(!index.transactional());
+        assert_eq!(index.risk_profile().lock_level, LockLevel::Row);
+    }
+
+    #[test]
+    fn mysql_ignores_concurrently() {
+        let index = CreateIndex {
+            concurrently: true,
+            ..index(None, &["email"], false)
+        };
+        assert_eq!(
+            index.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "CREATE INDEX idx_users_email ON users (email);"
+        );
+        assert_eq!(
+            index.ignored_options(&MySqlDialect::new()),
+            vec!["concurrently"]
+        );
+    }
+
+    #[test]
+    fn cannot_create_conditional_index_concurrently() {
+        let index = CreateIndex {
+            concurrently: true,
+            conditional_on_rows: Some(1_000_000),
+            ..index(None, &["email"], false)
+        };
+        assert_eq!(
+            index.generate_sql("users", &PostgresDialect::new()),
+            Err(MigrationError::unsupported(
+                "postgres",
+                "CREATE INDEX CONCURRENTLY with conditional_on_rows"
+            ))
+        );
+    }
+
+    #[test]
+    fn filters_partial_indexes() {
+        let index = CreateIndex {
+            where_clause: Some("deleted_at IS NULL".to_string()),
+            ..index(None, &["email"], true)
+        };
+        assert_eq!(
+            index
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "CREATE UNIQUE INDEX idx_users_email ON users (email) WHERE deleted_at IS NULL;"
+        );
+        assert_eq!(
+            index.generate_sql("users", &MssqlDialect::new()).unwrap(),
+            "CREATE UNIQUE INDEX idx_users_email ON users (email) WHERE deleted_at IS NULL;"
+        );
+    }
+
+    #[test]
+    fn rejects_partial_indexes_on_mysql() {
+        let index = CreateIndex {
+            where_clause: Some("deleted_at IS NULL".to_string()),
+            ..index(None, &["email"], true)
+        };
+        assert!(matches!(
+            index.generate_sql("users", &MySqlDialect::new()),
+            Err(MigrationError::Unsupported { .. })
+        ));
+    }
+
+    #[test]
+    fn guards_index_creation_where_supported() {
+        let index = CreateIndex {
+            if_not_exists: true,
+            ..index(None, &["email"], false)
+        };
+        assert_eq!(
+            index
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "CREATE INDEX IF NOT EXISTS idx_users_email ON users (email);"
+        );
+        assert_eq!(
+            index.generate_sql("users", &MariaDbDialect::new()).unwrap(),
+            "CREATE INDEX IF NOT EXISTS idx_users_email ON users (email);"
+        );
+        assert_eq!(
+            index.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "CREATE INDEX idx_users_email ON users (email);"
+        );
+        assert_eq!(
+            index.ignored_options(&MySqlDialect::new()),
+            vec!["IF NOT EXISTS"]
+        );
+    }
+
+    #[test]
+    fn drops_index_by_name() {
+        let drop = DropIndex {
+            name: "idx_users_email".to_string(),
+            if_exists: true,
+        };
+        assert_eq!(
+            drop.generate_sql("users", &PostgresDialect::new()).unwrap(),
+            "DROP INDEX IF EXISTS idx_users_email;"
+        );
+        assert_eq!(
+            drop.generate_sql("analytics.users", &PostgresDialect::new())
+                .unwrap(),
+            "DROP INDEX IF EXISTS analytics.idx_users_email;"
+        );
+        assert_eq!(
+            drop.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "DROP INDEX idx_users_email ON users;"
+        );
+        assert_eq!(
+            drop.ignored_options(&MySqlDialect::new()),
+            vec!["IF EXISTS"]
+        );
+        assert!(drop.ignored_options(&PostgresDialect::new()).is_empty());
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use super::*;
+
+    #[test]
+    fn create_index_round_trips() {
+        let index = CreateIndex {
+            name: None,
+            columns: vec![
+                IndexColumn {
+                    name: "bio".to_string(),
+                    prefix_length: Some(64),
+                },
+                "created_at".into(),
+            ],
+            unique: true,
+            conditional_on_rows: Some(1_000_000),
+            concurrently: false,
+            if_not_exists: false,
+            where_clause: None,
+        };
+        let json = serde_json::to_string(&index).unwrap();
+        assert_eq!(serde_json::from_str::<CreateIndex>(&json).unwrap(), index);
+    }
+}
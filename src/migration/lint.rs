@@ -0,0 +1,114 @@
+use std::fmt;
+
+use super::Migration;
+
+/// An option set on an operation that the migration's dialect ignores.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DialectLint {
+    /// Position of the operation in the migration.
+    pub index: usize,
+    pub option: String,
+    pub dialect: String,
+}
+
+impl fmt::Display for DialectLint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "operation {} sets {}, which the {} dialect ignores",
+            self.index, self.option, self.dialect
+        )
+    }
+}
+
+impl Migration {
+    /// Reports options that would be silently dropped when generating for the
+    /// current dialect, e.g. MySQL column positions on Postgres.
+    pub fn lint_dialect(&self) -> Vec<DialectLint> {
+        let dialect = self.dialect.as_ref();
+        self.operations
+            .iter()
+            .enumerate()
+            .flat_map(|(index, operation)| {
+                operation
+                    .ignored_options(dialect)
+                    .into_iter()
+                    .map(move |option| DialectLint {
+                        index,
+                        option: option.to_string(),
+                        dialect: dialect.name().to_string(),
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_type::{Column, DataType};
+    use crate::dialect::{Dialect, MySqlDialect, PostgresDialect};
+    use crate::migration::{AddColumn, ColumnPosition, CreateIndex, DropTable};
+
+    fn migration(dialect: Box<dyn Dialect>) -> Migration {
+        let mut migration = Migration::new("users", dialect);
+        migration
+            .add_operation(AddColumn {
+                column: Column {
+                    name: "updated_at".to_string(),
+                    data_type: DataType::Timestamp {
+                        with_timezone: false,
+                        precision: None,
+                    },
+                    nullable: true,
+                    auto_increment: false,
+                    generated: None,
+                    collation: None,
+                },
+                position: Some(ColumnPosition::After("created_at".to_string())),
+                if_not_exists: false,
+            })
+            .add_operation(CreateIndex {
+                name: None,
+                columns: vec!["email".into()],
+                unique: false,
+                conditional_on_rows: Some(1_000_000),
+                concurrently: false,
+                if_not_exists: false,
+                where_clause: None,
+            })
+            .add_operation(DropTable {
+                if_exists: true,
+                cascade: true,
+            });
+        migration
+    }
+
+    #[test]
+    fn flags_mysql_only_options_on_postgres() {
+        assert_eq!(
+            migration(Box::new(PostgresDialect::new())).lint_dialect(),
+            vec![DialectLint {
+                index: 0,
+                option: "column position".to_string(),
+                dialect: "postgres".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn flags_postgres_only_options_on_mysql() {
+        let lints: Vec<String> = migration(Box::new(MySqlDialect::new()))
+            .lint_dialect()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            lints,
+            vec![
+                "operation 1 sets conditional_on_rows, which the mysql dialect ignores",
+                "operation 2 sets cascade, which the mysql dialect ignores",
+            ]
+        );
+    }
+}
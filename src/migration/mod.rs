@@ -0,0 +1,653 @@
+mod column;
+mod combine;
+mod conditional;
+mod constraint;
+mod data;
+mod diff;
+mod enum_type;
+#[cfg(feature = "serde")]
+mod file;
+mod function;
+mod grant;
+mod header;
+mod identity;
+mod index;
+mod lint;
+mod node;
+mod overrides;
+mod param;
+mod partition;
+mod raw;
+mod risk;
+mod schema;
+mod script;
+mod sequence;
+mod statement;
+mod style;
+mod table;
+mod validate;
+mod view;
+
+pub use column::{
+    AddColumn, AddColumnSafe, AlterColumnCollation, ChangeColumnType, ColumnPosition, DropColumn,
+    DropColumnDefault, DropNotNull, MergeColumns, RenameColumn, ReorderColumn, SetColumnDefault,
+    SetNotNull,
+};
+pub use conditional::{ConditionalStep, Precondition};
+pub use constraint::{
+    AddCheckConstraint, AddForeignKey, AddPrimaryKey, AddUniqueConstraint, DropCheckConstraint,
+    DropPrimaryKey, DropUniqueConstraint, ReferentialAction,
+};
+pub use data::{
+    CopyColumnData, DeleteRows, ExternalProcessColumnData, InsertRows, Operator, UpdateColumnData,
+    UpdateValue, WhereCondition,
+};
+pub use diff::diff_columns;
+pub use enum_type::{AddEnumValue, CreateEnumType, EnumValuePosition, RenameEnumValue};
+#[cfg(feature = "serde")]
+pub use file::{MigrationFile, Operation};
+pub use function::{CreateFunction, DropFunction};
+pub use grant::{GrantPrivileges, Privilege, RevokePrivileges};
+pub use identity::{AddIdentity, DropIdentity, RestartIdentity};
+pub use index::{CreateIndex, DropIndex, IndexColumn};
+pub use lint::DialectLint;
+pub use node::{Node, TargetNode};
+pub use param::Param;
+pub use partition::{
+    AttachPartition, CreatePartition, DetachPartition, DropPartition, PartitionBound,
+};
+pub use raw::RawSql;
+pub use risk::{AnnotatedStatement, LockLevel, RiskProfile};
+pub use schema::Schema;
+pub use script::ScriptOptions;
+pub use sequence::{CreateSequence, DropSequence, RestartSequence};
+pub use statement::Statement;
+pub use style::SqlStyle;
+pub use table::{CreateSchema, CreateTable, DropTable, TableOptions, TableRef};
+pub use validate::{ColumnEffect, ValidationError};
+pub use view::{
+    CreateMaterializedView, CreateView, DropMaterializedView, DropView, RefreshMaterializedView,
+};
+
+pub(crate) use header::checksum;
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Add;
+use std::sync::Arc;
+
+use crate::dialect::keyword::sql;
+use crate::dialect::Dialect;
+use crate::error::MigrationError;
+
+/// A single operation in a migration.
+///
+/// Steps are [`Any`] so that a saved migration can recover each built-in
+/// operation from its boxed step.
+pub trait MigrationStep: Any {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError>;
+
+    /// The SQL as separate statements, in order, so they can be executed one
+    /// at a time. Operations needing more than one, or flagging statements
+    /// differently, override this, and their [`MigrationStep::generate_sql`]
+    /// joins them with newlines.
+    fn statements(
+        &self,
+        table: &str,
+        dialect: &dyn Dialect,
+    ) -> Result<Vec<Statement>, MigrationError> {
+        Ok(vec![Statement::new(
+            self,
+            table,
+            self.generate_sql(table, dialect)?,
+        )])
+    }
+
+    /// Short type name used in logs, e.g. `DropColumn`.
+    fn operation_name(&self) -> &'static str {
+        let name = std::any::type_name::<Self>();
+        name.rsplit("::").next().unwrap_or(name)
+    }
+
+    /// What the operation does to `table`, for people reading a plan, e.g.
+    /// `drop column email from users`.
+    fn description(&self, table: &str) -> String {
+        format!("{} on {}", self.operation_name(), table)
+    }
+
+    /// The statement with bindable values replaced by placeholders, and the
+    /// values in bind order. Defaults to `None` for steps with no values to
+    /// bind, whose [`MigrationStep::statements`] are used as they are.
+    fn generate_parameterized(
+        &self,
+        _table: &str,
+        _dialect: &dyn Dialect,
+    ) -> Result<Option<(String, Vec<Param>)>, MigrationError> {
+        Ok(None)
+    }
+
+    /// Whether the statements may run inside a transaction; see
+    /// [`Statement::transactional`].
+    fn transactional(&self) -> bool {
+        true
+    }
+
+    /// Whether the statements are `ALTER TABLE` actions on the column
+    /// definitions that may share one statement with neighbouring ones; see
+    /// [`Migration::generate_sql_combined`].
+    fn combinable(&self) -> bool {
+        false
+    }
+
+    /// SQL undoing this operation, or `None` if it cannot be reversed.
+    fn rollback_sql(&self, _table: &str, _dialect: &dyn Dialect) -> Option<String> {
+        None
+    }
+
+    /// The columns this operation adds, drops, renames or depends on.
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        Vec::new()
+    }
+
+    /// How dangerous the operation is to run against a live table.
+    fn risk_profile(&self) -> RiskProfile {
+        RiskProfile::default()
+    }
+
+    /// Substitutes `${token}` placeholders in the operation's literal values.
+    fn apply_overrides(
+        &mut self,
+        _overrides: &HashMap<String, String>,
+    ) -> Result<(), MigrationError> {
+        Ok(())
+    }
+
+    /// Options set on this operation that `dialect` cannot express and will
+    /// leave out of the generated SQL.
+    fn ignored_options(&self, _dialect: &dyn Dialect) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    /// The nodes of a replicated setup this operation runs on.
+    fn target_node(&self) -> TargetNode {
+        TargetNode::All
+    }
+}
+
+/// Generates the SQL for a single operation without building a [`Migration`].
+pub fn to_sql(
+    step: &dyn MigrationStep,
+    table: &str,
+    dialect: &dyn Dialect,
+) -> Result<String, MigrationError> {
+    step.generate_sql(table, dialect)
+}
+
+/// An ordered list of operations against one table.
+pub struct Migration {
+    table: TableRef,
+    dialect: Arc<dyn Dialect>,
+    operations: Vec<Box<dyn MigrationStep>>,
+    version: Option<String>,
+    name: Option<String>,
+}
+
+impl Migration {
+    /// `table` is a bare name or a `(schema, name)` pair; see [`TableRef`].
+    pub fn new(table: impl Into<TableRef>, dialect: Box<dyn Dialect>) -> Self {
+        Self::with_shared_dialect(table.into(), Arc::from(dialect))
+    }
+
+    pub(crate) fn with_shared_dialect(table: TableRef, dialect: Arc<dyn Dialect>) -> Self {
+        Migration {
+            table,
+            dialect,
+            operations: Vec::new(),
+            version: None,
+            name: None,
+        }
+    }
+
+    pub fn with_version(mut self, version: &str) -> Self {
+        self.version = Some(version.to_string());
+        self
+    }
+
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    pub fn add_operation(&mut self, operation: impl MigrationStep + 'static) -> &mut Self {
+        self.operations.push(Box::new(operation));
+        self
+    }
+
+    /// Queues already boxed operations, e.g. from [`diff_columns`].
+    pub fn extend_operations(
+        &mut self,
+        operations: impl IntoIterator<Item = Box<dyn MigrationStep>>,
+    ) -> &mut Self {
+        self.operations.extend(operations);
+        self
+    }
+
+    pub fn table(&self) -> &TableRef {
+        &self.table
+    }
+
+    /// The queued operations, in insertion order.
+    pub fn operations(&self) -> &[Box<dyn MigrationStep>] {
+        &self.operations
+    }
+
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Generates every operation's statements, in insertion order. Operations
+    /// that render nothing for this dialect are left out.
+    pub fn generate_sql(&self) -> Result<Vec<Statement>, MigrationError> {
+        let mut statements = Vec::new();
+        for operation in &self.operations {
+            statements.extend(self.generate_operation(operation.as_ref())?);
+        }
+        Ok(statements)
+    }
+
+    /// Generates one operation's statements, logging them when the `logging`
+    /// feature is enabled.
+    fn generate_operation(
+        &self,
+        operation: &dyn MigrationStep,
+    ) -> Result<Vec<Statement>, MigrationError> {
+        let mut statements =
+            operation.statements(&self.table.to_string(), self.dialect.as_ref())?;
+        statements.retain(|statement| !statement.sql.is_empty());
+        #[cfg(feature = "logging")]
+        for statement in &statements {
+            log::debug!(
+                "{} on {}: {}",
+                operation.operation_name(),
+                self.table,
+                statement
+            );
+        }
+        Ok(statements)
+    }
+
+    /// Like [`Migration::generate_sql`], but wraps each run of transactional
+    /// statements in `BEGIN`/`COMMIT`. Statements that cannot run inside a
+    /// transaction are emitted between the blocks on their own.
+    pub fn generate_sql_in_transaction(&self) -> Result<Vec<String>, MigrationError> {
+        let mut statements = Vec::new();
+        let mut in_transaction = false;
+
+        for statement in self.generate_sql()? {
+            if statement.transactional != in_transaction {
+                statements.push(if in_transaction {
+                    sql!(self.dialect, "COMMIT;")
+                } else {
+                    sql!(self.dialect, "BEGIN;")
+                });
+                in_transaction = !in_transaction;
+            }
+            statements.push(statement.sql);
+        }
+
+        if in_transaction {
+            statements.push(sql!(self.dialect, "COMMIT;"));
+        }
+        Ok(statements)
+    }
+
+    /// The down migration: each operation's rollback, last operation first.
+    /// Fails on the first operation that cannot be rolled back.
+    pub fn generate_rollback_sql(&self) -> Result<Vec<String>, MigrationError> {
+        self.operations
+            .iter()
+            .rev()
+            .map(|operation| {
+                operation
+                    .rollback_sql(&self.table.to_string(), self.dialect.as_ref())
+                    .ok_or_else(|| MigrationError::Irreversible {
+                        operation: operation.operation_name().to_string(),
+                    })
+            })
+            .filter(|sql| !matches!(sql, Ok(sql) if sql.is_empty()))
+            .collect()
+    }
+}
+
+/// Appends the operations of `other`, keeping the version and name of `self`.
+///
+/// # Panics
+///
+/// If the migrations target different tables or dialects.
+impl Add for Migration {
+    type Output = Migration;
+
+    fn add(mut self, other: Migration) -> Migration {
+        assert_eq!(
+            self.table, other.table,
+            "cannot combine migrations for different tables"
+        );
+        assert_eq!(
+            self.dialect.name(),
+            other.dialect.name(),
+            "cannot combine migrations for different dialects"
+        );
+        self.operations.extend(other.operations);
+        self
+    }
+}
+
+/// A summary for review and logs: the table, the dialect, and each numbered
+/// operation with its SQL indented below it. Use [`Migration::generate_sql`]
+/// for SQL to run.
+impl fmt::Display for Migration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Migration")?;
+        if let Some(version) = &self.version {
+            write!(f, " {}", version)?;
+        }
+        if let Some(name) = &self.name {
+            write!(f, " {}", name)?;
+        }
+        writeln!(f, " on {} ({})", self.table, self.dialect.name())?;
+
+        for (index, operation) in self.operations.iter().enumerate() {
+            writeln!(f, "  {}. {}", index + 1, operation.operation_name())?;
+            match operation.generate_sql(&self.table.to_string(), self.dialect.as_ref()) {
+                Ok(sql) => {
+                    for line in sql.lines() {
+                        writeln!(f, "     {}", line)?;
+                    }
+                }
+                Err(err) => writeln!(f, "     error: {}", err)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{PostgresDialect, SqliteDialect};
+
+    #[test]
+    fn keeps_non_transactional_statements_out_of_transactions() {
+        let mut migration = Migration::new("events", Box::new(PostgresDialect::new()));
+        migration
+            .add_operation(DropColumn {
+                column: "legacy_flag".to_string(),
+                if_exists: false,
+                cascade: false,
+            })
+            .add_operation(DetachPartition {
+                name: "events_2023_12".to_string(),
+                concurrently: true,
+            })
+            .add_operation(DropColumn {
+                column: "legacy_source".to_string(),
+                if_exists: false,
+                cascade: false,
+            });
+
+        assert_eq!(
+            migration.generate_sql_in_transaction().unwrap(),
+            vec![
+                "BEGIN;",
+                "ALTER TABLE events DROP COLUMN legacy_flag;",
+                "COMMIT;",
+                "ALTER TABLE events DETACH PARTITION events_2023_12 CONCURRENTLY;",
+                "BEGIN;",
+                "ALTER TABLE events DROP COLUMN legacy_source;",
+                "COMMIT;",
+            ]
+        );
+    }
+    #[test]
+    fn adding_migrations_concatenates_operations() {
+        let mut first = Migration::new("users", Box::new(PostgresDialect::new()));
+        first.add_operation(DropColumn {
+            column: "a".to_string(),
+            if_exists: false,
+            cascade: false,
+        });
+        let mut second = Migration::new("users", Box::new(PostgresDialect::new()));
+        second
+            .add_operation(DropColumn {
+                column: "b".to_string(),
+                if_exists: false,
+                cascade: false,
+            })
+            .add_operation(DropColumn {
+                column: "c".to_string(),
+                if_exists: false,
+                cascade: false,
+            });
+
+        assert_eq!(
+            (first + second).generate_sql().unwrap(),
+            vec![
+                "ALTER TABLE users DROP COLUMN a;",
+                "ALTER TABLE users DROP COLUMN b;",
+                "ALTER TABLE users DROP COLUMN c;",
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot combine migrations for different tables")]
+    fn adding_migrations_for_different_tables_panics() {
+        let _ = Migration::new("users", Box::new(PostgresDialect::new()))
+            + Migration::new("orders", Box::new(PostgresDialect::new()));
+    }
+    #[test]
+    fn names_operations_by_type() {
+        assert_eq!(
+            DropColumn {
+                column: "a".to_string(),
+                if_exists: false,
+                cascade: false,
+            }
+            .operation_name(),
+            "DropColumn"
+        );
+    }
+    #[test]
+    fn generates_a_single_step_without_a_migration() {
+        let step = DropColumn {
+            column: "legacy_flag".to_string(),
+            if_exists: false,
+            cascade: false,
+        };
+        assert_eq!(
+            to_sql(&step, "users", &PostgresDialect::new()).unwrap(),
+            "ALTER TABLE users DROP COLUMN legacy_flag;"
+        );
+    }
+    #[test]
+    fn rolls_back_operations_in_reverse_order() {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration
+            .add_operation(RenameColumn {
+                old_name: "phone".to_string(),
+                new_name: "contact_number".to_string(),
+                definition: None,
+            })
+            .add_operation(RawSql {
+                up: "ANALYZE users;".to_string(),
+                down: Some("SELECT 1;".to_string()),
+                dialect_up: HashMap::new(),
+                dialects: None,
+            });
+
+        assert_eq!(
+            migration.generate_rollback_sql().unwrap(),
+            vec![
+                "SELECT 1;",
+                "ALTER TABLE users RENAME COLUMN contact_number TO phone;",
+            ]
+        );
+
+        migration.add_operation(DropColumn {
+            column: "legacy_flag".to_string(),
+            if_exists: false,
+            cascade: false,
+        });
+        assert_eq!(
+            migration.generate_rollback_sql(),
+            Err(MigrationError::Irreversible {
+                operation: "DropColumn".to_string()
+            })
+        );
+    }
+    #[test]
+    fn displays_numbered_operations_with_their_sql() {
+        let mut migration = Migration::new("users", Box::new(SqliteDialect::new()))
+            .with_version("20240301")
+            .with_name("tidy_users");
+        migration
+            .add_operation(DropColumn {
+                column: "legacy_flag".to_string(),
+                if_exists: false,
+                cascade: false,
+            })
+            .add_operation(CreateEnumType {
+                name: "user_status".to_string(),
+                variants: vec!["active".to_string()],
+            });
+
+        assert_eq!(
+            migration.to_string(),
+            concat!(
+                "Migration 20240301 tidy_users on users (sqlite)\n",
+                "  1. DropColumn\n",
+                "     ALTER TABLE users DROP COLUMN legacy_flag;\n",
+                "  2. CreateEnumType\n",
+                "     error: CREATE TYPE is not supported by the sqlite dialect\n",
+            )
+        );
+    }
+    #[test]
+    fn exposes_queued_operations() {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        assert!(migration.is_empty());
+
+        migration
+            .add_operation(DropColumn {
+                column: "a".to_string(),
+                if_exists: false,
+                cascade: false,
+            })
+            .add_operation(RenameColumn {
+                old_name: "b".to_string(),
+                new_name: "c".to_string(),
+                definition: None,
+            });
+        assert_eq!(migration.len(), 2);
+        let names: Vec<&str> = migration
+            .operations()
+            .iter()
+            .map(|operation| operation.operation_name())
+            .collect();
+        assert_eq!(names, vec!["DropColumn", "RenameColumn"]);
+    }
+    #[test]
+    fn qualifies_the_table_in_every_statement() {
+        use crate::data_type::{Column, ColumnOptions, DataType};
+        use crate::dialect::MySqlDialect;
+
+        let email = Column {
+            name: "email".to_string(),
+            data_type: DataType::Varchar(255),
+            nullable: true,
+            auto_increment: false,
+            generated: None,
+            collation: None,
+        };
+        let build = |dialect: Box<dyn Dialect>| {
+            let mut migration = Migration::new(("analytics", "users"), dialect);
+            migration
+                .add_operation(CreateTable {
+                    columns: vec![email.clone()],
+                    primary_key: Vec::new(),
+                    options: TableOptions::default(),
+                })
+                .add_operation(AddColumn {
+                    column: email.clone(),
+                    position: None,
+                    if_not_exists: false,
+                })
+                .add_operation(RenameColumn {
+                    old_name: "email".to_string(),
+                    new_name: "contact_email".to_string(),
+                    definition: None,
+                })
+                .add_operation(ChangeColumnType {
+                    column: "contact_email".to_string(),
+                    data_type: DataType::Varchar(320),
+                    options: ColumnOptions::default(),
+                    previous: None,
+                    position: None,
+                })
+                .add_operation(SetNotNull {
+                    column: "contact_email".to_string(),
+                    current_type: Some(DataType::Varchar(320)),
+                    backfill: None,
+                })
+                .add_operation(CreateIndex {
+                    name: None,
+                    columns: vec!["contact_email".into()],
+                    unique: true,
+                    conditional_on_rows: None,
+                    concurrently: false,
+                    if_not_exists: false,
+                    where_clause: None,
+                })
+                .add_operation(AddCheckConstraint {
+                    name: Some("chk_users_email".to_string()),
+                    expression: "contact_email <> ''".to_string(),
+                    validate_separately: false,
+                })
+                .add_operation(UpdateColumnData {
+                    column: "contact_email".to_string(),
+                    value: UpdateValue::Fixed("lower(contact_email)".to_string()),
+                    conditions: Vec::new(),
+                })
+                .add_operation(DropColumn {
+                    column: "contact_email".to_string(),
+                    if_exists: false,
+                    cascade: false,
+                })
+                .add_operation(DropTable {
+                    if_exists: false,
+                    cascade: false,
+                });
+            migration
+        };
+
+        for dialect in [
+            Box::new(PostgresDialect::new()) as Box<dyn Dialect>,
+            Box::new(MySqlDialect::new()),
+        ] {
+            let migration = build(dialect);
+            let statements = migration.generate_sql().unwrap();
+            assert_eq!(statements.len(), migration.len());
+            for statement in &statements {
+                assert!(statement.sql.contains("analytics.users"), "{}", statement);
+                assert!(!statement.sql.contains(" users"), "{}", statement);
+            }
+            // Derived names use the bare table name.
+            assert!(statements[5].sql.contains("idx_users_contact_email"));
+        }
+    }
+}
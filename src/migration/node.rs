@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+
+use super::{ColumnEffect, Migration, MigrationStep, Param, RiskProfile, Statement};
+use crate::dialect::Dialect;
+use crate::error::MigrationError;
+
+/// Which nodes of a replicated setup an operation must run on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TargetNode {
+    /// DDL that replicates on its own, or must not run on replicas.
+    PrimaryOnly,
+    #[default]
+    All,
+}
+
+/// The kind of node a script is generated for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Node {
+    Primary,
+    Replica,
+}
+
+impl TargetNode {
+    pub fn includes(&self, node: Node) -> bool {
+        match self {
+            TargetNode::PrimaryOnly => node == Node::Primary,
+            TargetNode::All => true,
+        }
+    }
+}
+
+/// Tags an operation with the nodes it runs on.
+struct OnNode<S> {
+    target: TargetNode,
+    step: S,
+}
+
+impl<S: MigrationStep> MigrationStep for OnNode<S> {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        self.step.generate_sql(table, dialect)
+    }
+
+    fn statements(
+        &self,
+        table: &str,
+        dialect: &dyn Dialect,
+    ) -> Result<Vec<Statement>, MigrationError> {
+        self.step.statements(table, dialect)
+    }
+
+    fn operation_name(&self) -> &'static str {
+        self.step.operation_name()
+    }
+
+    fn description(&self, table: &str) -> String {
+        self.step.description(table)
+    }
+
+    fn generate_parameterized(
+        &self,
+        table: &str,
+        dialect: &dyn Dialect,
+    ) -> Result<Option<(String, Vec<Param>)>, MigrationError> {
+        self.step.generate_parameterized(table, dialect)
+    }
+
+    fn transactional(&self) -> bool {
+        self.step.transactional()
+    }
+
+    fn combinable(&self) -> bool {
+        self.step.combinable()
+    }
+
+    fn rollback_sql(&self, table: &str, dialect: &dyn Dialect) -> Option<String> {
+        self.step.rollback_sql(table, dialect)
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        self.step.column_effects()
+    }
+
+    fn risk_profile(&self) -> RiskProfile {
+        self.step.risk_profile()
+    }
+
+    fn apply_overrides(
+        &mut self,
+        overrides: &HashMap<String, String>,
+    ) -> Result<(), MigrationError> {
+        self.step.apply_overrides(overrides)
+    }
+
+    fn ignored_options(&self, dialect: &dyn Dialect) -> Vec<&'static str> {
+        self.step.ignored_options(dialect)
+    }
+
+    fn target_node(&self) -> TargetNode {
+        self.target
+    }
+}
+
+impl Migration {
+    /// Adds an operation that only runs on the given nodes.
+    pub fn add_operation_on(
+        &mut self,
+        target: TargetNode,
+        operation: impl MigrationStep + 'static,
+    ) -> &mut Self {
+        self.add_operation(OnNode {
+            target,
+            step: operation,
+        })
+    }
+
+    /// Like [`Migration::generate_sql`], leaving out operations that do not
+    /// run on `node`.
+    pub fn generate_for_node(&self, node: Node) -> Result<Vec<Statement>, MigrationError> {
+        let mut statements = Vec::new();
+        for operation in &self.operations {
+            if operation.target_node().includes(node) {
+                statements.extend(self.generate_operation(operation.as_ref())?);
+            }
+        }
+        Ok(statements)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::PostgresDialect;
+    use crate::migration::{DropColumn, UpdateColumnData, UpdateValue};
+
+    fn migration() -> Migration {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration
+            .add_operation(UpdateColumnData {
+                column: "status".to_string(),
+                value: UpdateValue::Fixed("'active'".to_string()),
+                conditions: Vec::new(),
+            })
+            .add_operation_on(
+                TargetNode::PrimaryOnly,
+                DropColumn {
+                    column: "legacy_flag".to_string(),
+                    if_exists: false,
+                    cascade: false,
+                },
+            );
+        migration
+    }
+
+    #[test]
+    fn excludes_primary_only_operations_on_replicas() {
+        assert_eq!(
+            migration().generate_for_node(Node::Replica).unwrap(),
+            vec!["UPDATE users SET status = 'active';"]
+        );
+    }
+
+    #[test]
+    fn primary_runs_every_operation() {
+        assert_eq!(
+            migration().generate_for_node(Node::Primary).unwrap(),
+            vec![
+                "UPDATE users SET status = 'active';",
+                "ALTER TABLE users DROP COLUMN legacy_flag;",
+            ]
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+
+use super::Migration;
+use crate::error::MigrationError;
+
+impl Migration {
+    /// Replaces `${token}` placeholders in the operations' literal values with
+    /// the matching entry from `overrides`, e.g. to use different defaults in
+    /// staging and production. Fails on the first token without an entry.
+    pub fn apply_overrides(
+        &mut self,
+        overrides: &HashMap<String, String>,
+    ) -> Result<(), MigrationError> {
+        for operation in &mut self.operations {
+            operation.apply_overrides(overrides)?;
+        }
+        Ok(())
+    }
+}
+
+/// Substitutes every `${token}` in `text`. An unterminated `${` is kept as-is.
+pub(crate) fn substitute(
+    text: &str,
+    overrides: &HashMap<String, String>,
+) -> Result<String, MigrationError> {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("${") {
+        let Some(length) = rest[start + 2..].find('}') else {
+            break;
+        };
+        let token = &rest[start + 2..start + 2 + length];
+        let value = overrides
+            .get(token)
+            .ok_or_else(|| MigrationError::UndefinedOverride {
+                token: token.to_string(),
+            })?;
+        result.push_str(&rest[..start]);
+        result.push_str(value);
+        rest = &rest[start + 2 + length + 1..];
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::PostgresDialect;
+    use crate::migration::{Operator, UpdateColumnData, UpdateValue, WhereCondition};
+
+    fn overrides(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(token, value)| (token.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn migration() -> Migration {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration.add_operation(UpdateColumnData {
+            column: "status".to_string(),
+            value: UpdateValue::Fixed("'${default_status}'".to_string()),
+            conditions: Vec::new(),
+        });
+        migration
+    }
+
+    #[test]
+    fn overriding_a_token_changes_the_generated_value() {
+        let mut staging = migration();
+        staging
+            .apply_overrides(&overrides(&[("default_status", "trial")]))
+            .unwrap();
+        let mut production = migration();
+        production
+            .apply_overrides(&overrides(&[("default_status", "active")]))
+            .unwrap();
+
+        assert_eq!(
+            staging.generate_sql().unwrap(),
+            vec!["UPDATE users SET status = 'trial';"]
+        );
+        assert_eq!(
+            production.generate_sql().unwrap(),
+            vec!["UPDATE users SET status = 'active';"]
+        );
+    }
+
+    #[test]
+    fn overrides_condition_values() {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration.add_operation(UpdateColumnData {
+            column: "status".to_string(),
+            value: UpdateValue::Literal("inactive".to_string()),
+            conditions: vec![WhereCondition {
+                column: "last_login".to_string(),
+                operator: Operator::Lt,
+                value: UpdateValue::Literal("${cutoff}".to_string()),
+            }],
+        });
+        migration
+            .apply_overrides(&overrides(&[("cutoff", "2024-01-01")]))
+            .unwrap();
+        assert_eq!(
+            migration.generate_sql().unwrap(),
+            vec!["UPDATE users SET status = 'inactive' WHERE last_login < '2024-01-01';"]
+        );
+    }
+
+    #[test]
+    fn undefined_tokens_are_an_error() {
+        assert_eq!(
+            migration().apply_overrides(&HashMap::new()),
+            Err(MigrationError::UndefinedOverride {
+                token: "default_status".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn leaves_unterminated_placeholders_alone() {
+        assert_eq!(
+            substitute("${a}-${b", &overrides(&[("a", "1")])).unwrap(),
+            "1-${b"
+        );
+    }
+}
@@ -0,0 +1,232 @@
+use super::Migration;
+use crate::error::MigrationError;
+
+/// A value bound to a placeholder instead of being written into the SQL.
+///
+/// Only [`UpdateValue::Literal`](super::UpdateValue::Literal) values are
+/// bound, passed on unquoted exactly as they appear in the operation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Param(pub String);
+
+impl Migration {
+    /// Like [`Migration::generate_sql`], but with literals replaced by the
+    /// dialect's placeholders and returned alongside each statement in bind
+    /// order. Statements with nothing to bind get an empty list, and come
+    /// one per entry like those of [`Migration::generate_sql`].
+    pub fn generate_parameterized(&self) -> Result<Vec<(String, Vec<Param>)>, MigrationError> {
+        let table = self.table.to_string();
+        let mut statements = Vec::new();
+        for operation in &self.operations {
+            match operation.generate_parameterized(&table, self.dialect.as_ref())? {
+                Some(statement) => statements.push(statement),
+                None => statements.extend(
+                    self.generate_operation(operation.as_ref())?
+                        .into_iter()
+                        .map(|statement| (statement.sql, Vec::new())),
+                ),
+            }
+        }
+        Ok(statements)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_type::{ColumnOptions, DataType};
+    use crate::dialect::{Dialect, MssqlDialect, MySqlDialect, PostgresDialect};
+    use crate::migration::{
+        ChangeColumnType, DeleteRows, DropColumn, Operator, UpdateColumnData, UpdateValue,
+        WhereCondition,
+    };
+
+    fn migration(dialect: Box<dyn Dialect>) -> Migration {
+        let mut migration = Migration::new("users", dialect);
+        migration
+            .add_operation(UpdateColumnData {
+                column: "status".to_string(),
+                value: UpdateValue::Literal("active".to_string()),
+                conditions: vec![
+                    WhereCondition {
+                        column: "status".to_string(),
+                        operator: Operator::Eq,
+                        value: UpdateValue::Literal("pending".to_string()),
+                    },
+                    WhereCondition {
+                        column: "created_at".to_string(),
+                        operator: Operator::Lt,
+                        value: UpdateValue::Literal("2024-01-01".to_string()),
+                    },
+                ],
+            })
+            .add_operation(DropColumn {
+                column: "legacy_flag".to_string(),
+                if_exists: false,
+                cascade: false,
+            });
+        migration
+    }
+
+    fn params(values: &[&str]) -> Vec<Param> {
+        values
+            .iter()
+            .map(|value| Param(value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn numbers_placeholders_on_postgres() {
+        assert_eq!(
+            migration(Box::new(PostgresDialect::new()))
+                .generate_parameterized()
+                .unwrap(),
+            vec![
+                (
+                    "UPDATE users SET status = $1 WHERE status = $2 AND created_at < $3;"
+                        .to_string(),
+                    params(&["active", "pending", "2024-01-01"])
+                ),
+                (
+                    "ALTER TABLE users DROP COLUMN legacy_flag;".to_string(),
+                    Vec::new()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn uses_question_marks_on_mysql() {
+        let statements = migration(Box::new(MySqlDialect::new()))
+            .generate_parameterized()
+            .unwrap();
+        assert_eq!(
+            statements[0],
+            (
+                "UPDATE users SET status = ? WHERE status = ? AND created_at < ?;".to_string(),
+                params(&["active", "pending", "2024-01-01"])
+            )
+        );
+    }
+
+    #[test]
+    fn names_parameters_on_mssql() {
+        let statements = migration(Box::new(MssqlDialect::new()))
+            .generate_parameterized()
+            .unwrap();
+        assert_eq!(
+            statements[0],
+            (
+                "UPDATE users SET status = @p1 WHERE status = @p2 AND created_at < @p3;"
+                    .to_string(),
+                params(&["active", "pending", "2024-01-01"])
+            )
+        );
+    }
+
+    #[test]
+    fn keeps_fixed_sql_in_the_statement() {
+        let update = UpdateColumnData {
+            column: "last_seen_at".to_string(),
+            value: UpdateValue::Fixed("now()".to_string()),
+            conditions: vec![
+                WhereCondition {
+                    column: "status".to_string(),
+                    operator: Operator::Eq,
+                    value: UpdateValue::Literal("active".to_string()),
+                },
+                WhereCondition {
+                    column: "login_count".to_string(),
+                    operator: Operator::Gt,
+                    value: UpdateValue::Fixed("0".to_string()),
+                },
+            ],
+        };
+        assert_eq!(
+            PostgresDialect::new().update_column_data_parameterized(
+                "users",
+                &update.column,
+                &update.value,
+                &update.conditions
+            ),
+            (
+                "UPDATE users SET last_seen_at = now() WHERE status = $1 AND login_count > 0;"
+                    .to_string(),
+                params(&["active"])
+            )
+        );
+    }
+
+    #[test]
+    fn binds_delete_conditions() {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration.add_operation(DeleteRows {
+            conditions: vec![
+                WhereCondition {
+                    column: "deleted_at".to_string(),
+                    operator: Operator::Lt,
+                    value: UpdateValue::Literal("2024-01-01".to_string()),
+                },
+                WhereCondition {
+                    column: "updated_at".to_string(),
+                    operator: Operator::Lt,
+                    value: UpdateValue::Column("deleted_at".to_string()),
+                },
+            ],
+            allow_full_table_delete: false,
+        });
+        assert_eq!(
+            migration.generate_parameterized().unwrap(),
+            vec![(
+                "DELETE FROM users WHERE deleted_at < $1 AND updated_at < deleted_at;".to_string(),
+                params(&["2024-01-01"])
+            )]
+        );
+    }
+
+    #[test]
+    fn splits_multi_statement_ddl() {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration.add_operation(ChangeColumnType {
+            column: "email".to_string(),
+            data_type: DataType::Text,
+            options: ColumnOptions {
+                unique: Some(true),
+                ..ColumnOptions::default()
+            },
+            previous: None,
+            position: None,
+        });
+        let statements = migration.generate_parameterized().unwrap();
+        assert_eq!(
+            statements,
+            migration
+                .generate_sql()
+                .unwrap()
+                .into_iter()
+                .map(|statement| (statement.sql, Vec::new()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(statements.len(), 2);
+    }
+
+    #[test]
+    fn does_not_bind_column_references() {
+        let update = UpdateColumnData {
+            column: "display_name".to_string(),
+            value: UpdateValue::Column("username".to_string()),
+            conditions: Vec::new(),
+        };
+        assert_eq!(
+            PostgresDialect::new().update_column_data_parameterized(
+                "users",
+                &update.column,
+                &update.value,
+                &update.conditions
+            ),
+            (
+                "UPDATE users SET display_name = username;".to_string(),
+                Vec::new()
+            )
+        );
+    }
+}
@@ -0,0 +1,322 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{LockLevel, MigrationStep, RiskProfile};
+use crate::dialect::Dialect;
+use crate::error::MigrationError;
+
+/// The rows a partition holds. Values are raw SQL literals such as
+/// `'2024-01-01'`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PartitionBound {
+    /// `FROM` is inclusive and `TO` exclusive.
+    Range {
+        from: String,
+        to: String,
+    },
+    List(Vec<String>),
+    Default,
+}
+
+impl fmt::Display for PartitionBound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PartitionBound::Range { from, to } => {
+                write!(f, "FOR VALUES FROM ({}) TO ({})", from, to)
+            }
+            PartitionBound::List(values) => write!(f, "FOR VALUES IN ({})", values.join(", ")),
+            PartitionBound::Default => write!(f, "DEFAULT"),
+        }
+    }
+}
+
+/// Attaches an existing table as a partition of the migration's table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AttachPartition {
+    pub name: String,
+    pub bounds: PartitionBound,
+}
+
+impl MigrationStep for AttachPartition {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        dialect.attach_partition(table, &self.name, &self.bounds)
+    }
+}
+
+/// Creates a new, empty partition of the migration's table.
+///
+/// MySQL partitions a range by upper bounds only, so the range's `from` is
+/// implied by the previous partition and not written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CreatePartition {
+    pub name: String,
+    pub bounds: PartitionBound,
+}
+
+impl MigrationStep for CreatePartition {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        dialect.create_partition(table, &self.name, &self.bounds)
+    }
+
+    fn rollback_sql(&self, table: &str, dialect: &dyn Dialect) -> Option<String> {
+        dialect.drop_partition(table, &self.name).ok()
+    }
+}
+
+/// Drops a partition of the migration's table together with its rows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DropPartition {
+    pub name: String,
+}
+
+impl MigrationStep for DropPartition {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        dialect.drop_partition(table, &self.name)
+    }
+
+    fn risk_profile(&self) -> RiskProfile {
+        RiskProfile {
+            destructive: true,
+            ..RiskProfile::default()
+        }
+    }
+}
+
+/// Detaches a partition from the migration's table, leaving it as a
+/// standalone table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DetachPartition {
+    pub name: String,
+    /// Detach without blocking queries on the parent (Postgres 14+). Cannot
+    /// run inside a transaction.
+    pub concurrently: bool,
+}
+
+impl MigrationStep for DetachPartition {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        dialect.detach_partition(table, &self.name, self.concurrently)
+    }
+
+    fn transactional(&self) -> bool {
+        !self.concurrently
+    }
+
+    fn risk_profile(&self) -> RiskProfile {
+        RiskProfile {
+            lock_level: if self.concurrently {
+                LockLevel::Shared
+            } else {
+                LockLevel::Exclusive
+            },
+            ..RiskProfile::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{MySqlDialect, PostgresDialect};
+
+    #[test]
+    fn attaches_range_partition() {
+        let attach = AttachPartition {
+            name: "events_2024_01".to_string(),
+            bounds: PartitionBound::Range {
+                from: "'2024-01-01'".to_string(),
+                to: "'2024-02-01'".to_string(),
+            },
+        };
+        assert_eq!(
+            attach
+                .generate_sql("events", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE events ATTACH PARTITION events_2024_01 \
+             FOR VALUES FROM ('2024-01-01') TO ('2024-02-01');"
+        );
+    }
+
+    #[test]
+    fn attaches_list_and_default_partitions() {
+        let attach = AttachPartition {
+            name: "customers_eu".to_string(),
+            bounds: PartitionBound::List(vec!["'de'".to_string(), "'fr'".to_string()]),
+        };
+        assert_eq!(
+            attach
+                .generate_sql("customers", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE customers ATTACH PARTITION customers_eu FOR VALUES IN ('de', 'fr');"
+        );
+
+        let attach = AttachPartition {
+            name: "customers_other".to_string(),
+            bounds: PartitionBound::Default,
+        };
+        assert_eq!(
+            attach
+                .generate_sql("customers", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE customers ATTACH PARTITION customers_other DEFAULT;"
+        );
+    }
+
+    #[test]
+    fn detaches_concurrently_outside_transactions() {
+        let detach = DetachPartition {
+            name: "events_2023_12".to_string(),
+            concurrently: true,
+        };
+        assert_eq!(
+            detach
+                .generate_sql("events", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE events DETACH PARTITION events_2023_12 CONCURRENTLY;"
+        );
+        assert!(!detach.transactional());
+
+        let detach = DetachPartition {
+            concurrently: false,
+            ..detach
+        };
+        assert_eq!(
+            detach
+                .generate_sql("events", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER TABLE events DETACH PARTITION events_2023_12;"
+        );
+        assert!(detach.transactional());
+    }
+
+    fn monthly(name: &str, from: &str, to: &str) -> CreatePartition {
+        CreatePartition {
+            name: name.to_string(),
+            bounds: PartitionBound::Range {
+                from: from.to_string(),
+                to: to.to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn creates_range_partition() {
+        let create = monthly("events_2024_02", "'2024-02-01'", "'2024-03-01'");
+        let dialect = PostgresDialect::new();
+        assert_eq!(
+            create.generate_sql("events", &dialect).unwrap(),
+            "CREATE TABLE events_2024_02 PARTITION OF events \
+             FOR VALUES FROM ('2024-02-01') TO ('2024-03-01');"
+        );
+        assert_eq!(
+            create.rollback_sql("events", &dialect).unwrap(),
+            "DROP TABLE events_2024_02;"
+        );
+        assert_eq!(
+            create.generate_sql("events", &MySqlDialect::new()).unwrap(),
+            "ALTER TABLE events ADD PARTITION \
+             (PARTITION events_2024_02 VALUES LESS THAN ('2024-03-01'));"
+        );
+    }
+
+    #[test]
+    fn creates_list_partition() {
+        let create = CreatePartition {
+            name: "customers_eu".to_string(),
+            bounds: PartitionBound::List(vec!["'de'".to_string(), "'fr'".to_string()]),
+        };
+        assert_eq!(
+            create
+                .generate_sql("customers", &PostgresDialect::new())
+                .unwrap(),
+            "CREATE TABLE customers_eu PARTITION OF customers FOR VALUES IN ('de', 'fr');"
+        );
+        assert_eq!(
+            create
+                .generate_sql("customers", &MySqlDialect::new())
+                .unwrap(),
+            "ALTER TABLE customers ADD PARTITION (PARTITION customers_eu VALUES IN ('de', 'fr'));"
+        );
+    }
+
+    #[test]
+    fn mysql_has_no_default_partition() {
+        let create = CreatePartition {
+            name: "customers_other".to_string(),
+            bounds: PartitionBound::Default,
+        };
+        assert_eq!(
+            create.generate_sql("customers", &MySqlDialect::new()),
+            Err(MigrationError::unsupported("mysql", "DEFAULT PARTITION"))
+        );
+    }
+
+    #[test]
+    fn drops_partition() {
+        let drop = DropPartition {
+            name: "events_2023_01".to_string(),
+        };
+        assert_eq!(
+            drop.generate_sql("events", &PostgresDialect::new())
+                .unwrap(),
+            "DROP TABLE events_2023_01;"
+        );
+        assert_eq!(
+            drop.generate_sql("events", &MySqlDialect::new()).unwrap(),
+            "ALTER TABLE events DROP PARTITION events_2023_01;"
+        );
+        assert!(drop.risk_profile().destructive);
+    }
+
+    #[test]
+    fn mysql_rejects_attach_and_detach() {
+        let detach = DetachPartition {
+            name: "events_2023_12".to_string(),
+            concurrently: false,
+        };
+        assert_eq!(
+            detach.generate_sql("events", &MySqlDialect::new()),
+            Err(MigrationError::Unsupported {
+                dialect: "mysql".to_string(),
+                operation: "DETACH PARTITION".to_string(),
+            })
+        );
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use super::*;
+
+    #[test]
+    fn partition_operations_round_trip() {
+        let attach = AttachPartition {
+            name: "events_2024_01".to_string(),
+            bounds: PartitionBound::Range {
+                from: "'2024-01-01'".to_string(),
+                to: "'2024-02-01'".to_string(),
+            },
+        };
+        let json = serde_json::to_string(&attach).unwrap();
+        assert_eq!(
+            serde_json::from_str::<AttachPartition>(&json).unwrap(),
+            attach
+        );
+
+        let detach = DetachPartition {
+            name: "events_2023_12".to_string(),
+            concurrently: true,
+        };
+        let json = serde_json::to_string(&detach).unwrap();
+        assert_eq!(
+            serde_json::from_str::<DetachPartition>(&json).unwrap(),
+            detach
+        );
+    }
+}
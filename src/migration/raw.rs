@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::overrides::substitute;
+use super::MigrationStep;
+use crate::dialect::Dialect;
+use crate::error::MigrationError;
+
+/// Hand-written SQL passed through verbatim, for anything the generators do
+/// not cover. `dialect_up` replaces `up` for the dialects it names, keyed by
+/// [`Dialect::name`]. `down` is used as the rollback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RawSql {
+    pub up: String,
+    pub down: Option<String>,
+    pub dialect_up: HashMap<String, String>,
+    /// Only emit the SQL for these dialects, by [`Dialect::name`]; others
+    /// render nothing.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dialects: Option<Vec<String>>,
+}
+
+impl RawSql {
+    fn applies_to(&self, dialect: &dyn Dialect) -> bool {
+        self.dialects
+            .as_ref()
+            .is_none_or(|dialects| dialects.iter().any(|name| name == dialect.name()))
+    }
+}
+
+impl MigrationStep for RawSql {
+    fn generate_sql(&self, _table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        if !self.applies_to(dialect) {
+            return Ok(String::new());
+        }
+        Ok(self
+            .dialect_up
+            .get(dialect.name())
+            .unwrap_or(&self.up)
+            .clone())
+    }
+
+    fn rollback_sql(&self, _table: &str, dialect: &dyn Dialect) -> Option<String> {
+        if !self.applies_to(dialect) {
+            return Some(String::new());
+        }
+        self.down.clone()
+    }
+
+    fn apply_overrides(
+        &mut self,
+        overrides: &HashMap<String, String>,
+    ) -> Result<(), MigrationError> {
+        self.up = substitute(&self.up, overrides)?;
+        if let Some(down) = &mut self.down {
+            *down = substitute(down, overrides)?;
+        }
+        for sql in self.dialect_up.values_mut() {
+            *sql = substitute(sql, overrides)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{MySqlDialect, PostgresDialect};
+    use crate::migration::{DropColumn, Migration};
+
+    fn analyze() -> RawSql {
+        RawSql {
+            up: "ANALYZE orders;".to_string(),
+            down: None,
+            dialect_up: HashMap::from([("mysql".to_string(), "ANALYZE TABLE orders;".to_string())]),
+            dialects: None,
+        }
+    }
+
+    #[test]
+    fn keeps_its_place_among_generated_operations() {
+        let mut migration = Migration::new("orders", Box::new(PostgresDialect::new()));
+        migration
+            .add_operation(DropColumn {
+                column: "legacy_total".to_string(),
+                if_exists: false,
+                cascade: false,
+            })
+            .add_operation(analyze())
+            .add_operation(DropColumn {
+                column: "legacy_tax".to_string(),
+                if_exists: false,
+                cascade: false,
+            });
+
+        assert_eq!(
+            migration.generate_sql().unwrap(),
+            vec![
+                "ALTER TABLE orders DROP COLUMN legacy_total;",
+                "ANALYZE orders;",
+                "ALTER TABLE orders DROP COLUMN legacy_tax;",
+            ]
+        );
+    }
+
+    #[test]
+    fn prefers_the_dialect_specific_variant() {
+        assert_eq!(
+            analyze()
+                .generate_sql("orders", &MySqlDialect::new())
+                .unwrap(),
+            "ANALYZE TABLE orders;"
+        );
+    }
+    #[test]
+    fn emits_nothing_for_dialects_it_is_not_limited_to() {
+        let vacuum = RawSql {
+            up: "VACUUM ANALYZE orders;".to_string(),
+            down: None,
+            dialect_up: HashMap::new(),
+            dialects: Some(vec!["postgres".to_string()]),
+        };
+        assert_eq!(
+            vacuum
+                .generate_sql("orders", &PostgresDialect::new())
+                .unwrap(),
+            "VACUUM ANALYZE orders;"
+        );
+
+        let mut migration = Migration::new("orders", Box::new(MySqlDialect::new()));
+        migration.add_operation(vacuum).add_operation(DropColumn {
+            column: "legacy_total".to_string(),
+            if_exists: false,
+            cascade: false,
+        });
+        assert_eq!(
+            migration.generate_sql().unwrap(),
+            vec!["ALTER TABLE orders DROP COLUMN legacy_total;"]
+        );
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use super::*;
+
+    #[test]
+    fn raw_sql_round_trips() {
+        let raw = RawSql {
+            up: "CREATE EXTENSION citext;".to_string(),
+            down: Some("DROP EXTENSION citext;".to_string()),
+            dialect_up: HashMap::new(),
+            dialects: None,
+        };
+        let json = serde_json::to_string(&raw).unwrap();
+        assert_eq!(serde_json::from_str::<RawSql>(&json).unwrap(), raw);
+    }
+}
@@ -0,0 +1,142 @@
+use super::Migration;
+use crate::error::MigrationError;
+
+/// The strongest lock a statement is expected to take on the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LockLevel {
+    /// Only the rows being written are locked.
+    Row,
+    /// Reads continue, writes wait.
+    Shared,
+    /// Both reads and writes wait.
+    Exclusive,
+}
+
+/// What a reviewer should know about a statement before running it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RiskProfile {
+    /// Data is lost and cannot be recovered by rolling back the schema.
+    pub destructive: bool,
+    /// The table may be rewritten, which is slow on large tables.
+    pub requires_rewrite: bool,
+    pub lock_level: LockLevel,
+}
+
+impl Default for RiskProfile {
+    /// Most operations are `ALTER TABLE`s that briefly take an exclusive lock.
+    fn default() -> Self {
+        RiskProfile {
+            destructive: false,
+            requires_rewrite: false,
+            lock_level: LockLevel::Exclusive,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnotatedStatement {
+    pub sql: String,
+    pub risk: RiskProfile,
+}
+
+impl Migration {
+    /// Generates the statements without running anything, each paired with
+    /// the risk profile of the operation that produced it, narrowed to
+    /// whether that statement itself is destructive.
+    pub fn dry_run(&self) -> Result<Vec<AnnotatedStatement>, MigrationError> {
+        let mut annotated = Vec::new();
+        for operation in &self.operations {
+            let risk = operation.risk_profile();
+            for statement in self.generate_operation(operation.as_ref())? {
+                annotated.push(AnnotatedStatement {
+                    risk: RiskProfile {
+                        destructive: statement.destructive,
+                        ..risk
+                    },
+                    sql: statement.sql,
+                });
+            }
+        }
+        Ok(annotated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_type::{ColumnOptions, DataType};
+    use crate::dialect::PostgresDialect;
+    use crate::migration::{
+        ChangeColumnType, CreateIndex, DropColumn, DropTable, Operator, UpdateColumnData,
+        UpdateValue, WhereCondition,
+    };
+
+    fn update(conditions: Vec<WhereCondition>) -> UpdateColumnData {
+        UpdateColumnData {
+            column: "status".to_string(),
+            value: UpdateValue::Fixed("'archived'".to_string()),
+            conditions,
+        }
+    }
+
+    #[test]
+    fn flags_updates_without_where_as_destructive() {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration
+            .add_operation(update(Vec::new()))
+            .add_operation(update(vec![WhereCondition {
+                column: "status".to_string(),
+                operator: Operator::Eq,
+                value: UpdateValue::Fixed("'inactive'".to_string()),
+            }]));
+
+        let statements = migration.dry_run().unwrap();
+        assert_eq!(statements[0].sql, "UPDATE users SET status = 'archived';");
+        assert!(statements[0].risk.destructive);
+        assert_eq!(statements[0].risk.lock_level, LockLevel::Row);
+        assert!(!statements[1].risk.destructive);
+    }
+
+    #[test]
+    fn annotates_schema_changes() {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration
+            .add_operation(DropColumn {
+                column: "legacy_flag".to_string(),
+                if_exists: false,
+                cascade: false,
+            })
+            .add_operation(ChangeColumnType {
+                column: "age".to_string(),
+                data_type: DataType::BigInt,
+                options: ColumnOptions::default(),
+                previous: None,
+                position: None,
+            })
+            .add_operation(CreateIndex {
+                name: None,
+                columns: vec!["email".into()],
+                unique: false,
+                conditional_on_rows: None,
+                concurrently: false,
+                if_not_exists: false,
+                where_clause: None,
+            })
+            .add_operation(DropTable {
+                if_exists: false,
+                cascade: false,
+            });
+
+        let risks: Vec<RiskProfile> = migration
+            .dry_run()
+            .unwrap()
+            .into_iter()
+            .map(|statement| statement.risk)
+            .collect();
+        assert!(risks[0].destructive);
+        assert!(risks[1].requires_rewrite && !risks[1].destructive);
+        assert_eq!(risks[2].lock_level, LockLevel::Shared);
+        assert!(risks[3].destructive);
+        assert_eq!(risks[3].lock_level, LockLevel::Exclusive);
+    }
+}
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use super::{Migration, Statement, TableRef};
+use crate::dialect::Dialect;
+use crate::error::MigrationError;
+
+/// Migrations for several tables that share one dialect, generated in the
+/// order they were added.
+pub struct Schema {
+    dialect: Arc<dyn Dialect>,
+    migrations: Vec<Migration>,
+}
+
+impl Schema {
+    pub fn new(dialect: Box<dyn Dialect>) -> Self {
+        Schema {
+            dialect: Arc::from(dialect),
+            migrations: Vec::new(),
+        }
+    }
+
+    /// Starts a migration for `table` after every migration added so far.
+    /// Calling it again for the same table starts a separate migration, so
+    /// changes to different tables can be interleaved.
+    pub fn table(&mut self, table: impl Into<TableRef>) -> &mut Migration {
+        self.migrations.push(Migration::with_shared_dialect(
+            table.into(),
+            self.dialect.clone(),
+        ));
+        self.migrations.last_mut().unwrap()
+    }
+
+    pub fn migrations(&self) -> &[Migration] {
+        &self.migrations
+    }
+
+    /// The statements of every migration, in insertion order.
+    pub fn generate_sql(&self) -> Result<Vec<Statement>, MigrationError> {
+        let mut statements = Vec::new();
+        for migration in &self.migrations {
+            statements.extend(migration.generate_sql()?);
+        }
+        Ok(statements)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::PostgresDialect;
+    use crate::migration::DropColumn;
+
+    fn drop(column: &str) -> DropColumn {
+        DropColumn {
+            column: column.to_string(),
+            if_exists: false,
+            cascade: false,
+        }
+    }
+
+    #[test]
+    fn concatenates_tables_in_insertion_order() {
+        let mut schema = Schema::new(Box::new(PostgresDialect::new()));
+        schema.table("users").add_operation(drop("legacy_flag"));
+        schema
+            .table("orders")
+            .add_operation(drop("coupon"))
+            .add_operation(drop("discount"));
+        schema.table("users").add_operation(drop("legacy_source"));
+
+        assert_eq!(
+            schema.generate_sql().unwrap(),
+            vec![
+                "ALTER TABLE users DROP COLUMN legacy_flag;",
+                "ALTER TABLE orders DROP COLUMN coupon;",
+                "ALTER TABLE orders DROP COLUMN discount;",
+                "ALTER TABLE users DROP COLUMN legacy_source;",
+            ]
+        );
+        assert_eq!(schema.migrations().len(), 3);
+    }
+}
@@ -0,0 +1,139 @@
+use std::fs;
+use std::path::Path;
+
+use super::{Migration, SqlStyle};
+use crate::error::MigrationError;
+
+/// How a migration is laid out as a `.sql` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptOptions {
+    /// End the script with `\n`, as POSIX text files do.
+    pub trailing_newline: bool,
+    /// Written between statements, e.g. `\n\n` for a blank line.
+    pub separator: String,
+    /// Applied to each statement; by default only adds missing `;`s.
+    pub style: SqlStyle,
+}
+
+impl Default for ScriptOptions {
+    fn default() -> Self {
+        ScriptOptions {
+            trailing_newline: true,
+            separator: "\n".to_string(),
+            style: SqlStyle::default(),
+        }
+    }
+}
+
+impl Migration {
+    /// The statements as one script, one statement per line by default.
+    /// Statements missing their terminating `;`, such as hand-written
+    /// [`RawSql`](super::RawSql), get one unless the style says otherwise.
+    pub fn generate_script(&self, options: &ScriptOptions) -> Result<String, MigrationError> {
+        let statements = self.generate_sql_with_style(&options.style)?;
+        let mut script = statements.join(&options.separator);
+        if options.trailing_newline {
+            script.push('\n');
+        }
+        Ok(script)
+    }
+
+    /// Writes [`Migration::generate_script`] to `path`, replacing any
+    /// existing file.
+    pub fn write_to_file(
+        &self,
+        path: impl AsRef<Path>,
+        options: &ScriptOptions,
+    ) -> Result<(), MigrationError> {
+        let path = path.as_ref();
+        let script = self.generate_script(options)?;
+        fs::write(path, script).map_err(|error| MigrationError::Io {
+            path: path.to_path_buf(),
+            message: error.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::PostgresDialect;
+    use crate::migration::{DropColumn, RawSql};
+
+    fn migration() -> Migration {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration
+            .add_operation(DropColumn {
+                column: "a".to_string(),
+                if_exists: false,
+                cascade: false,
+            })
+            .add_operation(DropColumn {
+                column: "b".to_string(),
+                if_exists: false,
+                cascade: false,
+            });
+        migration
+    }
+
+    #[test]
+    fn ends_with_newline_by_default() {
+        assert_eq!(
+            migration()
+                .generate_script(&ScriptOptions::default())
+                .unwrap(),
+            "ALTER TABLE users DROP COLUMN a;\nALTER TABLE users DROP COLUMN b;\n"
+        );
+    }
+
+    #[test]
+    fn omits_trailing_newline_when_disabled() {
+        let options = ScriptOptions {
+            trailing_newline: false,
+            ..ScriptOptions::default()
+        };
+        assert_eq!(
+            migration().generate_script(&options).unwrap(),
+            "ALTER TABLE users DROP COLUMN a;\nALTER TABLE users DROP COLUMN b;"
+        );
+    }
+
+    #[test]
+    fn writes_script_to_file() {
+        let path = std::env::temp_dir().join(format!("drift-script-{}.sql", std::process::id()));
+        let options = ScriptOptions {
+            trailing_newline: false,
+            ..ScriptOptions::default()
+        };
+        migration().write_to_file(&path, &options).unwrap();
+        let written = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(written, migration().generate_script(&options).unwrap());
+    }
+
+    #[test]
+    fn terminates_every_statement() {
+        let mut migration = migration();
+        migration.add_operation(RawSql {
+            up: "VACUUM ANALYZE users  \n".to_string(),
+            down: None,
+            dialect_up: Default::default(),
+            dialects: None,
+        });
+        let options = ScriptOptions {
+            separator: "\n\n".to_string(),
+            ..ScriptOptions::default()
+        };
+        let script = migration.generate_script(&options).unwrap();
+        assert_eq!(
+            script,
+            "ALTER TABLE users DROP COLUMN a;\n\n\
+             ALTER TABLE users DROP COLUMN b;\n\n\
+             VACUUM ANALYZE users;\n"
+        );
+        assert!(script
+            .split(&options.separator)
+            .all(|statement| statement.trim_end().ends_with(';')));
+    }
+}
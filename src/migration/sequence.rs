@@ -0,0 +1,213 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{MigrationStep, RiskProfile};
+use crate::dialect::Dialect;
+use crate::error::MigrationError;
+
+/// Moves the table's id sequence, e.g. after importing rows with explicit ids.
+///
+/// MySQL has no named sequences and sets the table's `AUTO_INCREMENT`
+/// instead, ignoring `sequence`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RestartSequence {
+    pub sequence: String,
+    /// The next value to hand out. Without it Postgres restarts from the
+    /// sequence's start value, while MySQL moves to one past the highest id.
+    pub value: Option<u64>,
+}
+
+impl MigrationStep for RestartSequence {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(dialect.restart_sequence(table, &self.sequence, self.value))
+    }
+}
+
+/// Creates a standalone sequence, e.g. for ids shared between tables.
+/// Only Postgres has named sequences of this kind.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CreateSequence {
+    pub name: String,
+    pub start: Option<i64>,
+    pub increment: Option<i64>,
+    /// A column of the migration's table; dropping the column drops the
+    /// sequence with it.
+    pub owned_by: Option<String>,
+}
+
+impl MigrationStep for CreateSequence {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        dialect.create_sequence(table, self)
+    }
+
+    fn rollback_sql(&self, _table: &str, dialect: &dyn Dialect) -> Option<String> {
+        dialect.drop_sequence(&self.name, false).ok()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DropSequence {
+    pub name: String,
+    pub if_exists: bool,
+}
+
+impl MigrationStep for DropSequence {
+    fn generate_sql(&self, _table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        dialect.drop_sequence(&self.name, self.if_exists)
+    }
+
+    fn risk_profile(&self) -> RiskProfile {
+        RiskProfile {
+            destructive: true,
+            ..RiskProfile::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{MySqlDialect, PostgresDialect};
+
+    #[test]
+    fn restarts_postgres_sequence() {
+        let restart = RestartSequence {
+            sequence: "users_id_seq".to_string(),
+            value: Some(1000),
+        };
+        assert_eq!(
+            restart
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER SEQUENCE users_id_seq RESTART WITH 1000;"
+        );
+
+        let restart = RestartSequence {
+            value: None,
+            ..restart
+        };
+        assert_eq!(
+            restart
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "ALTER SEQUENCE users_id_seq RESTART;"
+        );
+    }
+
+    #[test]
+    fn sets_mysql_auto_increment() {
+        let restart = RestartSequence {
+            sequence: "users_id_seq".to_string(),
+            value: Some(1000),
+        };
+        assert_eq!(
+            restart.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "ALTER TABLE users AUTO_INCREMENT = 1000;"
+        );
+
+        let restart = RestartSequence {
+            value: None,
+            ..restart
+        };
+        assert_eq!(
+            restart.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "ALTER TABLE users AUTO_INCREMENT = 1;"
+        );
+    }
+    #[test]
+    fn creates_postgres_sequence_with_every_option() {
+        let create = CreateSequence {
+            name: "order_number_seq".to_string(),
+            start: Some(1000),
+            increment: Some(10),
+            owned_by: Some("order_number".to_string()),
+        };
+        assert_eq!(
+            create.generate_sql("orders", &PostgresDialect::new()).unwrap(),
+            "CREATE SEQUENCE order_number_seq INCREMENT BY 10 START WITH 1000 OWNED BY orders.order_number;"
+        );
+        assert_eq!(
+            create
+                .rollback_sql("orders", &PostgresDialect::new())
+                .unwrap(),
+            "DROP SEQUENCE order_number_seq;"
+        );
+
+        let create = CreateSequence {
+            start: None,
+            increment: None,
+            owned_by: None,
+            ..create
+        };
+        assert_eq!(
+            create
+                .generate_sql("orders", &PostgresDialect::new())
+                .unwrap(),
+            "CREATE SEQUENCE order_number_seq;"
+        );
+    }
+
+    #[test]
+    fn drops_postgres_sequence() {
+        let drop = DropSequence {
+            name: "order_number_seq".to_string(),
+            if_exists: true,
+        };
+        assert_eq!(
+            drop.generate_sql("orders", &PostgresDialect::new())
+                .unwrap(),
+            "DROP SEQUENCE IF EXISTS order_number_seq;"
+        );
+    }
+
+    #[test]
+    fn mysql_has_no_named_sequences() {
+        let create = CreateSequence {
+            name: "order_number_seq".to_string(),
+            start: None,
+            increment: None,
+            owned_by: None,
+        };
+        assert_eq!(
+            create.generate_sql("orders", &MySqlDialect::new()),
+            Err(MigrationError::unsupported("mysql", "CREATE SEQUENCE"))
+        );
+        assert_eq!(create.rollback_sql("orders", &MySqlDialect::new()), None);
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use super::*;
+
+    #[test]
+    fn restart_sequence_round_trips() {
+        let restart = RestartSequence {
+            sequence: "users_id_seq".to_string(),
+            value: Some(1000),
+        };
+        let json = serde_json::to_string(&restart).unwrap();
+        assert_eq!(
+            serde_json::from_str::<RestartSequence>(&json).unwrap(),
+            restart
+        );
+    }
+
+    #[test]
+    fn create_sequence_round_trips() {
+        let create = CreateSequence {
+            name: "order_number_seq".to_string(),
+            start: Some(1000),
+            increment: None,
+            owned_by: Some("order_number".to_string()),
+        };
+        let json = serde_json::to_string(&create).unwrap();
+        assert_eq!(
+            serde_json::from_str::<CreateSequence>(&json).unwrap(),
+            create
+        );
+    }
+}
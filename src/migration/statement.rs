@@ -0,0 +1,191 @@
+use std::fmt;
+
+use super::MigrationStep;
+
+/// A generated statement together with what a runner needs to know about
+/// it. Displays as its SQL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Statement {
+    pub sql: String,
+    /// Whether it may run inside a transaction. Operations mixing both kinds
+    /// of statements flag each one.
+    pub transactional: bool,
+    /// Whether it loses data; see [`RiskProfile::destructive`]. Like
+    /// `transactional`, this is set per statement.
+    ///
+    /// [`RiskProfile::destructive`]: super::RiskProfile::destructive
+    pub destructive: bool,
+    /// The [`MigrationStep::operation_name`] of the operation that produced
+    /// it.
+    ///
+    /// [`MigrationStep::operation_name`]: super::MigrationStep::operation_name
+    pub operation: &'static str,
+    /// The operation's [`MigrationStep::description`].
+    ///
+    /// [`MigrationStep::description`]: super::MigrationStep::description
+    pub description: String,
+}
+
+impl Statement {
+    /// `sql` generated by `step` on `table`, flagged like the step as a
+    /// whole: see [`MigrationStep::transactional`] and
+    /// [`MigrationStep::risk_profile`].
+    pub fn new<S: MigrationStep + ?Sized>(step: &S, table: &str, sql: impl Into<String>) -> Self {
+        Statement {
+            sql: sql.into(),
+            transactional: step.transactional(),
+            destructive: step.risk_profile().destructive,
+            operation: step.operation_name(),
+            description: step.description(table),
+        }
+    }
+
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+}
+
+impl fmt::Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.sql)
+    }
+}
+
+impl AsRef<str> for Statement {
+    fn as_ref(&self) -> &str {
+        &self.sql
+    }
+}
+
+impl PartialEq<str> for Statement {
+    fn eq(&self, other: &str) -> bool {
+        self.sql == other
+    }
+}
+
+impl PartialEq<&str> for Statement {
+    fn eq(&self, other: &&str) -> bool {
+        self.sql == *other
+    }
+}
+
+impl PartialEq<String> for Statement {
+    fn eq(&self, other: &String) -> bool {
+        &self.sql == other
+    }
+}
+
+impl PartialEq<Statement> for String {
+    fn eq(&self, other: &Statement) -> bool {
+        *self == other.sql
+    }
+}
+
+/// Each of `sql` as a [`Statement::new`] of `step`.
+pub(crate) fn of_step<S: MigrationStep + ?Sized>(
+    step: &S,
+    table: &str,
+    sql: Vec<String>,
+) -> Vec<Statement> {
+    sql.into_iter()
+        .map(|sql| Statement::new(step, table, sql))
+        .collect()
+}
+
+/// The SQL of `statements` on separate lines, for
+/// [`MigrationStep::generate_sql`] of operations with several.
+pub(crate) fn joined(statements: &[Statement]) -> String {
+    let sql: Vec<&str> = statements.iter().map(Statement::sql).collect();
+    sql.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_type::{Column, DataType};
+    use crate::dialect::PostgresDialect;
+    use crate::migration::{AddColumn, CreateIndex, DropColumn, MergeColumns, Migration};
+
+    #[test]
+    fn flags_each_statement() {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration
+            .add_operation(AddColumn {
+                column: Column {
+                    name: "nickname".to_string(),
+                    data_type: DataType::Text,
+                    nullable: true,
+                    auto_increment: false,
+                    generated: None,
+                    collation: None,
+                },
+                position: None,
+                if_not_exists: false,
+            })
+            .add_operation(CreateIndex {
+                name: None,
+                columns: vec!["nickname".into()],
+                unique: false,
+                conditional_on_rows: None,
+                concurrently: true,
+                if_not_exists: false,
+                where_clause: None,
+            })
+            .add_operation(DropColumn {
+                column: "legacy_flag".to_string(),
+                if_exists: false,
+                cascade: false,
+            });
+
+        let statements = migration.generate_sql().unwrap();
+        assert_eq!(
+            statements[0],
+            Statement {
+                sql: "ALTER TABLE users ADD COLUMN nickname TEXT;".to_string(),
+                transactional: true,
+                destructive: false,
+                operation: "AddColumn",
+                description: "add column nickname to users".to_string(),
+            }
+        );
+
+        assert_eq!(
+            statements[1].to_string(),
+            "CREATE INDEX CONCURRENTLY idx_users_nickname ON users (nickname);"
+        );
+        assert!(!statements[1].transactional);
+        assert!(!statements[1].destructive);
+
+        assert!(statements[2].destructive && statements[2].transactional);
+        assert_eq!(
+            statements[2].description,
+            "drop column legacy_flag from users"
+        );
+    }
+
+    #[test]
+    fn flags_statements_of_one_operation_separately() {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration.add_operation(MergeColumns {
+            sources: vec!["first_name".to_string(), "last_name".to_string()],
+            separator: " ".to_string(),
+            target: Column {
+                name: "full_name".to_string(),
+                data_type: DataType::Text,
+                nullable: true,
+                auto_increment: false,
+                generated: None,
+                collation: None,
+            },
+            drop_sources: true,
+        });
+
+        let destructive: Vec<bool> = migration
+            .generate_sql()
+            .unwrap()
+            .iter()
+            .map(|statement| statement.destructive)
+            .collect();
+        assert_eq!(destructive, [false, false, true, true]);
+    }
+}
@@ -0,0 +1,172 @@
+use super::Migration;
+use crate::dialect::keyword::{dollar_quoted_len, quoted_len, word_len};
+use crate::error::MigrationError;
+
+/// Layout applied to generated statements. Keywords are written by the
+/// dialect; see [`KeywordCase`](crate::dialect::KeywordCase).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqlStyle {
+    /// End each statement with `;`. Otherwise any trailing `;` is removed.
+    pub terminate_with_semicolon: bool,
+    /// Start `FROM`, `WHERE`, `VALUES` and an `UPDATE`'s `SET` on a new
+    /// line, unless they are nested in parentheses.
+    pub newline_between_clauses: bool,
+}
+
+impl Default for SqlStyle {
+    fn default() -> Self {
+        SqlStyle {
+            terminate_with_semicolon: true,
+            newline_between_clauses: false,
+        }
+    }
+}
+
+impl SqlStyle {
+    /// Formats one generated statement.
+    pub fn apply(&self, sql: &str) -> String {
+        let sql = sql.trim_end();
+        let sql = sql.strip_suffix(';').unwrap_or(sql).trim_end();
+        let update = sql
+            .get(..7)
+            .is_some_and(|start| start.eq_ignore_ascii_case("UPDATE "));
+
+        let mut formatted = String::with_capacity(sql.len() + 1);
+        let mut depth = 0usize;
+        let mut rest = sql;
+        while let Some(c) = rest.chars().next() {
+            let len = match c {
+                '\'' | '"' | '`' => quoted_len(rest, c),
+                '[' => quoted_len(rest, ']'),
+                '-' if rest.starts_with("--") => rest.find('\n').unwrap_or(rest.len()),
+                '$' => dollar_quoted_len(rest).unwrap_or(1),
+                '(' => {
+                    depth += 1;
+                    1
+                }
+                ')' => {
+                    depth = depth.saturating_sub(1);
+                    1
+                }
+                c if c.is_ascii_alphabetic() || c == '_' => {
+                    let len = word_len(rest);
+                    let word = &rest[..len];
+                    if self.newline_between_clauses
+                        && depth == 0
+                        && is_clause(word, update)
+                        && formatted.ends_with(' ')
+                    {
+                        formatted.pop();
+                        formatted.push('\n');
+                    }
+                    formatted.push_str(word);
+                    rest = &rest[len..];
+                    continue;
+                }
+                c => c.len_utf8(),
+            };
+            formatted.push_str(&rest[..len]);
+            rest = &rest[len..];
+        }
+
+        if self.terminate_with_semicolon {
+            formatted.push(';');
+        }
+        formatted
+    }
+}
+
+impl Migration {
+    /// Like [`Migration::generate_sql`], with each statement formatted by
+    /// `style`.
+    pub fn generate_sql_with_style(&self, style: &SqlStyle) -> Result<Vec<String>, MigrationError> {
+        Ok(self
+            .generate_sql()?
+            .iter()
+            .map(|statement| style.apply(statement.sql()))
+            .collect())
+    }
+}
+
+fn is_clause(word: &str, update: bool) -> bool {
+    ["FROM", "WHERE", "VALUES"]
+        .iter()
+        .any(|clause| word.eq_ignore_ascii_case(clause))
+        || (update && word.eq_ignore_ascii_case("SET"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_type::{Column, DataType};
+    use crate::dialect::PostgresDialect;
+    use crate::migration::{
+        AddColumn, Operator, RawSql, UpdateColumnData, UpdateValue, WhereCondition,
+    };
+
+    fn migration() -> Migration {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration
+            .add_operation(AddColumn {
+                column: Column::builder("Status")
+                    .data_type(DataType::Varchar(20))
+                    .not_null()
+                    .collation("C")
+                    .build(),
+                position: None,
+                if_not_exists: false,
+            })
+            .add_operation(UpdateColumnData {
+                column: "Status".to_string(),
+                value: UpdateValue::Fixed("'INACTIVE'".to_string()),
+                conditions: vec![WhereCondition {
+                    column: "last_login".to_string(),
+                    operator: Operator::Lt,
+                    value: UpdateValue::Fixed("NOW() - INTERVAL '1 YEAR'".to_string()),
+                }],
+            });
+        migration
+    }
+
+    #[test]
+    fn keeps_generated_uppercase_by_default() {
+        let migration = migration();
+        assert_eq!(
+            migration
+                .generate_sql_with_style(&SqlStyle::default())
+                .unwrap(),
+            migration.generate_sql().unwrap()
+        );
+    }
+
+    #[test]
+    fn breaks_clauses_onto_new_lines_without_semicolons() {
+        let style = SqlStyle {
+            terminate_with_semicolon: false,
+            newline_between_clauses: true,
+        };
+        assert_eq!(
+            migration().generate_sql_with_style(&style).unwrap()[1],
+            "UPDATE users\nSET \"Status\" = 'INACTIVE'\nWHERE last_login < NOW() - INTERVAL '1 YEAR'"
+        );
+    }
+
+    #[test]
+    fn leaves_dollar_quoted_bodies_alone() {
+        let style = SqlStyle {
+            newline_between_clauses: true,
+            ..SqlStyle::default()
+        };
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration.add_operation(RawSql {
+            up: "DO $body$ BEGIN DELETE FROM users WHERE id = $1; END $body$".to_string(),
+            down: None,
+            dialect_up: Default::default(),
+            dialects: None,
+        });
+        assert_eq!(
+            migration.generate_sql_with_style(&style).unwrap(),
+            vec!["DO $body$ BEGIN DELETE FROM users WHERE id = $1; END $body$;"]
+        );
+    }
+}
@@ -0,0 +1,325 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::statement::{joined, of_step};
+use super::{ColumnEffect, MigrationStep, RiskProfile, Statement};
+use crate::data_type::Column;
+use crate::dialect::{Dialect, Feature};
+use crate::error::MigrationError;
+
+/// The table a migration changes, optionally qualified by a schema (a
+/// database on MySQL). Statements name it as `schema.name`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TableRef {
+    pub schema: Option<String>,
+    pub name: String,
+}
+
+impl TableRef {
+    pub fn new(name: &str) -> Self {
+        TableRef {
+            schema: None,
+            name: name.to_string(),
+        }
+    }
+
+    pub fn qualified(schema: &str, name: &str) -> Self {
+        TableRef {
+            schema: Some(schema.to_string()),
+            name: name.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for TableRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.schema {
+            Some(schema) => write!(f, "{}.{}", schema, self.name),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+impl From<&str> for TableRef {
+    fn from(name: &str) -> Self {
+        TableRef::new(name)
+    }
+}
+
+impl From<(&str, &str)> for TableRef {
+    fn from((schema, name): (&str, &str)) -> Self {
+        TableRef::qualified(schema, name)
+    }
+}
+
+/// Creates a schema, or a database on MySQL. The migration's table is
+/// ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CreateSchema {
+    pub name: String,
+    pub if_not_exists: bool,
+}
+
+impl MigrationStep for CreateSchema {
+    fn generate_sql(&self, _table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        dialect.create_schema(&self.name, self.if_not_exists)
+    }
+
+    fn rollback_sql(&self, _table: &str, dialect: &dyn Dialect) -> Option<String> {
+        // The schema may have existed before, with tables of its own.
+        if self.if_not_exists {
+            return None;
+        }
+        dialect.drop_schema(&self.name).ok()
+    }
+}
+
+/// Creates the migration's table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CreateTable {
+    pub columns: Vec<Column>,
+    pub primary_key: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub options: TableOptions,
+}
+
+/// Table-level settings of [`CreateTable`]. Only MySQL and MariaDB have
+/// them; other dialects leave them out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TableOptions {
+    /// e.g. `InnoDB`.
+    pub engine: Option<String>,
+    /// The default character set of the table's columns, e.g. `utf8mb4`.
+    pub charset: Option<String>,
+    /// The default collation of the table's columns.
+    pub collation: Option<String>,
+}
+
+impl TableOptions {
+    fn is_empty(&self) -> bool {
+        self.engine.is_none() && self.charset.is_none() && self.collation.is_none()
+    }
+}
+
+impl MigrationStep for CreateTable {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(joined(&self.statements(table, dialect)?))
+    }
+
+    fn statements(
+        &self,
+        table: &str,
+        dialect: &dyn Dialect,
+    ) -> Result<Vec<Statement>, MigrationError> {
+        let sql = dialect.create_table(table, &self.columns, &self.primary_key, &self.options);
+        Ok(of_step(self, table, sql))
+    }
+
+    fn ignored_options(&self, dialect: &dyn Dialect) -> Vec<&'static str> {
+        if !self.options.is_empty() && !dialect.supports(Feature::TableOptions) {
+            vec!["table options"]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn rollback_sql(&self, table: &str, dialect: &dyn Dialect) -> Option<String> {
+        Some(dialect.drop_table(table, false, false))
+    }
+
+    fn column_effects(&self) -> Vec<ColumnEffect> {
+        self.columns
+            .iter()
+            .map(|column| ColumnEffect::Add(column.name.clone()))
+            .collect()
+    }
+}
+
+/// Drops the migration's table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DropTable {
+    pub if_exists: bool,
+    /// Also drop dependent objects. Only Postgres acts on this.
+    pub cascade: bool,
+}
+
+impl MigrationStep for DropTable {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(dialect.drop_table(table, self.if_exists, self.cascade))
+    }
+
+    fn ignored_options(&self, dialect: &dyn Dialect) -> Vec<&'static str> {
+        if self.cascade && !dialect.supports(Feature::DropCascade) {
+            vec!["cascade"]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn risk_profile(&self) -> RiskProfile {
+        RiskProfile {
+            destructive: true,
+            ..RiskProfile::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_type::DataType;
+    use crate::dialect::{MssqlDialect, MySqlDialect, PostgresDialect, SqliteDialect};
+
+    fn users() -> CreateTable {
+        CreateTable {
+            columns: vec![
+                Column {
+                    name: "id".to_string(),
+                    data_type: DataType::BigInt,
+                    nullable: false,
+                    auto_increment: true,
+                    generated: None,
+                    collation: None,
+                },
+                Column {
+                    name: "email".to_string(),
+                    data_type: DataType::Varchar(255),
+                    nullable: false,
+                    auto_increment: false,
+                    generated: None,
+                    collation: None,
+                },
+            ],
+            primary_key: vec!["id".to_string()],
+            options: TableOptions::default(),
+        }
+    }
+
+    #[test]
+    fn creates_auto_increment_keys() {
+        let create = users();
+        assert_eq!(
+            create.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "CREATE TABLE users (id BIGINT NOT NULL AUTO_INCREMENT, \
+             email VARCHAR(255) NOT NULL, PRIMARY KEY (id));"
+        );
+        assert_eq!(
+            create
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "CREATE TABLE users (id BIGINT GENERATED BY DEFAULT AS IDENTITY NOT NULL, \
+             email VARCHAR(255) NOT NULL, PRIMARY KEY (id));"
+        );
+        assert_eq!(
+            create.generate_sql("users", &SqliteDialect::new()).unwrap(),
+            "CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, email TEXT NOT NULL);"
+        );
+        assert_eq!(
+            create.generate_sql("users", &MssqlDialect::new()).unwrap(),
+            "CREATE TABLE users (id BIGINT IDENTITY(1,1) NOT NULL, \
+             email VARCHAR(255) NOT NULL, PRIMARY KEY (id));"
+        );
+    }
+
+    #[test]
+    fn appends_table_options_on_mysql_only() {
+        let create = CreateTable {
+            options: TableOptions {
+                engine: Some("InnoDB".to_string()),
+                charset: Some("utf8mb4".to_string()),
+                collation: Some("utf8mb4_unicode_ci".to_string()),
+            },
+            ..users()
+        };
+        assert_eq!(
+            create.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "CREATE TABLE users (id BIGINT NOT NULL AUTO_INCREMENT, \
+             email VARCHAR(255) NOT NULL, PRIMARY KEY (id)) \
+             ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;"
+        );
+        assert!(create.ignored_options(&MySqlDialect::new()).is_empty());
+
+        assert_eq!(
+            create
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            users()
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap()
+        );
+        assert_eq!(
+            create.ignored_options(&PostgresDialect::new()),
+            vec!["table options"]
+        );
+    }
+
+    #[test]
+    fn drops_the_table_on_rollback() {
+        assert_eq!(
+            users()
+                .rollback_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "DROP TABLE users;"
+        );
+    }
+    #[test]
+    fn renders_table_refs() {
+        assert_eq!(TableRef::from("users").to_string(), "users");
+        assert_eq!(
+            TableRef::from(("analytics", "users")).to_string(),
+            "analytics.users"
+        );
+    }
+
+    #[test]
+    fn creates_schema() {
+        let create = CreateSchema {
+            name: "analytics".to_string(),
+            if_not_exists: false,
+        };
+        let postgres = PostgresDialect::new();
+        assert_eq!(
+            create.generate_sql("users", &postgres).unwrap(),
+            "CREATE SCHEMA analytics;"
+        );
+        assert_eq!(
+            create.rollback_sql("users", &postgres).unwrap(),
+            "DROP SCHEMA analytics;"
+        );
+
+        let create = CreateSchema {
+            if_not_exists: true,
+            ..create
+        };
+        assert_eq!(
+            create.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            "CREATE DATABASE IF NOT EXISTS analytics;"
+        );
+        assert_eq!(create.rollback_sql("users", &MySqlDialect::new()), None);
+        assert_eq!(
+            create.generate_sql("users", &MssqlDialect::new()).unwrap(),
+            "IF SCHEMA_ID(N'analytics') IS NULL EXEC('CREATE SCHEMA analytics');"
+        );
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use super::*;
+
+    #[test]
+    fn drop_table_round_trips() {
+        let drop = DropTable {
+            if_exists: true,
+            cascade: false,
+        };
+        let json = serde_json::to_string(&drop).unwrap();
+        assert_eq!(serde_json::from_str::<DropTable>(&json).unwrap(), drop);
+    }
+}
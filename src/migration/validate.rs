@@ -0,0 +1,310 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use super::Migration;
+
+/// How an operation touches a column, as seen by [`Migration::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnEffect {
+    Add(String),
+    Drop(String),
+    Rename {
+        from: String,
+        to: String,
+    },
+    /// The column is read or modified in place and must still exist.
+    Reference(String),
+}
+
+/// A problem found by [`Migration::validate`]. `index` is the position of
+/// the offending operation in the migration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    AddedThenDropped { index: usize, column: String },
+    RenamedAfterDrop { index: usize, column: String },
+    DuplicateName { index: usize, column: String },
+    UsedAfterDrop { index: usize, column: String },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::AddedThenDropped { index, column } => write!(
+                f,
+                "operation {}: drops column `{}` which was added earlier in the same migration",
+                index, column
+            ),
+            ValidationError::RenamedAfterDrop { index, column } => write!(
+                f,
+                "operation {}: renames column `{}` which was dropped earlier",
+                index, column
+            ),
+            ValidationError::DuplicateName { index, column } => write!(
+                f,
+                "operation {}: column `{}` is already created by an earlier operation",
+                index, column
+            ),
+            ValidationError::UsedAfterDrop { index, column } => write!(
+                f,
+                "operation {}: references column `{}` which was dropped earlier",
+                index, column
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ColumnState {
+    Added,
+    /// Given its name by a rename, so it exists but was not created here.
+    Renamed,
+    Dropped,
+}
+
+impl Migration {
+    /// Checks the operations for conflicts that the database would reject,
+    /// returning every problem found rather than stopping at the first.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut columns: HashMap<String, ColumnState> = HashMap::new();
+        let mut errors = Vec::new();
+
+        for (index, operation) in self.operations.iter().enumerate() {
+            for effect in operation.column_effects() {
+                match effect {
+                    ColumnEffect::Add(column) => {
+                        if matches!(
+                            columns.get(&column),
+                            Some(ColumnState::Added | ColumnState::Renamed)
+                        ) {
+                            errors.push(ValidationError::DuplicateName {
+                                index,
+                                column: column.clone(),
+                            });
+                        }
+                        columns.insert(column, ColumnState::Added);
+                    }
+                    ColumnEffect::Drop(column) => {
+                        match columns.get(&column) {
+                            Some(ColumnState::Added) => {
+                                errors.push(ValidationError::AddedThenDropped {
+                                    index,
+                                    column: column.clone(),
+                                })
+                            }
+                            Some(ColumnState::Dropped) => {
+                                errors.push(ValidationError::UsedAfterDrop {
+                                    index,
+                                    column: column.clone(),
+                                })
+                            }
+                            Some(ColumnState::Renamed) | None => {}
+                        }
+                        columns.insert(column, ColumnState::Dropped);
+                    }
+                    ColumnEffect::Rename { from, to } => {
+                        if columns.get(&from) == Some(&ColumnState::Dropped) {
+                            errors.push(ValidationError::RenamedAfterDrop {
+                                index,
+                                column: from.clone(),
+                            });
+                        }
+                        if matches!(
+                            columns.get(&to),
+                            Some(ColumnState::Added | ColumnState::Renamed)
+                        ) {
+                            errors.push(ValidationError::DuplicateName {
+                                index,
+                                column: to.clone(),
+                            });
+                        }
+                        // A column added earlier in the migration is still
+                        // one added here under its new name.
+                        let state = match columns.get(&from) {
+                            Some(ColumnState::Added) => ColumnState::Added,
+                            _ => ColumnState::Renamed,
+                        };
+                        columns.insert(from, ColumnState::Dropped);
+                        columns.insert(to, state);
+                    }
+                    ColumnEffect::Reference(column) => {
+                        if columns.get(&column) == Some(&ColumnState::Dropped) {
+                            errors.push(ValidationError::UsedAfterDrop { index, column });
+                        }
+                    }
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_type::{Column, DataType};
+    use crate::dialect::PostgresDialect;
+    use crate::migration::{
+        AddColumn, DropColumn, Operator, RenameColumn, UpdateColumnData, UpdateValue,
+        WhereCondition,
+    };
+
+    fn add(name: &str) -> AddColumn {
+        AddColumn {
+            column: Column {
+                name: name.to_string(),
+                data_type: DataType::Text,
+                nullable: true,
+                auto_increment: false,
+                generated: None,
+                collation: None,
+            },
+            position: None,
+            if_not_exists: false,
+        }
+    }
+
+    fn drop(name: &str) -> DropColumn {
+        DropColumn {
+            column: name.to_string(),
+            if_exists: false,
+            cascade: false,
+        }
+    }
+
+    fn rename(old_name: &str, new_name: &str) -> RenameColumn {
+        RenameColumn {
+            old_name: old_name.to_string(),
+            new_name: new_name.to_string(),
+            definition: None,
+        }
+    }
+
+    #[test]
+    fn accepts_independent_operations() {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration
+            .add_operation(add("email"))
+            .add_operation(rename("phone", "contact_number"))
+            .add_operation(drop("legacy_flag"));
+
+        assert_eq!(migration.validate(), Ok(()));
+    }
+
+    #[test]
+    fn reports_drop_of_added_column() {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration
+            .add_operation(add("email"))
+            .add_operation(drop("email"));
+
+        assert_eq!(
+            migration.validate(),
+            Err(vec![ValidationError::AddedThenDropped {
+                index: 1,
+                column: "email".to_string(),
+            }])
+        );
+    }
+
+    #[test]
+    fn reports_rename_of_dropped_column() {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration
+            .add_operation(drop("phone"))
+            .add_operation(rename("phone", "contact_number"));
+
+        assert_eq!(
+            migration.validate(),
+            Err(vec![ValidationError::RenamedAfterDrop {
+                index: 1,
+                column: "phone".to_string(),
+            }])
+        );
+    }
+
+    #[test]
+    fn accepts_drop_of_renamed_column() {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration
+            .add_operation(rename("phone", "contact_number"))
+            .add_operation(drop("contact_number"));
+
+        assert_eq!(migration.validate(), Ok(()));
+    }
+
+    #[test]
+    fn reports_drop_of_added_column_after_rename() {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration
+            .add_operation(add("email"))
+            .add_operation(rename("email", "contact_email"))
+            .add_operation(drop("contact_email"));
+
+        assert_eq!(
+            migration.validate(),
+            Err(vec![ValidationError::AddedThenDropped {
+                index: 2,
+                column: "contact_email".to_string(),
+            }])
+        );
+    }
+
+    #[test]
+    fn reports_two_operations_creating_the_same_column() {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration
+            .add_operation(add("contact_number"))
+            .add_operation(rename("phone", "contact_number"));
+
+        assert_eq!(
+            migration.validate(),
+            Err(vec![ValidationError::DuplicateName {
+                index: 1,
+                column: "contact_number".to_string(),
+            }])
+        );
+    }
+
+    #[test]
+    fn reports_update_of_dropped_column() {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration
+            .add_operation(drop("status"))
+            .add_operation(UpdateColumnData {
+                column: "state".to_string(),
+                value: UpdateValue::Column("status".to_string()),
+                conditions: vec![WhereCondition {
+                    column: "status".to_string(),
+                    operator: Operator::Eq,
+                    value: UpdateValue::Fixed("'active'".to_string()),
+                }],
+            });
+
+        assert_eq!(
+            migration.validate(),
+            Err(vec![ValidationError::UsedAfterDrop {
+                index: 1,
+                column: "status".to_string(),
+            }])
+        );
+    }
+
+    #[test]
+    fn reports_every_problem() {
+        let mut migration = Migration::new("users", Box::new(PostgresDialect::new()));
+        migration
+            .add_operation(add("email"))
+            .add_operation(drop("email"))
+            .add_operation(drop("phone"))
+            .add_operation(rename("phone", "mobile"));
+
+        assert_eq!(migration.validate().unwrap_err().len(), 2);
+    }
+}
@@ -0,0 +1,280 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::statement::{joined, of_step};
+use super::{MigrationStep, Statement};
+use crate::dialect::Dialect;
+use crate::error::MigrationError;
+
+/// Creates a view. Views carry their own name, so the migration's table is
+/// ignored. `query` is raw SQL and is not escaped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CreateView {
+    pub name: String,
+    pub query: String,
+    /// Replace an existing view of the same name instead of failing.
+    pub or_replace: bool,
+}
+
+impl MigrationStep for CreateView {
+    fn generate_sql(&self, table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(joined(&self.statements(table, dialect)?))
+    }
+
+    fn statements(
+        &self,
+        table: &str,
+        dialect: &dyn Dialect,
+    ) -> Result<Vec<Statement>, MigrationError> {
+        let sql = dialect.create_view(&self.name, &self.query, self.or_replace);
+        Ok(of_step(self, table, sql))
+    }
+}
+
+/// Drops a view. The migration's table is ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DropView {
+    pub name: String,
+    pub if_exists: bool,
+}
+
+impl MigrationStep for DropView {
+    fn generate_sql(&self, _table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        Ok(dialect.drop_view(&self.name, self.if_exists))
+    }
+}
+
+/// Creates a materialized view, which stores the query's result until it is
+/// refreshed. Only Postgres has them; the migration's table is ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CreateMaterializedView {
+    pub name: String,
+    pub query: String,
+    /// Run the query now. Without it the view is unreadable until its
+    /// first refresh.
+    pub with_data: bool,
+}
+
+impl MigrationStep for CreateMaterializedView {
+    fn generate_sql(&self, _table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        dialect.create_materialized_view(&self.name, &self.query, self.with_data)
+    }
+
+    fn rollback_sql(&self, _table: &str, dialect: &dyn Dialect) -> Option<String> {
+        dialect.drop_materialized_view(&self.name, false).ok()
+    }
+}
+
+/// Re-runs a materialized view's query. The migration's table is ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RefreshMaterializedView {
+    pub name: String,
+    /// Refresh without blocking reads of the view. Postgres requires a
+    /// unique index on the view for this.
+    pub concurrently: bool,
+}
+
+impl MigrationStep for RefreshMaterializedView {
+    fn generate_sql(&self, _table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        dialect.refresh_materialized_view(&self.name, self.concurrently)
+    }
+}
+
+/// Drops a materialized view. The migration's table is ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DropMaterializedView {
+    pub name: String,
+    pub if_exists: bool,
+}
+
+impl MigrationStep for DropMaterializedView {
+    fn generate_sql(&self, _table: &str, dialect: &dyn Dialect) -> Result<String, MigrationError> {
+        dialect.drop_materialized_view(&self.name, self.if_exists)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{MySqlDialect, PostgresDialect, SqliteDialect};
+
+    fn active_users(or_replace: bool) -> CreateView {
+        CreateView {
+            name: "active_users".to_string(),
+            query: "SELECT id, email FROM users WHERE status = 'active'".to_string(),
+            or_replace,
+        }
+    }
+
+    #[test]
+    fn creates_view() {
+        let sql =
+            "CREATE VIEW active_users AS SELECT id, email FROM users WHERE status = 'active';";
+        let view = active_users(false);
+        assert_eq!(
+            view.generate_sql("users", &PostgresDialect::new()).unwrap(),
+            sql
+        );
+        assert_eq!(
+            view.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            sql
+        );
+    }
+
+    #[test]
+    fn replaces_view() {
+        let sql = "CREATE OR REPLACE VIEW active_users AS \
+                   SELECT id, email FROM users WHERE status = 'active';";
+        let view = active_users(true);
+        assert_eq!(
+            view.generate_sql("users", &PostgresDialect::new()).unwrap(),
+            sql
+        );
+        assert_eq!(
+            view.generate_sql("users", &MySqlDialect::new()).unwrap(),
+            sql
+        );
+        assert_eq!(
+            view.statements("users", &SqliteDialect::new()).unwrap(),
+            [
+                "DROP VIEW IF EXISTS active_users;",
+                "CREATE VIEW active_users AS SELECT id, email FROM users WHERE status = 'active';"
+            ]
+        );
+    }
+
+    #[test]
+    fn drops_view() {
+        let drop = DropView {
+            name: "active_users".to_string(),
+            if_exists: true,
+        };
+        for dialect in [
+            &PostgresDialect::new() as &dyn Dialect,
+            &MySqlDialect::new(),
+        ] {
+            assert_eq!(
+                drop.generate_sql("users", dialect).unwrap(),
+                "DROP VIEW IF EXISTS active_users;"
+            );
+        }
+
+        let drop = DropView {
+            if_exists: false,
+            ..drop
+        };
+        for dialect in [
+            &PostgresDialect::new() as &dyn Dialect,
+            &MySqlDialect::new(),
+        ] {
+            assert_eq!(
+                drop.generate_sql("users", dialect).unwrap(),
+                "DROP VIEW active_users;"
+            );
+        }
+    }
+    #[test]
+    fn creates_materialized_view() {
+        let create = CreateMaterializedView {
+            name: "daily_signups".to_string(),
+            query: "SELECT created_at::date AS day, count(*) FROM users GROUP BY 1".to_string(),
+            with_data: false,
+        };
+        let dialect = PostgresDialect::new();
+        assert_eq!(
+            create.generate_sql("users", &dialect).unwrap(),
+            "CREATE MATERIALIZED VIEW daily_signups AS \
+             SELECT created_at::date AS day, count(*) FROM users GROUP BY 1 WITH NO DATA;"
+        );
+        assert_eq!(
+            create.rollback_sql("users", &dialect).unwrap(),
+            "DROP MATERIALIZED VIEW daily_signups;"
+        );
+        assert_eq!(
+            create.generate_sql("users", &MySqlDialect::new()),
+            Err(MigrationError::unsupported(
+                "mysql",
+                "CREATE MATERIALIZED VIEW"
+            ))
+        );
+    }
+
+    #[test]
+    fn refreshes_materialized_view_concurrently() {
+        let refresh = RefreshMaterializedView {
+            name: "daily_signups".to_string(),
+            concurrently: true,
+        };
+        assert_eq!(
+            refresh
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "REFRESH MATERIALIZED VIEW CONCURRENTLY daily_signups;"
+        );
+
+        let refresh = RefreshMaterializedView {
+            concurrently: false,
+            ..refresh
+        };
+        assert_eq!(
+            refresh
+                .generate_sql("users", &PostgresDialect::new())
+                .unwrap(),
+            "REFRESH MATERIALIZED VIEW daily_signups;"
+        );
+        assert_eq!(
+            refresh.generate_sql("users", &MySqlDialect::new()),
+            Err(MigrationError::unsupported(
+                "mysql",
+                "REFRESH MATERIALIZED VIEW"
+            ))
+        );
+    }
+
+    #[test]
+    fn drops_materialized_view() {
+        let drop = DropMaterializedView {
+            name: "daily_signups".to_string(),
+            if_exists: true,
+        };
+        assert_eq!(
+            drop.generate_sql("users", &PostgresDialect::new()).unwrap(),
+            "DROP MATERIALIZED VIEW IF EXISTS daily_signups;"
+        );
+        assert_eq!(
+            drop.generate_sql("users", &MySqlDialect::new()),
+            Err(MigrationError::unsupported(
+                "mysql",
+                "DROP MATERIALIZED VIEW"
+            ))
+        );
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use super::*;
+
+    #[test]
+    fn views_round_trip() {
+        let create = CreateView {
+            name: "active_users".to_string(),
+            query: "SELECT id FROM users".to_string(),
+            or_replace: true,
+        };
+        let json = serde_json::to_string(&create).unwrap();
+        assert_eq!(serde_json::from_str::<CreateView>(&json).unwrap(), create);
+
+        let drop = DropView {
+            name: "active_users".to_string(),
+            if_exists: false,
+        };
+        let json = serde_json::to_string(&drop).unwrap();
+        assert_eq!(serde_json::from_str::<DropView>(&json).unwrap(), drop);
+    }
+}